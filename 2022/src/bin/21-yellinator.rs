@@ -1,4 +1,5 @@
 use aoc::*;
+use graph_export::{Export, Graph};
 use itertools::Itertools;
 use std::{cell::RefCell, collections::HashMap, fs::File, io::Write, path::Path, rc::Rc};
 
@@ -397,14 +398,19 @@ impl Troop {
 
     #[allow(dead_code)]
     fn dot(&self, graph_name: &str) -> String {
-        let mut result = format!("graph {graph_name} {{\n");
+        let mut graph = self.graph();
+        graph.name = graph_name.to_string();
+        graph.to_dot()
+    }
+}
+
+impl Export for Troop {
+    fn graph(&self) -> Graph {
+        let mut graph = Graph::undirected("troop");
         for (name, monkey) in self.monkeys.iter() {
             match monkey.as_ref() {
                 Monkey::Constant { value } => {
-                    result.push_str(
-                        format!("\t\"{graph_name}.{name}\" [label=\"{name}\\n{value}\"];\n")
-                            .as_str(),
-                    );
+                    graph.node(name).label(format!("{name}\n{value}"));
                 }
                 Monkey::Math {
                     op: Op { name: op_name, .. },
@@ -412,31 +418,19 @@ impl Troop {
                     right,
                     ..
                 } => {
-                    result.push_str(format!("\t\"{graph_name}.{name}\" [label=\"{name}\\n{op_name}\", ordering=\"out\"];\n").as_str());
-                    result.push_str(
-                        format!(
-                            "\t\t\"{graph_name}.{name}\" -- \"{graph_name}.{left}\" [label=L];\n"
-                        )
-                        .as_str(),
-                    );
-                    result.push_str(
-                        format!(
-                            "\t\t\"{graph_name}.{name}\" -- \"{graph_name}.{right}\" [label=R];\n"
-                        )
-                        .as_str(),
-                    );
+                    graph
+                        .node(name)
+                        .label(format!("{name}\n{op_name}"))
+                        .attr("ordering", "out");
+                    graph.edge(name, left).label("L");
+                    graph.edge(name, right).label("R");
                 }
                 Monkey::Human => {
-                    result.push_str(
-                        format!("\t\"{graph_name}.humn\" [label=\"HELPME!\"];\n").as_str(),
-                    );
+                    graph.node("humn").label("HELPME!");
                 }
             }
         }
-
-        result.push('}');
-
-        result
+        graph
     }
 }
 
@@ -1,6 +1,8 @@
 use aoc::*;
+use grids::{Bounds, Wrap};
 use image::{ImageBuffer, RgbImage};
 use priority_queue::PriorityQueue;
+use scrubber::Scrubber;
 use std::{
     cell::RefCell,
     collections::{HashMap, HashSet, VecDeque},
@@ -67,24 +69,12 @@ impl Map {
         }
 
         // Calculate blizzard positions, find if any is at that point and time
-        // Top left is offset by 1 (at beginning and end) to account for top/left
-        // Modulus is offset by 2 to account for both walls in each direction
-        let mut is_occupied = false;
-
-        let x_loop_fix = ((self.width - 2) * (1 + t / (self.width - 2))) as isize;
-        let y_loop_fix = ((self.height - 2) * (1 + t / (self.height - 2))) as isize;
-
-        for (origin, delta) in self.blizzards.iter() {
-            if x == 1
-                + (x_loop_fix + origin.x - 1 + delta.x * t as isize) as usize % (self.width - 2)
-                && y == 1
-                    + (y_loop_fix + origin.y - 1 + delta.y * t as isize) as usize
-                        % (self.height - 2)
-            {
-                is_occupied = true;
-                break;
-            }
-        }
+        // Blizzards wrap around inside the walls
+        let bounds = Bounds::from_size((0, 0), self.width, self.height);
+        let is_occupied = self.blizzards.iter().any(|(origin, delta)| {
+            let p = (origin.x + delta.x * t as isize, origin.y + delta.y * t as isize);
+            Wrap::Interior(1).resolve(bounds, p) == Some((x as isize, y as isize))
+        });
 
         // Update cache and return
         self.occupied.borrow_mut().insert((x, y, t), is_occupied);
@@ -141,6 +131,28 @@ impl Map {
     }
 }
 
+// Frames for ffmpeg, and the same frames in {name}.html to step through minute by minute
+fn render(map: &Map, path: &VecDeque<(usize, usize)>, name: &str) {
+    let mut scrubber = Scrubber::new(map.width as u32, map.height as u32)
+        .fps(10)
+        .scale(4);
+
+    for t in 0..path.len() {
+        let frame = map.render_path(t, path);
+        frame
+            .save(format!("{:08}.png", t))
+            .expect("failed to save frame");
+        scrubber
+            .push(frame.as_raw(), Some(&format!("minute {t}")))
+            .expect("failed to add frame");
+    }
+
+    scrubber
+        .save(format!("{name}.html"))
+        .expect("failed to save scrubber");
+    make_mp4(10, String::from(name));
+}
+
 fn part1(filename: &Path) -> String {
     let map = Map::from(filename);
 
@@ -212,12 +224,7 @@ fn part1(filename: &Path) -> String {
         }
 
         if env::var("AOC24_RENDER").is_ok() {
-            for t in 0..path.len() {
-                map.render_path(t, &path)
-                    .save(format!("{:08}.png", t))
-                    .expect("failed to save frame");
-            }
-            make_mp4(10, String::from("aoc24-1-path"));
+            render(&map, &path, "aoc24-1-path");
         }
     }
 
@@ -315,12 +322,7 @@ fn part2(filename: &Path) -> String {
         }
 
         if env::var("AOC24_RENDER").is_ok() {
-            for t in 0..path.len() {
-                map.render_path(t, &path)
-                    .save(format!("{:08}.png", t))
-                    .expect("failed to save frame");
-            }
-            make_mp4(10, String::from("aoc24-2-path"));
+            render(&map, &path, "aoc24-2-path");
         }
     }
 
@@ -1,3 +1,4 @@
+use grids::{Bounds, Dense, GridLike, Pos};
 use std::env;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
@@ -63,11 +64,10 @@ pub fn aoc_test(day: &str, f: FnPart, expected: &str) {
 }
 
 /* ----- A 2D matrix of any kind of value ----- */
+// Row-major everywhere (index() used to be column-major while [[x, y]] wasn't), on top of grids::Dense
 #[derive(Clone, Debug)]
 pub struct Matrix<T> {
-    data: Vec<T>,
-    width: usize,
-    height: usize,
+    cells: Dense<T>,
 }
 
 impl<T> Matrix<T>
@@ -76,35 +76,37 @@ where
 {
     pub fn new(width: usize, height: usize) -> Self {
         Matrix::<T> {
-            data: vec![T::default(); width * height],
-            width,
-            height,
+            cells: Dense::new(width, height),
         }
     }
 
     pub fn width(&self) -> usize {
-        self.width
+        self.cells.width()
     }
 
     pub fn height(&self) -> usize {
-        self.height
+        self.cells.height()
     }
 
     pub fn in_bounds(&self, x: usize, y: usize) -> bool {
-        x < self.width && y < self.height
+        x < self.width() && y < self.height()
     }
 
     pub fn index(&self, x: usize, y: usize) -> &T {
-        &self.data[x * self.width + y]
+        &self[[x, y]]
     }
 
     pub fn index_mut(&mut self, x: usize, y: usize) -> &mut T {
-        &mut self.data[x * self.width + y]
+        &mut self[[x, y]]
     }
 
     pub fn at(&self, p: &Point) -> &T {
         &self[[p.x as usize, p.y as usize]]
     }
+
+    pub fn as_dense(&self) -> &Dense<T> {
+        &self.cells
+    }
 }
 
 impl<T> Index<[usize; 2]> for Matrix<T>
@@ -114,7 +116,7 @@ where
     type Output = T;
 
     fn index(&self, [x, y]: [usize; 2]) -> &Self::Output {
-        &self.data[y * self.width + x]
+        &self.cells[(x as isize, y as isize)]
     }
 }
 
@@ -123,7 +125,27 @@ where
     T: core::fmt::Debug,
 {
     fn index_mut(&mut self, [x, y]: [usize; 2]) -> &mut Self::Output {
-        &mut self.data[y * self.width + x]
+        &mut self.cells[(x as isize, y as isize)]
+    }
+}
+
+impl<T> GridLike for Matrix<T> {
+    type Item = T;
+
+    fn bounds(&self) -> Bounds {
+        self.cells.bounds()
+    }
+
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        self.cells.set(p, value)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.cells()
     }
 }
 
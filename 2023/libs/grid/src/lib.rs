@@ -1,11 +1,15 @@
 use bounds::Bounds;
-use fxhash::FxHashMap;
+use grids::{GridLike, Pos, Sparse};
 use point::Point;
 
+// The backends underneath, and wrapping (Wrap, Wrapped) for infinite and toroidal grids
+pub use grids;
+
+// Point-addressed, on top of grids::Sparse (which also makes it GridLike)
+// Bounds start out covering the origin, as they always have here
 #[derive(Debug)]
 pub struct Grid<T> {
-    pub bounds: Bounds,
-    data: FxHashMap<Point, T>,
+    cells: Sparse<T>,
 }
 
 impl<T: Default + Copy + Clone> Default for Grid<T> {
@@ -17,8 +21,7 @@ impl<T: Default + Copy + Clone> Default for Grid<T> {
 impl<T: Copy + Clone> Grid<T> {
     pub fn new() -> Self {
         Self {
-            bounds: Bounds::default(),
-            data: FxHashMap::default(),
+            cells: Sparse::with_bounds(grids::Bounds::new((0, 0), (0, 0))),
         }
     }
 
@@ -26,130 +29,148 @@ impl<T: Copy + Clone> Grid<T> {
         let mut grid = Self::new();
         for (y, line) in s.lines().enumerate() {
             for (x, c) in line.chars().enumerate() {
-                if let Some(c) = from_c(c) {
-                    grid.insert(
-                        Point {
-                            x: x as isize,
-                            y: y as isize,
-                        },
-                        c,
-                    );
+                if let Some(v) = from_c(c) {
+                    grid.cells.insert((x as isize, y as isize), v);
                 }
             }
         }
         grid
     }
 
-    pub fn to_string(&self, empty_c: char, to_c: impl Fn(&T) -> char) -> String {
-        let mut s = String::new();
-
-        for y in self.bounds.min_y..=self.bounds.max_y {
-            for x in self.bounds.min_x..=self.bounds.max_x {
-                let p = Point { x, y };
-                if let Some(c) = self.get(&p).map(&to_c) {
-                    s.push(c);
-                } else {
-                    s.push(empty_c);
-                }
-            }
-            s.push('\n');
+    // In Points, unlike GridLike::bounds
+    pub fn bounds(&self) -> Bounds {
+        let bounds = self.cells.bounds();
+        Bounds {
+            min_x: bounds.min_x,
+            max_x: bounds.max_x,
+            min_y: bounds.min_y,
+            max_y: bounds.max_y,
         }
+    }
 
-        s
+    pub fn to_string(&self, empty_c: char, to_c: impl Fn(&T) -> char) -> String {
+        GridLike::to_string(&self.cells, empty_c, to_c)
     }
 
+    // RGBA (unlike GridLike::render) so the empty cells can be see-through
     pub fn to_image(
         &self,
         empty_c: image::Rgba<u8>,
         to_c: impl Fn(&T) -> image::Rgba<u8>,
     ) -> image::RgbaImage {
-        let width = self.bounds.max_x - self.bounds.min_x + 1;
-        let height = self.bounds.max_y - self.bounds.min_y + 1;
+        let bounds = self.bounds();
+        let width = bounds.max_x - bounds.min_x + 1;
+        let height = bounds.max_y - bounds.min_y + 1;
 
         let mut image = image::RgbaImage::new(width as u32, height as u32);
 
-        for y in self.bounds.min_y..=self.bounds.max_y {
-            for x in self.bounds.min_x..=self.bounds.max_x {
-                let p = Point { x, y };
-                if let Some(c) = self.get(&p).map(&to_c) {
-                    image.put_pixel(
-                        (x - self.bounds.min_x) as u32,
-                        (y - self.bounds.min_y) as u32,
-                        c,
-                    );
-                } else {
-                    image.put_pixel(
-                        (x - self.bounds.min_x) as u32,
-                        (y - self.bounds.min_y) as u32,
-                        empty_c,
-                    );
-                }
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                let c = self.get(&Point { x, y }).map_or(empty_c, &to_c);
+                image.put_pixel(
+                    (x - bounds.min_x) as u32,
+                    (y - bounds.min_y) as u32,
+                    c,
+                );
             }
         }
 
         image
     }
+    // Scaled up (nearest neighbour) with the overlay drawn on top afterwards, so text and lines stay sharp
+    // Overlay coordinates are relative to the top left of the bounds, like the image's pixels
+    pub fn to_image_with_overlay(
+        &self,
+        empty_c: image::Rgba<u8>,
+        to_c: impl Fn(&T) -> image::Rgba<u8>,
+        scale: u32,
+        overlay: &overlay::Overlay,
+    ) -> image::RgbaImage {
+        let image = self.to_image(empty_c, to_c);
+        let mut image = image::imageops::resize(
+            &image,
+            image.width() * scale,
+            image.height() * scale,
+            image::imageops::FilterType::Nearest,
+        );
+
+        let (width, height) = image.dimensions();
+        overlay.draw(width, height, scale, &mut |x, y, [r, g, b]| {
+            image.put_pixel(x, y, image::Rgba([r, g, b, 255]))
+        });
+        image
+    }
 
     pub fn get(&self, point: &Point) -> Option<&T> {
-        self.data.get(point)
+        self.cells.get((*point).into())
     }
 
     pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
-        self.data.get_mut(point)
+        self.cells.get_mut((*point).into())
     }
 
     pub fn insert(&mut self, point: Point, value: T) {
-        self.bounds.include(point);
-        self.data.insert(point, value);
+        self.cells.insert(point.into(), value);
     }
 
     pub fn remove(&mut self, point: &Point) -> Option<T> {
-        self.data.remove(point)
+        self.cells.remove((*point).into())
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
-        self.data.iter()
+    // Row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, v)| (p.into(), v))
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Point, &mut T)> {
-        self.data.iter_mut()
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.cells.iter_mut().map(|(p, v)| (p.into(), v))
     }
 
-    pub fn iter_points(&self) -> impl Iterator<Item = &Point> {
-        self.data.keys()
+    pub fn iter_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.positions().map(Point::from)
     }
 
     pub fn iter_values(&self) -> impl Iterator<Item = &T> {
-        self.data.values()
+        self.cells.values()
     }
 
     pub fn iter_values_mut(&mut self) -> impl Iterator<Item = &mut T> {
-        self.data.values_mut()
+        self.cells.values_mut()
     }
 
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.cells.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.cells.is_empty()
     }
 
     // Flood fill points not currently in the grid with the given value
     // Will not extend bounds
     pub fn flood_fill(&mut self, pt: Point, arg: T) {
-        let mut stack = vec![pt];
+        assert!(self.bounds().contains(&pt));
+        self.cells.fill_empty(pt.into(), arg);
+    }
+}
 
-        while let Some(pt) = stack.pop() {
-            assert!(self.bounds.contains(&pt));
+impl<T> GridLike for Grid<T> {
+    type Item = T;
 
-            if self.get(&pt).is_none() {
-                self.insert(pt, arg);
-                stack.push(pt + Point::new(0, 1));
-                stack.push(pt + Point::new(0, -1));
-                stack.push(pt + Point::new(1, 0));
-                stack.push(pt + Point::new(-1, 0));
-            }
-        }
+    fn bounds(&self) -> grids::Bounds {
+        self.cells.bounds()
+    }
+
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        self.cells.insert(p, value);
+        true
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.cells()
     }
 }
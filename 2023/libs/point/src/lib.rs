@@ -71,6 +71,19 @@ impl std::ops::Mul<Point> for isize {
     }
 }
 
+// To and from the plain (x, y) the shared grids crate uses
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (isize, isize) {
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
 impl std::fmt::Display for Point {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use std::io;
+
+use day10::types::*;
+use grid::grids::regions;
+
+aoc_test::generate!{day10_part2_enclosed_test_10 as "test/10.txt" => "1"}
+aoc_test::generate!{day10_part2_enclosed_test_10b as "test/10b.txt" => "1"}
+aoc_test::generate!{day10_part2_enclosed_test_10c as "test/10c.txt" => "1"}
+aoc_test::generate!{day10_part2_enclosed_test_10d as "test/10d.txt" => "4"}
+aoc_test::generate!{day10_part2_enclosed_test_10e as "test/10e.txt" => "8"}
+aoc_test::generate!{day10_part2_enclosed_test_10f as "test/10f.txt" => "10"}
+aoc_test::generate!{day10_part2_enclosed_10 as "10.txt" => "455"}
+
+fn main() {
+    let stdin = io::stdin();
+    let input = io::read_to_string(stdin.lock()).expect("read input");
+    let result = process(input.as_str()).expect("no errors");
+    println!("{}", result);
+}
+
+fn process(input: &str) -> Result<String> {
+    let map = Map::from(input);
+
+    // The main loop in order, then count everything it goes around
+    // Squeezing between pipes comes for free: only the loop's crossings count, not which cells it fills
+    let path = map
+        .iter()
+        .map(|node| (node.x(), node.y()))
+        .collect::<Vec<_>>();
+
+    Ok(regions::enclosed(&path).len().to_string())
+}
@@ -86,7 +86,7 @@ pub(crate) fn illuminate(mirrors: &Grid<Mirror>, start: (Point, Direction)) -> G
             image::imageops::overlay(&mut foreground, &barely_black, 0, 0);
 
             for (p, _) in &points {
-                if mirrors.bounds.contains(p) {
+                if mirrors.bounds().contains(p) {
                     foreground.put_pixel(p.x as u32, p.y as u32, image::Rgba([255, 0, 0, 255]));
                 }
             }
@@ -99,7 +99,7 @@ pub(crate) fn illuminate(mirrors: &Grid<Mirror>, start: (Point, Direction)) -> G
         let mut next = vec![];
         for (p, d) in points {
             // Ignore points that have gone out of bounds
-            if !mirrors.bounds.contains(&p) {
+            if !mirrors.bounds().contains(&p) {
                 continue;
             }
 
@@ -44,7 +44,7 @@ pub(crate) fn illuminate(mirrors: &Grid<Mirror>, start: (Point, Direction)) -> G
 
     while let Some((p, d)) = queue.pop() {
         // Ignore points that have gone out of bounds
-        if !mirrors.bounds.contains(&p) {
+        if !mirrors.bounds().contains(&p) {
             continue;
         }
 
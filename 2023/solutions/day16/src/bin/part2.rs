@@ -31,13 +31,13 @@ fn process(input: &str) -> Result<String> {
     });
 
     let mut starts = Vec::new();
-    for x in mirrors.bounds.min_x..=mirrors.bounds.max_x {
-        starts.push((Point::new(x, mirrors.bounds.min_y), South));
-        starts.push((Point::new(x, mirrors.bounds.max_y), North));
+    for x in mirrors.bounds().min_x..=mirrors.bounds().max_x {
+        starts.push((Point::new(x, mirrors.bounds().min_y), South));
+        starts.push((Point::new(x, mirrors.bounds().max_y), North));
     }
-    for y in mirrors.bounds.min_y..=mirrors.bounds.max_y {
-        starts.push((Point::new(mirrors.bounds.min_x, y), East));
-        starts.push((Point::new(mirrors.bounds.max_x, y), West));
+    for y in mirrors.bounds().min_y..=mirrors.bounds().max_y {
+        starts.push((Point::new(mirrors.bounds().min_x, y), East));
+        starts.push((Point::new(mirrors.bounds().max_x, y), West));
     }
 
     Ok(starts
@@ -41,7 +41,7 @@ fn process(input: &str) -> Result<String> {
 
         // Base case, we're in the south-east corner
         // Direction here doesn't actually matter
-        if p.x == grid.bounds.max_x && p.y == grid.bounds.max_y {
+        if p.x == grid.bounds().max_x && p.y == grid.bounds().max_y {
             cache.insert(key, (0, South));
             return Some((0, South));
         }
@@ -60,7 +60,7 @@ fn process(input: &str) -> Result<String> {
         {
             let d_next = d.left();
             let p_next = p + Point::from(d_next);
-            if grid.bounds.contains(&p_next) {
+            if grid.bounds().contains(&p_next) {
                 cases.push((p_next, d_next, c));
             }
         }
@@ -69,7 +69,7 @@ fn process(input: &str) -> Result<String> {
         {
             let d_next = d.right();
             let p_next = p + Point::from(d_next);
-            if grid.bounds.contains(&p_next) {
+            if grid.bounds().contains(&p_next) {
                 cases.push((p_next, d_next, c));
             }
         }
@@ -77,7 +77,7 @@ fn process(input: &str) -> Result<String> {
         // Try going straight (if we haven't gone 3)
         if c < 3 {
             let p_next = p + Point::from(d);
-            if grid.bounds.contains(&p_next) {
+            if grid.bounds().contains(&p_next) {
                 cases.push((p_next, d, c + 1));
             }
         }
@@ -30,7 +30,7 @@ fn process(input: &str) -> Result<String> {
 
     let mut best = Grid::default();
     best.insert(
-        Point::new(grid.bounds.max_x, grid.bounds.max_y),
+        Point::new(grid.bounds().max_x, grid.bounds().max_y),
         State {
             score: 0,
             direction: East,
@@ -45,8 +45,8 @@ fn process(input: &str) -> Result<String> {
     for _i in 0..10 {
         let mut updated = false;
 
-        for x in (grid.bounds.min_x..=grid.bounds.max_x).rev() {
-            for y in (grid.bounds.min_y..=grid.bounds.max_y).rev() {
+        for x in (grid.bounds().min_x..=grid.bounds().max_x).rev() {
+            for y in (grid.bounds().min_y..=grid.bounds().max_y).rev() {
                 let p_current = Point::new(x, y);
                 let mut current_best = None;
 
@@ -27,7 +27,7 @@ fn best_path(
 
     // Base case, we're in the south-east corner
     // Direction here doesn't actually matter
-    if p.x == grid.bounds.max_x && p.y == grid.bounds.max_y {
+    if p.x == grid.bounds().max_x && p.y == grid.bounds().max_y {
         cache.insert((p, d, c), (0, South));
         return Some((0, South));
     }
@@ -52,7 +52,7 @@ fn best_path(
     let result = cases
         .iter()
         // Remove cases that go out of bounds
-        .filter(|(p, _, _)| grid.bounds.contains(p))
+        .filter(|(p, _, _)| grid.bounds().contains(p))
         // Remove cases that go straight too far
         .filter(|(_, _, c)| *c <= 3)
         // Remove cases we've already seen
@@ -95,8 +95,8 @@ fn process(input: &str) -> Result<String> {
     let mut cache = fxhash::FxHashMap::default();
 
     // Pre-populate the cache
-    for x in (grid.bounds.min_x..=grid.bounds.max_x).rev() {
-        for y in (grid.bounds.min_y..=grid.bounds.max_y).rev() {
+    for x in (grid.bounds().min_x..=grid.bounds().max_x).rev() {
+        for y in (grid.bounds().min_y..=grid.bounds().max_y).rev() {
             let p = Point::new(x, y);
             let _ = best_path(&grid, &mut cache, p, South, 0, vec![], 100);
         }
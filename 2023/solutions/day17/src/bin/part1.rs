@@ -39,7 +39,7 @@ fn process(input: &str) -> Result<String> {
             [s.direction.left(), s.direction, s.direction.right()]
                 .into_iter()
                 // Next point must be in bounds
-                .filter(|d| grid.bounds.contains(&(s.position + Point::from(*d))))
+                .filter(|d| grid.bounds().contains(&(s.position + Point::from(*d))))
                 // Can't go more than 3 in the same direction
                 .filter(|d| s.count < 3 || s.direction != *d)
                 // Generate the next state for each neighbor
@@ -55,11 +55,11 @@ fn process(input: &str) -> Result<String> {
         // heuristic function
         |&s| {
             s.position
-                .manhattan_distance(&Point::new(grid.bounds.max_x, grid.bounds.max_y))
+                .manhattan_distance(&Point::new(grid.bounds().max_x, grid.bounds().max_y))
                 as u32
         },
         // goal function
-        |&s| s.position.x == grid.bounds.max_x && s.position.y == grid.bounds.max_y,
+        |&s| s.position.x == grid.bounds().max_x && s.position.y == grid.bounds().max_y,
     );
 
     // Calculate total score
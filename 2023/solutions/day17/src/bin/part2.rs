@@ -40,7 +40,7 @@ fn process(input: &str) -> Result<String> {
             [s.direction.left(), s.direction, s.direction.right()]
                 .into_iter()
                 // Next point must be in bounds
-                .filter(|d| grid.bounds.contains(&(s.position + Point::from(*d))))
+                .filter(|d| grid.bounds().contains(&(s.position + Point::from(*d))))
                 // Must go 4 in a direction before turning
                 // Cannot go more than 10 in a direction
                 // count == 0 is a special case for the start
@@ -63,18 +63,18 @@ fn process(input: &str) -> Result<String> {
         // heuristic function
         |&s| {
             s.position
-                .manhattan_distance(&Point::new(grid.bounds.max_x, grid.bounds.max_y))
+                .manhattan_distance(&Point::new(grid.bounds().max_x, grid.bounds().max_y))
                 as u32
         },
         // goal function
         // New condition: must have moved at least 4 in a straight line to stop
-        |&s| s.position.x == grid.bounds.max_x && s.position.y == grid.bounds.max_y && s.count >= 4,
+        |&s| s.position.x == grid.bounds().max_x && s.position.y == grid.bounds().max_y && s.count >= 4,
     );
 
     // Calculate total score
     if let Some((_path, score)) = result {
-        // for y in grid.bounds.min_y..=grid.bounds.max_y {
-        //     for x in grid.bounds.min_x..=grid.bounds.max_x {
+        // for y in grid.bounds().min_y..=grid.bounds().max_y {
+        //     for x in grid.bounds().min_x..=grid.bounds().max_x {
         //         if let Some(s) = _path.iter().find(|s| s.position.x == x && s.position.y == y) {
         //             match s.direction {
         //                 North => print!("^"),
@@ -1,4 +1,5 @@
-use std::io;
+use graph_export::{Export, Format, Graph};
+use std::{env, io};
 
 use day20::{parse, types::*};
 
@@ -11,30 +12,34 @@ fn main() {
     let (s, modules) = parse::modules(&input).unwrap();
     assert_eq!(s.trim(), "");
 
-    println!("digraph G {{");
+    // dot (the default), mermaid or graphml
+    let format = env::args()
+        .nth(1)
+        .map_or(Ok(Format::Dot), |f| f.parse::<Format>())
+        .expect("format");
+
+    let mut graph = Graph::directed("G");
 
     // Nodes with labels
     modules.iter().for_each(|(label, module)| {
-        println!(
-            "{}",
-            match module.module_type {
-                ModuleType::Broadcast => format!("  {label}"),
-                ModuleType::FlipFlop(_) =>
-                    format!("  {label} [label=\"%{label}\", color=\"blue\"];"),
-                ModuleType::Conjunction(_) =>
-                    format!("  {label} [label=\"&{label}\", color=\"green\"];"),
-                ModuleType::Output => format!("  {label}"),
+        let node = graph.node(label);
+        match module.module_type {
+            ModuleType::FlipFlop(_) => {
+                node.label(format!("%{label}")).attr("color", "blue");
+            }
+            ModuleType::Conjunction(_) => {
+                node.label(format!("&{label}")).attr("color", "green");
             }
-        )
+            ModuleType::Broadcast | ModuleType::Output => {}
+        }
     });
 
     // Edges
     modules.iter().for_each(|(label, module)| {
-        module
-            .outputs
-            .iter()
-            .for_each(|output| println!("  {} -> {};", label, output));
+        module.outputs.iter().for_each(|output| {
+            graph.edge(label, output);
+        });
     });
 
-    println!("}}");
+    print!("{}", graph.export(format));
 }
@@ -5,6 +5,7 @@ use std::io;
 use day21::parse;
 
 use bounds::Bounds;
+use grid::grids::{self, Wrap};
 use point::Point;
 
 const STEPS: i32 = 100;
@@ -29,17 +30,10 @@ fn process(input: &str) -> Result<String> {
     let height = wall_bounds.max_y + 2;
 
     // A modular wall function
+    let cell = grids::Bounds::from_size((0, 0), width as usize, height as usize);
     let wall_mod_contains = |&p: &Point| {
-        let mut p = Point::new(p.x % width, p.y % height);
-
-        if p.x < 0 {
-            p.x += width;
-        }
-        if p.y < 0 {
-            p.y += height;
-        }
-
-        walls.contains(&p)
+        let p = Wrap::Tiled.resolve(cell, p.into()).expect("tiled grids go on forever");
+        walls.contains(&p.into())
     };
 
     let mut active = FxHashSet::default();
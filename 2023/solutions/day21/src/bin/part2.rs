@@ -5,6 +5,7 @@ use std::io;
 use day21::parse;
 
 use bounds::Bounds;
+use grid::grids::{self, Wrap};
 use point::Point;
 
 const STEPS: i32 = 26501365;
@@ -35,17 +36,10 @@ fn process(input: &str) -> Result<String> {
     let target = ((STEPS as isize) - half_width) / cell_width;
 
     // A modular wall function
+    let cell = grids::Bounds::from_size((0, 0), cell_width as usize, cell_height as usize);
     let wall_mod_contains = |&p: &Point| {
-        let mut p = Point::new(p.x % cell_width, p.y % cell_height);
-
-        if p.x < 0 {
-            p.x += cell_width;
-        }
-        if p.y < 0 {
-            p.y += cell_height;
-        }
-
-        walls.contains(&p)
+        let p = Wrap::Tiled.resolve(cell, p.into()).expect("tiled grids go on forever");
+        walls.contains(&p.into())
     };
 
     // The set of active points
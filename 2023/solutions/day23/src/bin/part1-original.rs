@@ -50,13 +50,13 @@ fn process(input: &str) -> Result<String> {
             let next_position = state.position + *direction;
 
             // If we're at the exit, we've found a complete path
-            if next_position == Point::new(grid.bounds.max_x - 1, grid.bounds.max_y) {
+            if next_position == Point::new(grid.bounds().max_x - 1, grid.bounds().max_y) {
                 complete.push(state.path.clone());
                 continue;
             }
 
             // If we're out of bounds, we've found an invalid path
-            if !grid.bounds.contains(&next_position) {
+            if !grid.bounds().contains(&next_position) {
                 continue;
             }
 
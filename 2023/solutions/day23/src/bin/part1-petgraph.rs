@@ -35,8 +35,8 @@ fn process(input: &str) -> Result<String> {
         let mut end = None;
 
         // Add each walkable point as a node to the graph
-        for y in 0..=grid.bounds.max_y {
-            for x in 0..=grid.bounds.max_x {
+        for y in 0..=grid.bounds().max_y {
+            for x in 0..=grid.bounds().max_x {
                 let p = Point::new(x, y);
 
                 if let Some(Object::Wall) = grid.get(&p) {
@@ -49,7 +49,7 @@ fn process(input: &str) -> Result<String> {
                 if x == 1 && y == 0 {
                     start = Some(node);
                 }
-                if x == grid.bounds.max_x - 1 && y == grid.bounds.max_y {
+                if x == grid.bounds().max_x - 1 && y == grid.bounds().max_y {
                     end = Some(node);
                 }
             }
@@ -68,7 +68,7 @@ fn process(input: &str) -> Result<String> {
                 let next_position = p + *direction;
 
                 // If we're out of bounds, we've found an invalid path
-                if !grid.bounds.contains(&next_position) {
+                if !grid.bounds().contains(&next_position) {
                     continue;
                 }
 
@@ -37,10 +37,10 @@ fn process(input: &str) -> Result<String> {
     let mut splits = vec![];
 
     splits.push(Point::new(1, 0));
-    splits.push(Point::new(walls.bounds.max_x - 1, walls.bounds.max_y));
+    splits.push(Point::new(walls.bounds().max_x - 1, walls.bounds().max_y));
 
-    for y in 0..=walls.bounds.max_y {
-        for x in 0..=walls.bounds.max_x {
+    for y in 0..=walls.bounds().max_y {
+        for x in 0..=walls.bounds().max_x {
             let p = Point::new(x, y);
 
             if walls.get(&p).is_some() {
@@ -63,8 +63,8 @@ fn process(input: &str) -> Result<String> {
     // DEBUG
     {
         let mut map = String::from("Map with splits:\n");
-        for y in 0..=walls.bounds.max_y {
-            for x in 0..=walls.bounds.max_x {
+        for y in 0..=walls.bounds().max_y {
+            for x in 0..=walls.bounds().max_x {
                 let p = Point::new(x, y);
                 if splits.contains(&p) {
                     map.push('O');
@@ -90,7 +90,7 @@ fn process(input: &str) -> Result<String> {
             let mut path = vec![*split, *split + direction];
 
             // Make sure the initial move is not out of the map or into a wall
-            if !walls.bounds.contains(&position) {
+            if !walls.bounds().contains(&position) {
                 continue;
             }
             if walls.get(&position).is_some() {
@@ -151,7 +151,7 @@ fn process(input: &str) -> Result<String> {
             if *split == Point::new(1, 0) {
                 start = Some(node);
             }
-            if *split == Point::new(walls.bounds.max_x - 1, walls.bounds.max_y) {
+            if *split == Point::new(walls.bounds().max_x - 1, walls.bounds().max_y) {
                 end = Some(node);
             }
         }
@@ -34,10 +34,10 @@ fn process(input: &str) -> Result<String> {
     let mut splits = vec![];
 
     splits.push(Point::new(1, 0));
-    splits.push(Point::new(walls.bounds.max_x - 1, walls.bounds.max_y));
+    splits.push(Point::new(walls.bounds().max_x - 1, walls.bounds().max_y));
 
-    for y in 0..=walls.bounds.max_y {
-        for x in 0..=walls.bounds.max_x {
+    for y in 0..=walls.bounds().max_y {
+        for x in 0..=walls.bounds().max_x {
             let p = Point::new(x, y);
 
             if walls.get(&p).is_some() {
@@ -60,8 +60,8 @@ fn process(input: &str) -> Result<String> {
     // DEBUG
     {
         let mut map = String::from("Map with splits:\n");
-        for y in 0..=walls.bounds.max_y {
-            for x in 0..=walls.bounds.max_x {
+        for y in 0..=walls.bounds().max_y {
+            for x in 0..=walls.bounds().max_x {
                 let p = Point::new(x, y);
                 if splits.contains(&p) {
                     map.push('O');
@@ -87,7 +87,7 @@ fn process(input: &str) -> Result<String> {
             let mut path = vec![*split, *split + direction];
 
             // Make sure the initial move is not out of the map or into a wall
-            if !walls.bounds.contains(&position) {
+            if !walls.bounds().contains(&position) {
                 continue;
             }
             if walls.get(&position).is_some() {
@@ -168,7 +168,7 @@ fn process(input: &str) -> Result<String> {
 
         for (next, distance) in nexts {
             // If we're at the exit, we've found a complete path
-            if next == Point::new(walls.bounds.max_x - 1, walls.bounds.max_y) {
+            if next == Point::new(walls.bounds().max_x - 1, walls.bounds().max_y) {
                 complete.push((state.path.clone(), state.distance + distance));
                 continue;
             }
@@ -1,5 +1,5 @@
-use petgraph::dot::Dot;
-use std::{fs, io};
+use graph_export::{Export, Format};
+use std::{env, io};
 
 use day25::parse;
 
@@ -9,5 +9,13 @@ fn main() {
     let input = io::read_to_string(stdin.lock()).expect("read input");
     let graph = parse::read(&input);
 
-    fs::write("graph.dot", format!("{:?}", Dot::new(&graph))).expect("write graph.dot");
+    // dot (the default), mermaid or graphml
+    let format = env::args()
+        .nth(1)
+        .map_or(Ok(Format::Dot), |f| f.parse::<Format>())
+        .expect("format");
+
+    graph
+        .save(format!("graph.{}", format.extension()))
+        .expect("write graph");
 }
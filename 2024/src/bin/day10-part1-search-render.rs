@@ -1,8 +1,10 @@
+use std::path::Path;
+
 use aoc2024::Grid;
 use image::{imageops, ImageBuffer};
 
-fn render(grid: &Grid<u8>, checked: &Grid<bool>, path: &str) {
-    println!("Rendering frame: {path}...");
+fn render(grid: &Grid<u8>, checked: &Grid<bool>, path: &Path) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(grid.width as u32, grid.height as u32);
 
@@ -36,9 +38,9 @@ fn render(grid: &Grid<u8>, checked: &Grid<bool>, path: &str) {
 
 fn main() {
     let input = include_str!("../../input/2024/day10.txt");
+    aoc2024::output::begin(input);
     let heights = Grid::read(input, &|c| c.to_digit(10).unwrap() as u8);
 
-    std::fs::create_dir_all("output").unwrap();
     let mut frame = 0;
     let frame_skip = 4;
 
@@ -52,7 +54,7 @@ fn main() {
 
             while let Some(p) = queue.pop() {
                 if frame % frame_skip == 0 {
-                    render(&heights, &checked, &format!("output/{:0>8}.png", frame));
+                    render(&heights, &checked, &aoc2024::output::frame_path(frame));
                 }
                 frame += 1;
 
@@ -74,31 +76,30 @@ fn main() {
                     });
             }
 
-            render(&heights, &checked, &format!("output/{:0>8}.png", frame));
+            render(&heights, &checked, &aoc2024::output::frame_path(frame));
             frame += 1;
         });
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day10-part1-search", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day10-part1-search.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
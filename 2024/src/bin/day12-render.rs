@@ -1,10 +1,12 @@
+use std::path::Path;
+
 use aoc2024::{day12::get_regions, Direction, Grid, Point};
 use hashbrown::HashMap;
 use image::{imageops, ImageBuffer};
 use rand::seq::SliceRandom;
 
-fn render_regions(width: usize, height: usize, regions: &[(&char, Vec<Point>)], path: &str) {
-    println!("Rendering frame: {path}...");
+fn render_regions(width: usize, height: usize, regions: &[(&char, Vec<Point>)], path: &Path) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(width as u32, height as u32);
     let mut regions = regions.to_vec();
@@ -30,15 +32,22 @@ fn render_regions(width: usize, height: usize, regions: &[(&char, Vec<Point>)],
         image::imageops::Nearest,
     );
     image.save(path).unwrap();
+    aoc2024::output::record(path, "png", None);
 }
 
 fn main() {
     let input = include_str!("../../input/2024/day12.txt");
+    aoc2024::output::begin(input);
     let grid = Grid::read(input, &|c| c);
 
     let regions = get_regions(&grid);
 
-    render_regions(grid.width, grid.height, &regions, "day12.png");
+    render_regions(
+        grid.width,
+        grid.height,
+        &regions,
+        &aoc2024::output::path_for("day12", "png"),
+    );
 
     let mut edges = HashMap::new();
     Direction::all().iter().for_each(|&d| {
@@ -70,6 +79,8 @@ fn main() {
             grid.height as u32 * 4,
             image::imageops::Nearest,
         );
-        image.save(format!("day12-edges-{:?}.png", d)).unwrap();
+        let path = aoc2024::output::path_for(&format!("day12-edges-{:?}", d), "png");
+        image.save(&path).unwrap();
+        aoc2024::output::record(&path, "png", None);
     });
 }
@@ -1,8 +1,10 @@
+use std::path::Path;
+
 use aoc2024::day14::{self, Robot};
 use image::{imageops, ImageBuffer};
 
-fn render(width: usize, height: usize, robots: &[Robot], path: &str) {
-    println!("Rendering frame: {path}...");
+fn render(width: usize, height: usize, robots: &[Robot], path: &Path) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(width as u32, height as u32);
 
@@ -26,19 +28,20 @@ fn render(width: usize, height: usize, robots: &[Robot], path: &str) {
 
 fn main() {
     let input = include_str!("../../input/2024/day14.txt");
+    aoc2024::output::begin(input);
     let (width, height, mut robots) = day14::parse(input);
 
-    let max_frames = std::env::args()
-        .nth(1)
+    let max_frames = aoc2024::output::args()
+        .first()
+        .cloned()
         .unwrap_or("100".to_string())
         .parse::<usize>()
         .unwrap();
 
-    std::fs::create_dir_all("output").unwrap();
     let mut frame = 0;
 
     for _i in 0..max_frames {
-        render(width, height, &robots, &format!("output/{:0>8}.png", frame));
+        render(width, height, &robots, &aoc2024::output::frame_path(frame));
         frame += 1;
 
         for robot in robots.iter_mut() {
@@ -48,31 +51,28 @@ fn main() {
         }
     }
 
-    render(width, height, &robots, &format!("output/{:0>8}.png", frame));
+    render(width, height, &robots, &aoc2024::output::frame_path(frame));
 
     // Render to mp4
     println!("Rendering video...");
+    let out = aoc2024::output::path_for(&format!("day14-{max_frames}"), "mp4");
     let cmd = format!(
         "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day14-{max_frames}.mp4"
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
     );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
@@ -1,9 +1,11 @@
-use aoc2024::day15::{self, State, Tile};
-use image::{imageops, ImageBuffer};
+use std::path::Path;
 
-fn render(state: &State, path: &str, scale: usize) {
-    println!("Rendering frame: {path}...");
+use aoc2024::day15::{self, State, Tile};
+use image::{imageops, ImageBuffer, RgbImage};
+use scrubber::Scrubber;
 
+// One pixel per tile
+fn draw(state: &State) -> RgbImage {
     let mut image = ImageBuffer::new(state.tiles.width as u32, state.tiles.height as u32);
 
     for (point, tile) in state.tiles.iter_enumerate() {
@@ -22,11 +24,16 @@ fn render(state: &State, path: &str, scale: usize) {
         state.position.y as u32,
         image::Rgb([255, 0, 255]),
     );
+    image
+}
+
+fn render(image: &RgbImage, path: &Path, scale: usize) {
+    println!("Rendering frame: {}...", path.display());
 
     let image = imageops::resize(
-        &image,
-        state.tiles.width as u32 * scale as u32,
-        state.tiles.height as u32 * scale as u32,
+        image,
+        image.width() * scale as u32,
+        image.height() * scale as u32,
         image::imageops::Nearest,
     );
     image.save(path).unwrap();
@@ -34,81 +41,66 @@ fn render(state: &State, path: &str, scale: usize) {
 
 fn main() {
     let input = include_str!("../../input/2024/day15.txt");
+    aoc2024::output::begin(input);
     let input = day15::parse(input);
 
-    std::fs::create_dir_all("output/part1").unwrap();
-    std::fs::create_dir_all("output/part2").unwrap();
-    std::fs::create_dir_all("output/part2plus").unwrap();
-
     let frame_skip = 10;
 
-    let mut frame = 0;
-    let mut state = input.clone();
-
-    while let Some((_d, _p)) = state.next() {
-        if frame % frame_skip == 0 {
-            render(
-                &state.clone(),
-                &format!("output/part1/{:0>8}.png", frame),
-                6,
-            );
-        }
-        frame += 1;
-    }
-
-    let mut frame = 0;
-    let mut state = input.clone_but_wider();
-
-    while let Some((_d, _p)) = state.next() {
-        if frame % frame_skip == 0 {
-            render(
-                &state.clone(),
-                &format!("output/part2/{:0>8}.png", frame),
-                6,
-            );
+    for (part, mut state, scale) in [
+        ("1", input.clone(), 6),
+        ("2", input.clone_but_wider(), 6),
+        ("2plus", input.clone_but_wider().clone_but_wider(), 3),
+    ] {
+        let mut frame = 0;
+        // The same frames in a page to step through one at a time, it scales them up itself
+        let mut scrubber = Scrubber::new(state.tiles.width as u32, state.tiles.height as u32)
+            .fps(24)
+            .scale(scale as u32);
+
+        while let Some((d, p)) = state.next() {
+            if frame % frame_skip == 0 {
+                let image = draw(&state);
+                render(&image, &aoc2024::output::frame_path(frame), scale);
+                scrubber
+                    .push(
+                        image.as_raw(),
+                        Some(&format!(
+                            "part {part}, move {frame}: {d:?}, robot at {},{}",
+                            p.x, p.y
+                        )),
+                    )
+                    .unwrap();
+            }
+            frame += 1;
         }
-        frame += 1;
-    }
 
-    let mut frame = 0;
-    let mut state = input.clone_but_wider().clone_but_wider();
-
-    while let Some((_d, _p)) = state.next() {
-        if frame % frame_skip == 0 {
-            render(
-                &state.clone(),
-                &format!("output/part2plus/{:0>8}.png", frame),
-                3,
-            );
+        let html = aoc2024::output::path_for(&format!("day15-part{part}"), "html");
+        match scrubber.save(&html) {
+            Ok(_) => aoc2024::output::record(&html, "html", Some(scrubber.len())),
+            Err(err) => eprintln!("Failed to write {}: {:?}", html.display(), err),
         }
-        frame += 1;
-    }
 
-    // Render to mp4
-    println!("Rendering video...");
-    for part in ["1", "2", "2plus"] {
+        // Render to mp4
+        println!("Rendering video...");
+        let out = aoc2024::output::path_for(&format!("day15-part{part}"), "mp4");
         let cmd = format!(
             "ffmpeg -y \
             -framerate 24 \
             -pattern_type glob \
-            -i 'output/part{part}/*.png' \
+            -i '{frames}/*.png' \
             -c:v libx264 \
             -crf 24 \
             -vf format=yuv420p \
             -movflags +faststart \
-            day15-part{part}.mp4"
+            '{out}'",
+            frames = aoc2024::output::frames_dir().display(),
+            out = out.display()
         );
 
-        match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-            Ok(_) => {}
-            Err(err) => {
-                eprintln!("Failed to run ffmpeg: {:?}", err);
-                std::process::exit(1);
-            }
-        }
-    }
+        aoc2024::output::ffmpeg(&cmd, &out);
 
-    // Clean up
-    println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+        // Clean up, so the next part starts with an empty frames directory
+        println!("Cleaning up...");
+        aoc2024::output::cleanup();
+    }
 }
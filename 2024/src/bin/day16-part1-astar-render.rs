@@ -2,6 +2,7 @@ use aoc2024::{
     day16::{self, Puzzle},
     Direction, Point,
 };
+use colormap::Colormap;
 use image::{imageops, ImageBuffer};
 
 const SCALE: usize = 4;
@@ -11,13 +12,13 @@ static mut FRAME_COUNT: usize = 0;
 fn render(puzzle: &Puzzle, points: &[Point], force: bool) {
     let path = unsafe {
         FRAME_COUNT += 1;
-        &format!("output/{:0>8}.png", FRAME_COUNT / FRAME_SKIP)
+        &aoc2024::output::frame_path(FRAME_COUNT / FRAME_SKIP)
     };
     if !force && unsafe { FRAME_COUNT } % FRAME_SKIP != 0 {
         return;
     }
 
-    println!("Rendering frame: {path}...");
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(puzzle.walls.width as u32, puzzle.walls.height as u32);
 
@@ -30,14 +31,8 @@ fn render(puzzle: &Puzzle, points: &[Point], force: bool) {
     }
 
     for point in points.iter() {
-        let hue = 360.0 * (point.x as f64 / puzzle.walls.width as f64);
-        let color = hsv::hsv_to_rgb(hue, 1.0, 1.0);
-
-        image.put_pixel(
-            point.x as u32,
-            point.y as u32,
-            image::Rgb([color.0, color.1, color.2]),
-        );
+        let color = Colormap::Turbo.color(point.x as f64 / puzzle.walls.width as f64);
+        image.put_pixel(point.x as u32, point.y as u32, image::Rgb(color));
     }
 
     let image = imageops::resize(
@@ -51,12 +46,11 @@ fn render(puzzle: &Puzzle, points: &[Point], force: bool) {
 
 fn main() {
     let input = include_str!("../../input/2024/day16.txt");
+    aoc2024::output::begin(input);
     let input = day16::parse(input);
 
     let mut points_checked = vec![];
 
-    std::fs::create_dir_all("output").unwrap();
-
     match pathfinding::prelude::astar(
         &(input.start, Direction::Right),
         |(point, direction)| {
@@ -105,25 +99,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day16-part1-astar", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day16-part1-astar.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
@@ -13,13 +13,13 @@ static mut FRAME_COUNT: usize = 0;
 fn render(puzzle: &Puzzle, points: &[Point], force: bool) {
     let path = unsafe {
         FRAME_COUNT += 1;
-        &format!("output/{:0>8}.png", FRAME_COUNT / FRAME_SKIP)
+        &aoc2024::output::frame_path(FRAME_COUNT / FRAME_SKIP)
     };
     if !force && unsafe { FRAME_COUNT } % FRAME_SKIP != 0 {
         return;
     }
 
-    println!("Rendering frame: {path}...");
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(puzzle.walls.width as u32, puzzle.walls.height as u32);
 
@@ -53,12 +53,11 @@ fn render(puzzle: &Puzzle, points: &[Point], force: bool) {
 
 fn main() {
     let input = include_str!("../../input/2024/day16.txt");
+    aoc2024::output::begin(input);
     let input = day16::parse(input);
 
     let mut points_checked = vec![];
 
-    std::fs::create_dir_all("output").unwrap();
-
     let mut pq = PriorityQueue::new();
     pq.push((input.start, Direction::Right), 0_isize);
 
@@ -99,25 +98,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day16-part1-pq", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day16-part1-pq.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
@@ -0,0 +1,78 @@
+use aoc2024::day16;
+use colormap::Colormap;
+use image::{imageops, ImageBuffer};
+
+const SCALE: u32 = 4;
+const FRAME_SKIP: usize = 25;
+
+fn main() {
+    let input = include_str!("../../input/2024/day16.txt");
+    aoc2024::output::begin(input);
+    let input = day16::parse(input);
+    let (width, height) = (input.walls.width as u32, input.walls.height as u32);
+
+    // Search first, then play back the order it got to each point in
+    let search = day16::search(&input);
+    let trace = search.trace();
+    let best = search.on_shortest_paths();
+
+    let mut image = ImageBuffer::new(width, height);
+    for (point, tile) in input.walls.iter_enumerate() {
+        let color = if *tile { [0, 0, 0] } else { [255, 255, 255] };
+        image.put_pixel(point.x as u32, point.y as u32, image::Rgb(color));
+    }
+
+    let save = |image: &ImageBuffer<image::Rgb<u8>, Vec<u8>>, frame: usize| {
+        let path = aoc2024::output::frame_path(frame);
+        println!("Rendering frame: {}...", path.display());
+        imageops::resize(image, width * SCALE, height * SCALE, imageops::Nearest)
+            .save(path)
+            .unwrap();
+    };
+
+    for (i, reindeer) in trace.iter().enumerate() {
+        let color = Colormap::Turbo.color(reindeer.point.x as f64 / width as f64);
+        image.put_pixel(
+            reindeer.point.x as u32,
+            reindeer.point.y as u32,
+            image::Rgb(color),
+        );
+
+        if i % FRAME_SKIP == 0 {
+            save(&image, i / FRAME_SKIP);
+        }
+    }
+
+    // Finish on every best path
+    for reindeer in best.iter() {
+        image.put_pixel(
+            reindeer.point.x as u32,
+            reindeer.point.y as u32,
+            image::Rgb([255, 0, 0]),
+        );
+    }
+    save(&image, trace.len() / FRAME_SKIP + 1);
+
+    // Render to mp4
+    println!("Rendering video...");
+    let out = aoc2024::output::path_for("day16-part1-search", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
+        -framerate 24 \
+        -pattern_type glob \
+        -i '{frames}/*.png' \
+        -c:v libx264 \
+        -crf 24 \
+        -vf format=yuv420p \
+        -movflags +faststart \
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
+
+    aoc2024::output::ffmpeg(&cmd, &out);
+
+    // Clean up
+    println!("Cleaning up...");
+    aoc2024::output::cleanup();
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use aoc2024::{
     day16::{self, Puzzle},
     Direction, Point,
@@ -6,8 +8,8 @@ use image::{imageops, ImageBuffer};
 
 const SCALE: usize = 4;
 
-fn render(puzzle: &Puzzle, points: &[Point], path: &str) {
-    println!("Rendering frame: {path}...");
+fn render(puzzle: &Puzzle, points: &[Point], path: &Path) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(puzzle.walls.width as u32, puzzle.walls.height as u32);
 
@@ -37,16 +39,16 @@ fn render(puzzle: &Puzzle, points: &[Point], path: &str) {
         image::imageops::Nearest,
     );
     image.save(path).unwrap();
+    aoc2024::output::record(path, "png", None);
 }
 
 fn main() {
     let input = include_str!("../../input/2024/day16.txt");
+    aoc2024::output::begin(input);
     let input = day16::parse(input);
 
     let mut points_checked = vec![];
 
-    std::fs::create_dir_all("output").unwrap();
-
     match pathfinding::prelude::astar_bag(
         &(input.start, Direction::Right),
         |(point, direction)| {
@@ -87,7 +89,11 @@ fn main() {
                     path_points.push(point);
                 }
             }
-            render(&input, &path_points, "day16-part2.png");
+            render(
+                &input,
+                &path_points,
+                &aoc2024::output::path_for("day16-part2", "png"),
+            );
         }
         _ => panic!("unsolvable maze"),
     }
@@ -12,13 +12,13 @@ static mut FRAME_COUNT: usize = 0;
 fn render(puzzle: &Puzzle, cutoff: usize, points: &[Point], force: bool) {
     let path = unsafe {
         FRAME_COUNT += 1;
-        &format!("output/{:0>8}.png", FRAME_COUNT / FRAME_SKIP)
+        &aoc2024::output::frame_path(FRAME_COUNT / FRAME_SKIP)
     };
     if !force && unsafe { FRAME_COUNT } % FRAME_SKIP != 0 {
         return;
     }
 
-    println!("Rendering frame: {path}...");
+    println!("Rendering frame: {}...", path.display());
 
     let image = puzzle.render_image(cutoff, points);
     let image = imageops::resize(
@@ -32,10 +32,9 @@ fn render(puzzle: &Puzzle, cutoff: usize, points: &[Point], force: bool) {
 
 fn main() {
     let input = include_str!("../../input/2024/day18.txt");
+    aoc2024::output::begin(input);
     let input = day18::parse(input);
 
-    std::fs::create_dir_all("output").unwrap();
-
     let end = (input.width - 1, input.height - 1).into();
 
     let mut previous_best_path = None;
@@ -97,25 +96,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day18-part2-v4", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day18-part2-v4.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
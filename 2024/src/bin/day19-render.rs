@@ -20,13 +20,13 @@ R   R  GGG  BBBB   UUU  W   W
 fn render(input: &str, highlights: &[(usize, usize)], force: bool) {
     let path = unsafe {
         FRAME_COUNT += 1;
-        &format!("output/{:0>8}.png", FRAME_COUNT / FRAME_SKIP)
+        &aoc2024::output::frame_path(FRAME_COUNT / FRAME_SKIP)
     };
     if !force && unsafe { FRAME_COUNT } % FRAME_SKIP != 0 {
         return;
     }
 
-    println!("Rendering frame: {path}...");
+    println!("Rendering frame: {}...", path.display());
 
     let size_in_chars = (unsafe { MAX_CHARS } as f64).sqrt().ceil() as usize;
 
@@ -90,6 +90,7 @@ fn render(input: &str, highlights: &[(usize, usize)], force: bool) {
 
 fn main() {
     let input = include_str!("../../input/2024/day19.txt");
+    aoc2024::output::begin(input);
     let puzzle: Puzzle = input.into();
 
     let mut segments = vec![];
@@ -158,7 +159,6 @@ fn main() {
 
     for use_cache in [false, true] {
         unsafe {
-            std::fs::create_dir_all("output").unwrap();
             FRAME_COUNT = 0;
         }
 
@@ -183,29 +183,28 @@ fn main() {
 
         // Render to mp4
         println!("Rendering video...");
+        let out = aoc2024::output::path_for(
+            &format!("day19-{}", if use_cache { "memo" } else { "no_memo" }),
+            "mp4",
+        );
         let cmd = format!(
             "ffmpeg -y \
             -framerate 24 \
             -pattern_type glob \
-            -i 'output/*.png' \
+            -i '{frames}/*.png' \
             -c:v libx264 \
             -crf 24 \
             -vf format=yuv420p \
             -movflags +faststart \
-            day19-{}.mp4",
-            if use_cache { "memo" } else { "no_memo" }
+            '{out}'",
+            frames = aoc2024::output::frames_dir().display(),
+            out = out.display()
         );
 
-        match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-            Ok(_) => {}
-            Err(err) => {
-                eprintln!("Failed to run ffmpeg: {:?}", err);
-                std::process::exit(1);
-            }
-        }
+        aoc2024::output::ffmpeg(&cmd, &out);
 
         // Clean up
         println!("Cleaning up...");
-        std::fs::remove_dir_all("output").unwrap();
+        aoc2024::output::cleanup();
     }
 }
@@ -18,7 +18,7 @@ fn render(
     force: bool,
 ) {
     let path = unsafe {
-        let path = format!("output/{:0>8}.png", FRAME_COUNT);
+        let path = aoc2024::output::frame_path(FRAME_COUNT);
 
         FRAME_COUNT += 1;
 
@@ -32,7 +32,8 @@ fn render(
         }
 
         println!(
-            "Rendering frame: {path}... (progress={}/{}, next={})",
+            "Rendering frame: {}... (progress={}/{}, next={})",
+            path.display(),
             progress,
             best_path.len(),
             NEXT_TO_RENDER
@@ -40,8 +41,6 @@ fn render(
         path
     };
 
-    std::fs::create_dir_all("output").unwrap();
-
     let mut image = image::ImageBuffer::new(puzzle.walls.width as u32, puzzle.walls.height as u32);
 
     // Draw walls
@@ -111,6 +110,7 @@ fn render(
 
 fn main() {
     let input = include_str!("../../input/2024/day20.txt");
+    aoc2024::output::begin(input);
     let input = aoc2024::day20::parse(input);
 
     let skiplength = 20_i32;
@@ -221,25 +221,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day20-part2", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day20-part2.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
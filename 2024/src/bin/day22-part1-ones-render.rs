@@ -1,8 +1,6 @@
 use aoc2024::day22::{self};
 
 fn main() {
-    std::fs::create_dir_all("output").unwrap();
-
     let mut rng = day22::SuperSecretPseudoRandomNumberGenerator::new(123);
     let mut image = image::ImageBuffer::new(400, 400);
 
@@ -15,6 +13,7 @@ fn main() {
         }
     }
 
-    let path = "day22-part1-ones.png";
-    image.save(path).unwrap();
+    let path = aoc2024::output::path_for("day22-part1-ones", "png");
+    image.save(&path).unwrap();
+    aoc2024::output::record(&path, "png", None);
 }
@@ -1,8 +1,6 @@
 use aoc2024::day22::{self};
 
 fn main() {
-    std::fs::create_dir_all("output").unwrap();
-
     let mut rng = day22::SuperSecretPseudoRandomNumberGenerator::new(123);
 
     let mut ones_counts = [0; 10];
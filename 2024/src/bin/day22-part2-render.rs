@@ -15,7 +15,7 @@ fn render(
     force: bool,
 ) {
     let path = unsafe {
-        let path = format!("output/{:0>8}.png", FRAME_COUNT);
+        let path = aoc2024::output::frame_path(FRAME_COUNT);
 
         FRAME_COUNT += 1;
 
@@ -28,12 +28,10 @@ fn render(
             NEXT_TO_RENDER += 1.0;
         }
 
-        println!("Rendering frame: {path}...");
+        println!("Rendering frame: {}...", path.display());
         path
     };
 
-    std::fs::create_dir_all("output").unwrap();
-
     let size = 20 * 20;
     let mut image = image::ImageBuffer::new(size, size);
 
@@ -71,6 +69,7 @@ fn render(
 
 fn main() {
     let input = include_str!("../../input/2024/day22.txt");
+    aoc2024::output::begin(input);
     let input = aoc2024::day22::parse(input);
 
     let mut sequence_scores = HashMap::new();
@@ -128,25 +127,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day22-part2", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day22-part2.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
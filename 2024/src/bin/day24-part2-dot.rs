@@ -4,23 +4,30 @@ use std::{
 };
 
 use aoc2024::day24::Machine;
+use graph_export::Export;
 
 fn main() {
     let input = include_str!("../../input/2024/day24.txt");
+    aoc2024::output::begin(input);
     let graph = Machine::from(input);
 
     for limit in [7, 45] {
-        let dot = graph.to_graphviz_limited(limit);
-        let filename = format!("day24-limit{}.dot", limit);
+        let graph = graph.graph_limited(limit);
 
-        println!("Generating {filename}");
-        std::fs::write(filename, dot.clone()).unwrap();
+        // Mermaid and GraphML for anything that isn't graphviz
+        for format in ["dot", "mmd", "graphml"] {
+            let path = aoc2024::output::path_for(&format!("day24-limit{limit}"), format);
+            println!("Generating {}", path.display());
+            graph.save(&path).unwrap();
+            aoc2024::output::record(&path, format, None);
+        }
+        let dot = graph.to_dot();
 
         for format in ["png", "svg"] {
-            let filename = format!("day24-limit{}.{}", limit, format);
-            println!("Rendering {filename}");
+            let path = aoc2024::output::path_for(&format!("day24-limit{limit}"), format);
+            println!("Rendering {}", path.display());
 
-            let output = std::fs::File::create(filename).unwrap();
+            let output = std::fs::File::create(&path).unwrap();
             let mut child = Command::new("dot")
                 .arg(format!("-T{format}"))
                 .stdin(Stdio::piped())
@@ -30,6 +37,8 @@ fn main() {
 
             let stdin = child.stdin.as_mut().unwrap();
             stdin.write_all(dot.as_bytes()).unwrap();
+            child.wait().unwrap();
+            aoc2024::output::record(&path, format, None);
         }
     }
 }
@@ -1,8 +1,10 @@
+use std::path::PathBuf;
+
 use aoc2024::{Grid, Point};
 use image::ImageBuffer;
 
-fn render(grid: &Grid<char>, active: &Point, scanning: &[Point], visited: &[Point], path: String) {
-    println!("Rendering frame: {path}...");
+fn render(grid: &Grid<char>, active: &Point, scanning: &[Point], visited: &[Point], path: PathBuf) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(grid.width as u32 * 6, grid.height as u32 * 6);
 
@@ -59,9 +61,8 @@ X   X M   M A   A SSSS
 }
 
 fn main() {
-    std::fs::create_dir_all("output").unwrap();
-
     let input = include_str!("../../input/2024/day4.txt");
+    aoc2024::output::begin(input);
     let grid = Grid::read(input, &|c| c);
 
     #[allow(unused_assignments)]
@@ -123,7 +124,7 @@ fn main() {
                             &active_letter,
                             &scanning_letters,
                             &final_letters,
-                            format!("output/{:08}.png", rendered_frame),
+                            aoc2024::output::frame_path(rendered_frame),
                         );
                         rendered_frame += 1;
                     }
@@ -135,25 +136,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day4-part1", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day4-part1.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
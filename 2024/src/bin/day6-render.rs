@@ -1,11 +1,13 @@
+use std::path::PathBuf;
+
 use aoc2024::{
     day6::{self, Map, Tile},
     Grid, Point,
 };
 use image::ImageBuffer;
 
-fn render(grid: &Grid<Tile>, guard: &Point, visited: &[Point], path: String) {
-    println!("Rendering frame: {path}...");
+fn render(grid: &Grid<Tile>, guard: &Point, visited: &[Point], path: PathBuf) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(grid.width as u32, grid.height as u32);
 
@@ -41,12 +43,12 @@ fn render(grid: &Grid<Tile>, guard: &Point, visited: &[Point], path: String) {
 }
 
 fn main() {
-    std::fs::create_dir_all("output").unwrap();
-
     let mut frame = 0;
     const FRAME_SKIP: u32 = 10;
 
     let input = include_str!("../../input/2024/day6.txt");
+
+    aoc2024::output::begin(input);
     let map = day6::parse(input);
 
     let Map {
@@ -61,7 +63,12 @@ fn main() {
     while grid.in_bounds(guard) {
         frame += 1;
         if frame % FRAME_SKIP == 0 {
-            render(&grid, &guard, &visited, format!("output/{:0>8}.png", frame));
+            render(
+                &grid,
+                &guard,
+                &visited,
+                aoc2024::output::frame_path(frame as usize),
+            );
         }
 
         match grid.get(guard + facing) {
@@ -78,25 +85,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day6-part1", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day6-part1.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
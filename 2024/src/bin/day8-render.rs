@@ -1,10 +1,12 @@
+use std::path::PathBuf;
+
 use aoc2024::{day8::Tile, Grid};
 
 use hsv::hsv_to_rgb;
 use image::{imageops, ImageBuffer};
 
-fn render(grid: &Grid<Tile>, antinodes: &Grid<bool>, chars: &[char], path: String) {
-    println!("Rendering frame: {path}...");
+fn render(grid: &Grid<Tile>, antinodes: &Grid<bool>, chars: &[char], path: PathBuf) {
+    println!("Rendering frame: {}...", path.display());
 
     let mut image = ImageBuffer::new(grid.width as u32, grid.height as u32);
 
@@ -38,11 +40,11 @@ fn render(grid: &Grid<Tile>, antinodes: &Grid<bool>, chars: &[char], path: Strin
 }
 
 fn main() {
-    std::fs::create_dir_all("output").unwrap();
-
     let mut frame = 0;
 
     let input = include_str!("../../input/2024/day8.txt");
+
+    aoc2024::output::begin(input);
     let grid = Grid::read(input, &|c| match c {
         '.' => Tile::Empty,
         _ => Tile::Tower(c),
@@ -73,7 +75,7 @@ fn main() {
                 &grid,
                 &antinodes,
                 &all_chars,
-                format!("output/{:0>8}.png", frame),
+                aoc2024::output::frame_path(frame),
             );
 
             for p2 in points {
@@ -98,25 +100,24 @@ fn main() {
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day8-part2", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day8-part2.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
@@ -1,9 +1,11 @@
+use std::path::Path;
+
 use aoc2024::day9::{Block, Disk};
 use hsv::hsv_to_rgb;
 use image::ImageBuffer;
 
-fn render(disk: &Disk, path: &str, left_index: usize, right_index: usize) {
-    println!("Rendering frame: {path}...");
+fn render(disk: &Disk, path: &Path, left_index: usize, right_index: usize) {
+    println!("Rendering frame: {}...", path.display());
 
     let grid_size = 4;
     let max_width = 1200;
@@ -71,12 +73,12 @@ fn render(disk: &Disk, path: &str, left_index: usize, right_index: usize) {
 
 fn main() {
     let input = include_str!("../../input/2024/day9.txt");
+    aoc2024::output::begin(input);
     let mut disk = Disk::from(input);
 
     let mut left_index = 0;
     let mut right_index = disk.blocks.len() - 1;
 
-    std::fs::create_dir_all("output").unwrap();
     let mut frame = 0;
 
     while left_index < right_index {
@@ -84,7 +86,7 @@ fn main() {
         if frame % 100 == 0 {
             render(
                 &disk,
-                &format!("output/{:0>8}.png", frame),
+                &aoc2024::output::frame_path(frame),
                 left_index,
                 right_index,
             );
@@ -115,31 +117,30 @@ fn main() {
 
     render(
         &disk,
-        &format!("output/{:0>8}.png", frame + 1),
+        &aoc2024::output::frame_path(frame + 1),
         left_index,
         right_index,
     );
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day9-part1", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day9-part1.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
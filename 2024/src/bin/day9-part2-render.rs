@@ -1,9 +1,11 @@
+use std::path::Path;
+
 use aoc2024::day9::{Block, Disk};
 use hsv::hsv_to_rgb;
 use image::ImageBuffer;
 
-fn render(disk: &Disk, path: &str, left_index: usize) {
-    println!("Rendering frame: {path}...");
+fn render(disk: &Disk, path: &Path, left_index: usize) {
+    println!("Rendering frame: {}...", path.display());
 
     let grid_size = 4;
     let max_width = 1200;
@@ -59,10 +61,10 @@ fn render(disk: &Disk, path: &str, left_index: usize) {
 
 fn main() {
     let input = include_str!("../../input/2024/day9.txt");
+    aoc2024::output::begin(input);
     let mut disk = Disk::from(input);
 
-    std::fs::create_dir_all("output").unwrap();
-    render(&disk, &format!("output/{:0>8}.png", 0), 0);
+    render(&disk, &aoc2024::output::frame_path(0), 0);
 
     let mut frame = 0;
     let mut rendered_frame = 0;
@@ -87,7 +89,7 @@ fn main() {
                 rendered_frame += 1;
                 render(
                     &disk,
-                    &format!("output/{:0>8}.png", rendered_frame),
+                    &aoc2024::output::frame_path(rendered_frame),
                     left_index,
                 );
             }
@@ -122,29 +124,28 @@ fn main() {
         }
     }
 
-    render(&disk, &format!("output/{:0>8}.png", frame + 1), 0);
+    render(&disk, &aoc2024::output::frame_path(frame + 1), 0);
 
     // Render to mp4
     println!("Rendering video...");
-    let cmd = "ffmpeg -y \
+    let out = aoc2024::output::path_for("day9-part2", "mp4");
+    let cmd = format!(
+        "ffmpeg -y \
         -framerate 24 \
         -pattern_type glob \
-        -i 'output/*.png' \
+        -i '{frames}/*.png' \
         -c:v libx264 \
         -crf 24 \
         -vf format=yuv420p \
         -movflags +faststart \
-        day9-part2.mp4";
+        '{out}'",
+        frames = aoc2024::output::frames_dir().display(),
+        out = out.display()
+    );
 
-    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
-        Ok(_) => {}
-        Err(err) => {
-            eprintln!("Failed to run ffmpeg: {:?}", err);
-            std::process::exit(1);
-        }
-    }
+    aoc2024::output::ffmpeg(&cmd, &out);
 
     // Clean up
     println!("Cleaning up...");
-    std::fs::remove_dir_all("output").unwrap();
+    aoc2024::output::cleanup();
 }
@@ -1,4 +1,5 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use grids::{Connectivity, GridLike};
 
 use crate::{Direction, Grid, Point};
 
@@ -156,6 +157,26 @@ fn part2_corners(input: &Grid<char>) -> usize {
         .sum::<usize>()
 }
 
+#[aoc(day12, part1, regions)]
+fn part1_regions(input: &Grid<char>) -> usize {
+    input
+        .regions(Connectivity::Four)
+        .regions()
+        .iter()
+        .map(|region| region.area() * region.perimeter())
+        .sum()
+}
+
+#[aoc(day12, part2, regions)]
+fn part2_regions(input: &Grid<char>) -> usize {
+    input
+        .regions(Connectivity::Four)
+        .regions()
+        .iter()
+        .map(|region| region.area() * region.sides())
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,8 +194,8 @@ MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
 
-    make_test!([part1_v1] => "day12.txt", 1930, 1450816);
-    make_test!([part2_edge_detection, part2_corners] => "day12.txt", 1206, 865662);
+    make_test!([part1_v1, part1_regions] => "day12.txt", 1930, 1450816);
+    make_test!([part2_edge_detection, part2_corners, part2_regions] => "day12.txt", 1206, 865662);
 
     const EXAMPLE_MINI: &str = "\
 AAAA
@@ -236,4 +257,14 @@ AAAAAA";
     fn test_part2_corners_example_figure8() {
         assert_eq!(part2_corners(&parse(EXAMPLE_FIGURE8)), 368);
     }
+
+    #[test]
+    fn test_part1_regions_example_xoxo() {
+        assert_eq!(part1_regions(&parse(EXAMPLE_XOXO)), 772);
+    }
+
+    #[test]
+    fn test_part2_regions_example_figure8() {
+        assert_eq!(part2_regions(&parse(EXAMPLE_FIGURE8)), 368);
+    }
 }
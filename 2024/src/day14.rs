@@ -1,4 +1,5 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use grids::{Bounds, Wrap};
 
 use crate::{Grid, Point};
 
@@ -41,6 +42,15 @@ pub fn parse(input: &str) -> (usize, usize, Vec<Robot>) {
     (width, height, robots)
 }
 
+// The room is a torus, robots walking off one edge come back on the other
+fn wrap(p: Point, width: usize, height: usize) -> Point {
+    let bounds = Bounds::from_size((0, 0), width, height);
+    Wrap::Toroidal
+        .resolve(bounds, (p.x as isize, p.y as isize))
+        .expect("the room isn't empty")
+        .into()
+}
+
 #[allow(dead_code)]
 fn print_state(width: usize, height: usize, robots: &[Robot]) {
     let mut grid: Grid<usize> = Grid::new(width, height);
@@ -59,8 +69,7 @@ fn part1_v1((width, height, input): &(usize, usize, Vec<Robot>)) -> usize {
     for _i in 0..100 {
         for robot in robots.iter_mut() {
             robot.position += robot.velocity;
-            robot.position.x = robot.position.x.rem_euclid(*width as i32);
-            robot.position.y = robot.position.y.rem_euclid(*height as i32);
+            robot.position = wrap(robot.position, *width, *height);
         }
     }
 
@@ -113,8 +122,7 @@ fn part2_v1((width, height, input): &(usize, usize, Vec<Robot>)) -> usize {
 
         for robot in robots.iter_mut() {
             robot.position += robot.velocity;
-            robot.position.x = robot.position.x.rem_euclid(*width as i32);
-            robot.position.y = robot.position.y.rem_euclid(*height as i32);
+            robot.position = wrap(robot.position, *width, *height);
         }
 
         // Target image is 31x33 with a 1x border
@@ -183,8 +191,7 @@ fn part2_v2((width, height, input): &(usize, usize, Vec<Robot>)) -> usize {
 
         for robot in robots.iter_mut() {
             robot.position += robot.velocity;
-            robot.position.x = robot.position.x.rem_euclid(*width as i32);
-            robot.position.y = robot.position.y.rem_euclid(*height as i32);
+            robot.position = wrap(robot.position, *width, *height);
         }
 
         // Check for unusually many 'busy' horizontal lines
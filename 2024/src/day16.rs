@@ -1,6 +1,8 @@
 use core::panic;
 
 use aoc_runner_derive::{aoc, aoc_generator};
+use grids::search::{self, Search, State};
+use grids::Pos;
 use hashbrown::HashSet;
 use priority_queue::PriorityQueue;
 
@@ -80,13 +82,17 @@ impl Puzzle {
 pub fn parse(input: &str) -> Puzzle {
     let walls = Grid::read(input, &|c| c == '#');
 
-    let newline_width = walls.width + 1;
-
-    let start = input.chars().position(&|c| c == 'S').unwrap();
-    let start = (start % newline_width, start / newline_width).into();
-
-    let end = input.chars().position(&|c| c == 'E').unwrap();
-    let end = (end % newline_width, end / newline_width).into();
+    // From the grid rather than the input, which may be indented
+    let cells = Grid::read(input, &|c| c);
+    let find = |target| {
+        cells
+            .iter_enumerate()
+            .find(|(_, &c)| c == target)
+            .unwrap()
+            .0
+    };
+    let start = find('S');
+    let end = find('E');
 
     Puzzle { start, end, walls }
 }
@@ -211,6 +217,68 @@ fn part2_astar(input: &Puzzle) -> usize {
     }
 }
 
+// The same search again, on grids::search
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Reindeer {
+    pub point: Point,
+    pub facing: Direction,
+}
+
+impl State for Reindeer {
+    type Grid = Puzzle;
+
+    fn pos(&self) -> Pos {
+        (self.point.x as isize, self.point.y as isize)
+    }
+
+    fn successors(&self, input: &Puzzle) -> impl IntoIterator<Item = (Reindeer, u64)> {
+        let open = |point: Point| input.walls.get(point) != Some(&true);
+        let mut successors = vec![];
+
+        // Walk straight
+        if open(self.point + self.facing) {
+            let point = self.point + self.facing;
+            successors.push((Reindeer { point, ..*self }, 1));
+        }
+
+        // Turn left or right, but only if there's somewhere to go
+        for facing in [self.facing.rotate_left(), self.facing.rotate_right()] {
+            if open(self.point + facing) {
+                successors.push((Reindeer { facing, ..*self }, 1000));
+            }
+        }
+
+        successors
+    }
+
+    fn heuristic(&self, input: &Puzzle) -> u64 {
+        self.point.manhattan_distance(&input.end) as u64
+    }
+}
+
+pub fn search(input: &Puzzle) -> Search<Reindeer> {
+    let start = Reindeer {
+        point: input.start,
+        facing: Direction::Right,
+    };
+    search::astar(input, [start], |reindeer| reindeer.point == input.end)
+}
+
+#[aoc(day16, part1, search)]
+fn part1_search(input: &Puzzle) -> u64 {
+    search(input).cost().expect("unsolvable maze")
+}
+
+#[aoc(day16, part2, search)]
+fn part2_search(input: &Puzzle) -> usize {
+    search(input)
+        .on_shortest_paths()
+        .iter()
+        .map(|reindeer| reindeer.point)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,6 +308,6 @@ mod tests {
     #S..#.....#...#
     ###############";
 
-    make_test!([part1_pq, part1_astar] => "day16.txt", 7036, 65436);
-    make_test!([/*part1_pq, */part2_astar] => "day16.txt", 45, 489);
+    make_test!([part1_pq, part1_astar, part1_search] => "day16.txt", 7036, 65436);
+    make_test!([/*part1_pq, */part2_astar, part2_search] => "day16.txt", 45, 489);
 }
@@ -148,6 +148,19 @@ fn part1_v2_grid(input: &Puzzle) -> i32 {
     }
 }
 
+#[aoc(day18, part1, v3_search)]
+fn part1_v3_search(input: &Puzzle) -> u64 {
+    let mut walls = grids::Bitset::new(input.width, input.height);
+    for point in input.points.iter().take(input.part1_cutoff) {
+        walls.insert((point.x as isize, point.y as isize));
+    }
+
+    let end = (input.width as isize - 1, input.height as isize - 1);
+    grids::search::bfs(&walls, [(0, 0)], |&p| p == end)
+        .cost()
+        .expect("unsolvable maze")
+}
+
 #[aoc(day18, part2, v1)]
 fn part2_v1(input: &Puzzle) -> String {
     let end = (input.width - 1, input.height - 1).into();
@@ -446,6 +459,6 @@ mod tests {
 1,6
 2,0";
 
-    make_test!([part1_v1] => "day18.txt", 22, 354);
+    make_test!([part1_v1, part1_v3_search] => "day18.txt", 22, 354);
     make_test!([part2_v1, part2_v2_two_neighbors, part2_v3_grid, part2_v4_on_best_path] => "day18.txt", "6,1", "36,17");
 }
@@ -4,6 +4,7 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use hashbrown::HashSet;
 
 use crate::{Direction, Grid, Point};
+use grids::GridLike;
 use pathfinding::prelude::{astar, dijkstra_all};
 
 #[derive(Debug, Clone)]
@@ -372,6 +373,27 @@ fn part1_dijkstra(input: &Puzzle) -> usize {
     shortcut_count
 }
 
+#[aoc(day20, part1, distances)]
+fn part1_distances(input: &Puzzle) -> usize {
+    // How far every open point is from the end
+    let walls = &input.walls.to_bitset(|&wall| wall);
+    let end = (input.end.x as isize, input.end.y as isize);
+    let distances = &grids::search::explore(walls, [end]).distances();
+
+    // A cheat goes straight through one wall, two steps, and saves whatever the path would have taken less those two
+    let cutoff = if input.example { 1 } else { 100 };
+    distances
+        .iter()
+        .flat_map(|((x, y), &distance)| {
+            grids::ORTHOGONAL.into_iter().filter_map(move |(dx, dy)| {
+                let wall = walls.contains((x + dx, y + dy));
+                let landing = distances.get((x + dx * 2, y + dy * 2))?;
+                (wall && distance >= landing + 2 + cutoff).then_some(())
+            })
+        })
+        .count()
+}
+
 #[aoc(day20, part2, pathscan)]
 fn part2_pathscan(input: &Puzzle) -> usize {
     let skip_length = 20_i32;
@@ -693,6 +715,6 @@ example
 #...#...#...###
 ###############";
 
-    make_test!([part1_pathscan, part1_grid, part1_dijkstra] => "day20.txt", 44, 1399);
+    make_test!([part1_pathscan, part1_grid, part1_dijkstra, part1_distances] => "day20.txt", 44, 1399);
     make_test!([part2_pathscan, part2_griddist, part2_listiter, part2_listfor] => "day20.txt", 285, 994807);
 }
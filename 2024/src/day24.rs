@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
 use aoc_runner_derive::{aoc, aoc_generator};
+use graph_export::{Export, Graph};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
@@ -205,14 +206,30 @@ impl<'input> Machine<'input> {
         result
     }
 
-    fn wire_to_graphviz(&self, wire: &str) -> Vec<String> {
+    fn add_wire(&self, graph: &mut Graph, wire: &str, cluster: &str) {
         match self.wires[wire] {
-            Wire::Input(value) => vec![format!("    {wire} [label=\"{wire}={value}\"];")],
-            Wire::Function(op, arg0, arg1) => vec![
-                format!("    {wire} [label=\"{wire}={op:?}\"];"),
-                format!("    {wire} -> {arg0};"),
-                format!("    {wire} -> {arg1};"),
-            ],
+            Wire::Input(value) => {
+                graph
+                    .node(wire)
+                    .label(format!("{wire}={value}"))
+                    .cluster(cluster);
+            }
+            Wire::Function(op, arg0, arg1) => {
+                graph
+                    .node(wire)
+                    .label(format!("{wire}={op:?}"))
+                    .cluster(cluster);
+                graph.edge(wire, arg0);
+                graph.edge(wire, arg1);
+
+                // Anything it points at that no cluster has claimed (yet) goes in this one, not loose outside
+                for arg in [arg0, arg1] {
+                    let node = graph.node(arg);
+                    if node.cluster.is_none() {
+                        node.cluster(cluster);
+                    }
+                }
+            }
         }
     }
 
@@ -221,11 +238,14 @@ impl<'input> Machine<'input> {
     }
 
     pub fn to_graphviz_limited(&self, limit: usize) -> String {
-        let mut dot = String::new();
+        self.graph_limited(limit).to_dot()
+    }
 
-        dot.push_str("digraph {\n");
-        dot.push_str("  compounded=true;\n");
-        dot.push_str("  rankdir=LR;\n");
+    // Only the first `limit` z outputs, each in a cluster with the wires it's the first to depend on
+    pub fn graph_limited(&self, limit: usize) -> Graph {
+        let mut graph = Graph::directed("machine");
+        graph.attr("compounded", "true");
+        graph.attr("rankdir", "LR");
 
         let mut added = HashSet::new();
         let mut by_output: HashMap<&str, HashSet<&str>> = HashMap::new();
@@ -246,31 +266,21 @@ impl<'input> Machine<'input> {
             }
         }
 
+        // Sorted, so the same wires end up in the same clusters every time
         for (output, deps) in by_output.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
-            dot.push_str(&format!(
-                "  subgraph cluster_{output} {{\n    label=\"{output}\";\n\n",
-                output = output
-            ));
-            for line in self.wire_to_graphviz(output) {
-                dot.push_str(&line);
-                dot.push('\n');
-            }
-
+            self.add_wire(&mut graph, output, output);
             for dep in deps.iter().sorted() {
-                for line in self.wire_to_graphviz(dep) {
-                    dot.push_str(&line);
-                    dot.push('\n');
-                }
-                dot.push('\n');
+                self.add_wire(&mut graph, dep, output);
             }
-            dot.push_str("  }\n");
         }
 
-        // External edges have any output in one and a wire in the other
-
-        dot.push_str("}\n");
+        graph
+    }
+}
 
-        dot
+impl Export for Machine<'_> {
+    fn graph(&self) -> Graph {
+        self.graph_limited(45)
     }
 }
 
@@ -665,6 +675,25 @@ hwm AND bqk -> z03
 tgd XOR rvg -> z12
 tnw OR pbm -> gnj";
 
+    #[test]
+    fn test_graph_limited() {
+        let machine = Machine::from(EXAMPLE);
+        let graph = machine.graph_limited(3);
+
+        assert_eq!(graph.attributes["compounded"], "true");
+        assert!(graph.nodes.values().all(|node| node.cluster.is_some()));
+        assert!(graph
+            .edges
+            .iter()
+            .all(|edge| graph.nodes.contains_key(&edge.to)));
+
+        // Another machine hashes its wires differently, the graph has to come out the same anyway
+        assert_eq!(
+            graph.to_dot(),
+            Machine::from(EXAMPLE).graph_limited(3).to_dot()
+        );
+    }
+
     make_test!([part1_v1] => "day24.txt", 2024, "60714423975686");
     // make_test!([part2_bruteforce, part2_trimmed_bruteforce] => "day24.txt", "", "cgh,frt,pmd,sps,tst,z05,z11,z23");
     make_test!([part2_findadder] => "day24.txt", "", "cgh,frt,pmd,sps,tst,z05,z11,z23");
@@ -1,4 +1,5 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use grids::{GridLike, Pos, View};
 
 use crate::Grid;
 
@@ -94,6 +95,22 @@ fn part1_inline(grid: &Grid<char>) -> i32 {
     count
 }
 
+// Read every row, column and diagonal as a string, XMAS can be either way along any of them
+#[aoc(day4, part1, lines)]
+fn part1_lines(grid: &Grid<char>) -> i32 {
+    fn text<'a>(line: impl Iterator<Item = (Pos, &'a char)>) -> String {
+        line.map(|(_, c)| *c).collect()
+    }
+
+    (0..grid.height as isize)
+        .map(|y| text(grid.row(y)))
+        .chain((0..grid.width as isize).map(|x| text(grid.column(x))))
+        .chain(grid.diagonals().map(text))
+        .chain(grid.anti_diagonals().map(text))
+        .map(|line| line.matches("XMAS").count() + line.matches("SAMX").count())
+        .sum::<usize>() as i32
+}
+
 #[aoc(day4, part2)]
 fn part2_inline(grid: &Grid<char>) -> i32 {
     let mut count = 0;
@@ -137,6 +154,27 @@ fn part2_inline(grid: &Grid<char>) -> i32 {
     count
 }
 
+// Every 3x3 window, turned each way round to see if it's the one with the Ms on the left
+#[aoc(day4, part2, windows)]
+fn part2_windows(grid: &Grid<char>) -> i32 {
+    let is_x = |w: View<Grid<char>>| {
+        w[(1, 1)] == 'A'
+            && w[(0, 0)] == 'M'
+            && w[(0, 2)] == 'M'
+            && w[(2, 0)] == 'S'
+            && w[(2, 2)] == 'S'
+    };
+
+    grid.view()
+        .windows(3, 3)
+        .filter(|&w| {
+            [w, w.rotate90(), w.rotate180(), w.rotate270()]
+                .into_iter()
+                .any(is_x)
+        })
+        .count() as i32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +192,6 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
 
-    make_test!([part1_original, part1_inline] => "day4.txt", 18, 2406);
-    make_test!([part2_inline] => "day4.txt", 9, 1807);
+    make_test!([part1_original, part1_inline, part1_lines] => "day4.txt", 18, 2406);
+    make_test!([part2_inline, part2_windows] => "day4.txt", 9, 1807);
 }
@@ -1,10 +1,17 @@
+use grids::{Bounds, Dense, GridLike, Pos};
+
 use crate::point::Point;
 
+// Point-addressed, on top of grids::Dense (which also makes it GridLike)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     pub width: usize,
     pub height: usize,
-    data: Vec<T>,
+    cells: Dense<T>,
+}
+
+fn pos(p: Point) -> Pos {
+    (p.x as isize, p.y as isize)
 }
 
 #[allow(dead_code)]
@@ -13,128 +20,91 @@ where
     T: Default + Clone + Sized,
 {
     pub fn new(width: usize, height: usize) -> Self {
-        Self {
-            width,
-            height,
-            data: vec![Default::default(); width * height],
-        }
+        Self::from(Dense::new(width, height))
     }
 
+    // Lines are trimmed first, so examples indented to line up with the test around them still read
     pub fn read(input: &str, f: &dyn Fn(char) -> T) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut data = Vec::new();
-
-        for line in input.lines() {
-            if line.is_empty() {
-                continue;
-            }
-
-            width = line.len();
-            height += 1;
-
-            for c in line.chars() {
-                data.push(f(c));
-            }
-        }
-
-        Self {
-            width,
-            height,
-            data,
-        }
+        let input = input.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        Self::from(Dense::read(&input, f))
     }
 
     pub fn to_string(&self, f: &dyn Fn(&T) -> String) -> String {
-        let mut s = String::new();
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                s.push_str(&f(&self.data[y * self.width + x]));
-            }
-            s.push('\n');
-        }
-
-        s
+        self.cells.to_string_with("", f)
     }
 
     pub fn to_string_char(&self, f: &dyn Fn(&T) -> char) -> String {
-        let mut s = String::new();
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                s.push(f(&self.data[y * self.width + x]));
-            }
-            s.push('\n');
-        }
-
-        s
-    }
-
-    fn index(&self, p: &Point) -> usize {
-        (p.y * self.width as i32 + p.x)
-            .try_into()
-            .expect("Index out of bounds")
+        GridLike::to_string(&self.cells, ' ', f)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.data.iter()
+        self.cells.values()
     }
 
     pub fn iter_enumerate(&self) -> impl Iterator<Item = (Point, &T)> {
-        self.data
-            .iter()
-            .enumerate()
-            .map(|(i, v)| ((i % self.width, i / self.width).into(), v))
+        self.cells.iter().map(|(p, v)| (p.into(), v))
     }
 
     pub fn in_bounds(&self, p: impl Into<Point>) -> bool {
-        let p = p.into();
-
-        p.x >= 0 && p.x < (self.width as i32) && p.y >= 0 && p.y < (self.height as i32)
+        self.cells.in_bounds(pos(p.into()))
     }
 
     pub fn get(&self, p: impl Into<Point>) -> Option<&T> {
-        let p = p.into();
-
-        if !self.in_bounds(p) {
-            return None;
-        }
-
-        Some(&self.data[self.index(&p)])
+        self.cells.get(pos(p.into()))
     }
 
     pub fn get_mut(&mut self, p: impl Into<Point>) -> Option<&mut T> {
-        let p = p.into();
-
-        if !self.in_bounds(p) {
-            None
-        } else {
-            let index = self.index(&p);
-            Some(&mut self.data[index])
-        }
+        self.cells.get_mut(pos(p.into()))
     }
 
     pub fn set(&mut self, p: impl Into<Point>, value: T) -> bool {
-        let p = p.into();
-
-        if !self.in_bounds(p) {
-            return false;
-        }
-
-        let index = self.index(&p);
-        self.data[index] = value;
-        true
+        self.cells.set(pos(p.into()), value)
     }
 
     pub fn render(&self, f: &dyn Fn(&T) -> [u8; 3]) -> image::RgbImage {
         let mut image = image::RgbImage::new(self.width as u32, self.height as u32);
+        self.cells.render([0, 0, 0], f, &mut |x, y, color| {
+            image.put_pixel(x, y, image::Rgb(color))
+        });
+        image
+    }
 
-        for (p, v) in self.iter_enumerate() {
-            let color = f(v);
-            image.put_pixel(p.x as u32, p.y as u32, image::Rgb(color));
-        }
+    // A heatmap: `f` gives each cell a number, the colormap is stretched over the range they cover
+    // Non-finite values (f64::INFINITY for somewhere unreachable, say) are drawn black
+    pub fn render_values(
+        &self,
+        f: &dyn Fn(&T) -> f64,
+        colormap: colormap::Colormap,
+    ) -> image::RgbImage {
+        let scale = colormap::Scale::fit(self.iter().map(f), colormap);
+        self.render(&|v| {
+            let value = f(v);
+            match value.is_finite() {
+                true => scale.color(value),
+                false => [0, 0, 0],
+            }
+        })
+    }
 
+    // Scaled up (nearest neighbour) with the overlay drawn on top afterwards, so text and lines stay sharp
+    pub fn render_with_overlay(
+        &self,
+        f: &dyn Fn(&T) -> [u8; 3],
+        scale: u32,
+        overlay: &overlay::Overlay,
+    ) -> image::RgbImage {
+        let image = self.render(f);
+        let mut image = image::imageops::resize(
+            &image,
+            image.width() * scale,
+            image.height() * scale,
+            image::imageops::FilterType::Nearest,
+        );
+
+        let (width, height) = image.dimensions();
+        overlay.draw(width, height, scale, &mut |x, y, color| {
+            image.put_pixel(x, y, image::Rgb(color))
+        });
         image
     }
 }
@@ -144,43 +114,39 @@ where
     T: PartialEq + Clone + Default,
 {
     pub fn flood_fill(&self, p: impl Into<Point>) -> Vec<Point> {
-        let p = p.into();
+        GridLike::flood_fill(&self.cells, pos(p.into()))
+            .into_iter()
+            .map(Point::from)
+            .collect()
+    }
+}
 
-        if !self.in_bounds(p) {
-            return Vec::new();
+impl<T> From<Dense<T>> for Grid<T> {
+    fn from(cells: Dense<T>) -> Self {
+        Self {
+            width: cells.width(),
+            height: cells.height(),
+            cells,
         }
+    }
+}
 
-        let target = self.data[self.index(&p)].clone();
-
-        let mut stack = vec![p];
-        let mut visited = vec![false; self.data.len()];
-        let mut result = Vec::new();
-
-        while let Some(p) = stack.pop() {
-            if !self.in_bounds(p) {
-                continue;
-            }
-
-            if self.get(p) != Some(&target) {
-                continue;
-            }
-
-            let index = self.index(&p);
-
-            if visited[index] {
-                continue;
-            }
+impl<T> GridLike for Grid<T> {
+    type Item = T;
 
-            visited[index] = true;
+    fn bounds(&self) -> Bounds {
+        self.cells.bounds()
+    }
 
-            result.push(p);
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(p)
+    }
 
-            stack.push(p + Point::new(1, 0));
-            stack.push(p + Point::new(-1, 0));
-            stack.push(p + Point::new(0, 1));
-            stack.push(p + Point::new(0, -1));
-        }
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        self.cells.set(p, value)
+    }
 
-        result
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.cells()
     }
 }
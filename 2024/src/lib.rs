@@ -36,6 +36,8 @@ pub use point::Point;
 mod strgraph;
 pub use strgraph::StrGraph;
 
+pub mod output;
+
 mod make_test;
 
 extern crate aoc_runner;
@@ -0,0 +1,262 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+// Where the render bins put their frames, videos and images, and a record of everything written
+// Frames go in a temporary directory of the run's own, so two renders can run at once (and an output/ directory
+// that happens to be lying around is left alone). Videos and images go in `--out-dir` ($AOC_OUT_DIR, or the
+// current directory if neither is given), named by `--name-template` ($AOC_NAME_TEMPLATE), and each one gets a
+// line in manifest.jsonl there.
+
+pub const DEFAULT_TEMPLATE: &str = "{name}.{ext}";
+pub const MANIFEST: &str = "manifest.jsonl";
+
+const PLACEHOLDERS: [&str; 4] = ["bin", "name", "input", "ext"];
+const OPTIONS: [&str; 2] = ["--out-dir", "--name-template"];
+
+static FRAMES_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// Created if it isn't there, so it's fine to call again after cleanup() to start another video
+pub fn frames_dir() -> &'static Path {
+    let dir = FRAMES_DIR.get_or_init(|| {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_nanos();
+        std::env::temp_dir().join(format!("aoc2024_{}_{}_{nanos}", bin(), std::process::id()))
+    });
+    std::fs::create_dir_all(dir).unwrap();
+    dir
+}
+
+// Where frame `n` goes, numbered so ffmpeg's glob picks them up in order
+pub fn frame_path(n: usize) -> PathBuf {
+    frames_dir().join(format!("{n:0>8}.png"))
+}
+
+// How many frames are waiting to go in a video
+pub fn frame_count() -> usize {
+    std::fs::read_dir(frames_dir()).map_or(0, |entries| {
+        entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "png"))
+            .count()
+    })
+}
+
+pub fn out_dir() -> PathBuf {
+    let dir = option("--out-dir")
+        .or_else(|| std::env::var("AOC_OUT_DIR").ok())
+        .map_or_else(|| PathBuf::from("."), PathBuf::from);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn template() -> String {
+    option("--name-template")
+        .or_else(|| std::env::var("AOC_NAME_TEMPLATE").ok())
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string())
+}
+
+// The command line without --out-dir and --name-template, for bins that take arguments of their own
+pub fn args() -> Vec<String> {
+    let mut args = vec![];
+    let mut rest = std::env::args().skip(1);
+    while let Some(arg) = rest.next() {
+        if OPTIONS.contains(&arg.as_str()) {
+            rest.next();
+        } else if !OPTIONS.iter().any(|o| arg.starts_with(&format!("{o}="))) {
+            args.push(arg);
+        }
+    }
+    args
+}
+
+// `--name value` or `--name=value`
+fn option(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(name).and_then(|v| v.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+// The bin that's running, which is also the variant: the same day can have several renders
+fn bin() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| Some(p.file_stem()?.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "render".to_string())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    pub bin: String,
+    // The video or image
+    pub name: String,
+    // A hash of the input, empty until begin()
+    pub input: String,
+    pub ext: String,
+}
+
+// {bin}, {name}, {input} and {ext}, with {{ and }} for literal braces
+pub fn expand(template: &str, fields: &Fields) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let end = match (rest.starts_with('{'), rest.find('}')) {
+            (true, Some(end)) => end,
+            _ => return Err(format!("unmatched brace in name template {template:?}")),
+        };
+        out.push_str(match &rest[1..end] {
+            "bin" => &fields.bin,
+            "name" => &fields.name,
+            "input" => &fields.input,
+            "ext" => &fields.ext,
+            other => {
+                return Err(format!(
+                    "unknown placeholder {{{other}}} in name template, expected one of {}",
+                    PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+                ));
+            }
+        });
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// The same hash 2025's benches and renders use, so the same input gets the same name in both
+pub use input_hash::input_hash;
+
+static INPUT: Mutex<String> = Mutex::new(String::new());
+
+// Call before rendering anything
+// Checks the name template up front, a typo shouldn't only turn up once a long render has finished
+pub fn begin(input: &str) {
+    if let Err(e) = expand(&template(), &Fields::default()) {
+        eprintln!("{e}");
+        std::process::exit(2);
+    }
+    *INPUT.lock().unwrap() = input_hash(input);
+}
+
+// Where to write `name` (the output directory and anything the template puts in between are created)
+pub fn path_for(name: &str, ext: &str) -> PathBuf {
+    let fields = Fields {
+        bin: bin(),
+        name: name.to_string(),
+        input: INPUT.lock().unwrap().clone(),
+        ext: ext.to_string(),
+    };
+    let name = expand(&template(), &fields).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(2);
+    });
+    let path = out_dir().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    path
+}
+
+// Seconds and pid: unique on this machine, and sorts by when the run started
+pub fn run_id() -> &'static str {
+    static RUN_ID: OnceLock<String> = OnceLock::new();
+    RUN_ID.get_or_init(|| {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        format!("{secs}-{}", std::process::id())
+    })
+}
+
+// Add a line for `path` to the manifest in the output directory
+// `frames` is None for still images
+pub fn record(path: &Path, format: &str, frames: Option<usize>) {
+    let line = serde_json::json!({
+        "run": run_id(),
+        "path": path,
+        "format": format,
+        "frames": frames,
+        "variant": bin(),
+        "input": *INPUT.lock().unwrap(),
+    });
+
+    let manifest = out_dir().join(MANIFEST);
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&manifest)
+        .and_then(|mut f| writeln!(f, "{line}"));
+    if let Err(e) = result {
+        eprintln!("Failed to update {}: {:?}", manifest.display(), e);
+    }
+}
+
+// Run an ffmpeg command line (through sh, for the frame glob) that writes the video `out`
+// It only goes in the manifest if ffmpeg says it worked; a failed ffmpeg is only reported, like before
+pub fn ffmpeg(cmd: &str, out: &Path) {
+    match std::process::Command::new("sh").arg("-c").arg(cmd).status() {
+        Ok(status) if status.success() => record(out, "mp4", Some(frame_count())),
+        Ok(status) => eprintln!("ffmpeg failed ({status}), {} wasn't written", out.display()),
+        Err(err) => {
+            eprintln!("Failed to run ffmpeg: {:?}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Remove the frames once they've been turned into a video
+pub fn cleanup() {
+    if let Some(dir) = FRAMES_DIR.get() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let fields = Fields {
+            bin: "day16-part1-search-render".to_string(),
+            name: "day16-part1-search".to_string(),
+            input: "1234abcd".to_string(),
+            ext: "mp4".to_string(),
+        };
+
+        assert_eq!(
+            expand(DEFAULT_TEMPLATE, &fields).unwrap(),
+            "day16-part1-search.mp4"
+        );
+        assert_eq!(
+            expand("{bin}/{input}{{1}}.{ext}", &fields).unwrap(),
+            "day16-part1-search-render/1234abcd{1}.mp4"
+        );
+        assert!(expand("{nmae}.{ext}", &fields).is_err());
+        assert!(expand("{name", &fields).is_err());
+    }
+
+    #[test]
+    fn test_frame_path() {
+        let path = frame_path(12);
+        assert_eq!(path.file_name().unwrap(), "00000012.png");
+        assert_eq!(path.parent().unwrap(), frames_dir());
+        cleanup();
+    }
+}
@@ -1,3 +1,4 @@
+use graph_export::{Export, Graph};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
@@ -114,3 +115,17 @@ impl<'a> StrGraph<'a> {
             .collect()
     }
 }
+
+// Each edge is stored both ways round, but only drawn once
+impl Export for StrGraph<'_> {
+    fn graph(&self) -> Graph {
+        let mut graph = Graph::undirected("network");
+        for node in self.nodes.iter() {
+            graph.node(node);
+        }
+        for (a, b) in self.edges.iter().filter(|(a, b)| a < b) {
+            graph.edge(a, b);
+        }
+        graph
+    }
+}
@@ -0,0 +1,28 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ItemFn;
+use syn::parse_macro_input;
+
+pub fn generator_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(item as ItemFn);
+    let fn_name = func.sig.ident.clone();
+    let name_lit = fn_name.to_string();
+
+    // Fixed names, so a second generator in the same module is a (duplicate definition) compile error
+    // One in another module gets past that, register_generator panics instead
+    let expanded = quote! {
+        #func
+
+        #[doc(hidden)]
+        fn __aoc_generator_shim(input: &str) -> Box<dyn std::any::Any> { Box::new(#fn_name(input)) }
+
+        #[doc(hidden)]
+        static __AOC_GENERATOR: crate::__aoc::Generator = crate::__aoc::Generator { day: crate::__aoc::DAY, name: #name_lit, func: __aoc_generator_shim };
+
+        #[doc(hidden)]
+        #[::ctor::ctor]
+        fn __aoc_register_generator() { crate::__aoc::register_generator(&__AOC_GENERATOR); }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -17,17 +17,31 @@ pub fn test(input: TokenStream) -> TokenStream {
 }
 
 // Place #[aoc::register] to register it as an aoc solution.
+// Optionally: #[aoc::register(part = 2, tags = ["bruteforce"], slow, example = "...", expected_example = "...", expected = "...")]
+// `example` can be a const, so variants can share one; without it expected_example reads input/2025/<day>_example.txt
+// Solutions can take a second `progress: &aoc2025::harness::progress::Progress` argument to report how far along they are.
 mod register;
 
 #[proc_macro_attribute]
-pub fn register(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    register::register_impl(_attr, item)
+pub fn register(attr: TokenStream, item: TokenStream) -> TokenStream {
+    register::register_impl(attr, item)
+}
+
+// Place #[aoc::generator] on *one* fn(&str) -> T to parse the input once for every solution taking &T.
+// Registered solutions can then take `input: &T` instead of `input: &str`.
+mod generator;
+
+#[proc_macro_attribute]
+pub fn generator(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    generator::generator_impl(_attr, item)
 }
 
 // Macros for rendering images and frames.
 // Place #[aoc::register_render(...)] to register a render function.
 // Then use aoc::render_image! and aoc::render_frame! within that function.
 // Both take (width, height, f) where f is a closure (x, y) -> (r, g, b).
+// render_frame! also takes an optional `label = expr`, a caption shown under the frame (render --tty, --format html),
+// and `overlay = expr`, an aoc2025::overlay::Overlay of text and shapes drawn over the frame after it's scaled up.
 
 mod register_render;
 mod render_image;
@@ -58,6 +72,16 @@ pub fn render_svg_frame(input: TokenStream) -> TokenStream {
     render_frame::render_svg_frame_impl(input)
 }
 
+// Function-like macro: aoc::render_test!(text = "..." | file = "path", [render1, render2], frames = [first, last, 3, every = N], hash = N)
+// Runs each render on the input and compares the picked frames (default: first and last) against PNGs in tests/golden/<day>/,
+// or with `hash`, perceptual hashes at most N bits apart. `AOC_BLESS=1 cargo test` or `render-test --bless` updates them.
+mod render_test;
+
+#[proc_macro]
+pub fn render_test(input: TokenStream) -> TokenStream {
+    render_test::render_test_impl(input)
+}
+
 // Helper function to convert syn::Expr to String
 
 use syn::Expr;
@@ -12,14 +12,56 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
         mod __aoc {
             use std::sync::{Mutex, OnceLock};
             pub const DAY: &str = #day_str;
-            pub struct Entry { pub day: &'static str, pub name: &'static str, pub func: fn(&str) -> String }
+            pub const YEAR: u32 = 2025;
+            pub struct Entry {
+                pub day: &'static str,
+                pub name: &'static str,
+                pub func: fn(&str) -> String,
+                // Only for solutions taking the generator's output: solve from already parsed input
+                pub solve: Option<fn(&dyn std::any::Any) -> String>,
+                pub part: Option<u32>,
+                pub tags: &'static [&'static str],
+                pub slow: bool,
+                pub expected_example: Option<&'static str>,
+                pub expected: Option<&'static str>,
+            }
+
+            impl Entry {
+                pub fn matches(&self, part: Option<u32>, tag: Option<&str>) -> bool {
+                    part.is_none_or(|p| self.part == Some(p)) && tag.is_none_or(|t| self.tags.contains(&t))
+                }
+
+                // part 2, slow, #bruteforce
+                pub fn describe(&self) -> String {
+                    let mut parts = vec![];
+                    if let Some(p) = self.part { parts.push(format!("part {}", p)); }
+                    if self.slow { parts.push("slow".to_string()); }
+                    parts.extend(self.tags.iter().map(|t| format!("#{}", t)));
+                    parts.join(", ")
+                }
+            }
+
             pub struct RenderEntry { pub day: &'static str, pub name: &'static str, pub func: fn(&str) }
+            // One render on one input, from aoc::render_test!
+            pub struct RenderTestEntry { pub day: &'static str, pub name: &'static str, pub render: &'static str, pub func: fn(bool) -> Result<String, String> }
+            pub struct Generator { pub day: &'static str, pub name: &'static str, pub func: fn(&str) -> Box<dyn std::any::Any> }
 
             static REGISTRY: OnceLock<Mutex<Vec<&'static Entry>>> = OnceLock::new();
             static RENDER_REGISTRY: OnceLock<Mutex<Vec<&'static RenderEntry>>> = OnceLock::new();
+            static RENDER_TEST_REGISTRY: OnceLock<Mutex<Vec<&'static RenderTestEntry>>> = OnceLock::new();
+            static GENERATOR: OnceLock<&'static Generator> = OnceLock::new();
 
             pub fn register(e: &'static Entry) { let reg = REGISTRY.get_or_init(|| Mutex::new(Vec::new())); reg.lock().unwrap().push(e); }
             pub fn register_render(e: &'static RenderEntry) { let reg = RENDER_REGISTRY.get_or_init(|| Mutex::new(Vec::new())); reg.lock().unwrap().push(e); }
+            pub fn register_render_test(e: &'static RenderTestEntry) { let reg = RENDER_TEST_REGISTRY.get_or_init(|| Mutex::new(Vec::new())); reg.lock().unwrap().push(e); }
+            // A day has one generator, every solution taking &T shares its output
+            pub fn register_generator(g: &'static Generator) {
+                if let Err(g) = GENERATOR.set(g) {
+                    panic!("{} has two #[aoc::generator]s: {} and {}", g.day, GENERATOR.get().unwrap().name, g.name);
+                }
+            }
+
+            pub fn generator() -> Option<&'static Generator> { GENERATOR.get().copied() }
 
             pub fn entries_for_day(day: &str) -> Vec<&'static Entry> {
                 let reg = REGISTRY.get_or_init(|| Mutex::new(Vec::new()));
@@ -33,6 +75,12 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
                 v.sort_by(|a,b| a.name.cmp(b.name)); v
             }
 
+            pub fn render_test_entries_for_day(day: &str) -> Vec<&'static RenderTestEntry> {
+                let reg = RENDER_TEST_REGISTRY.get_or_init(|| Mutex::new(Vec::new()));
+                let mut v: Vec<&'static RenderTestEntry> = reg.lock().unwrap().iter().copied().filter(|e| e.day == day).collect();
+                v.sort_by(|a,b| a.name.cmp(b.name)); v
+            }
+
             pub fn get(day: &str, name: &str) -> Option<&'static Entry> {
                 let reg = REGISTRY.get_or_init(|| Mutex::new(Vec::new()));
                 reg.lock().unwrap().iter().copied().find(|e| e.day == day && e.name == name)
@@ -67,10 +115,31 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
 
             #[derive(Subcommand)]
             enum Commands {
-                List,
+                List(FilterArgs),
+                Fetch(FetchArgs),
                 Render(RenderArgs),
+                RenderTest(RenderTestArgs),
                 Run(RunArgs),
                 Bench(BenchArgs),
+                Submit(SubmitArgs),
+                Verify(VerifyArgs),
+            }
+
+            #[derive(Args)]
+            struct FilterArgs {
+                /// Only solutions for this part
+                #[arg(long)]
+                part: Option<u32>,
+                /// Only solutions with this tag
+                #[arg(long)]
+                tag: Option<String>,
+            }
+
+            #[derive(Args)]
+            struct FetchArgs {
+                /// Download again even if the input is already cached
+                #[arg(long)]
+                force: bool,
             }
 
             #[derive(Args)]
@@ -80,8 +149,32 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
                 all: bool,
                 /// Name of the render solution to run (if not using --all)
                 name: Option<String>,
-                /// Input path (use '-' for stdin)
+                /// Input path (use '-' for stdin, default: input/2025/<day>.txt, fetched if missing)
                 input: Option<String>,
+                /// Animation format: gif, apng, webp, mp4, or html (default: whatever the render asks for)
+                #[arg(long)]
+                format: Option<aoc2025::harness::encode::Format>,
+                /// Stop recording after this many frames (default: whatever the render asks for, or no limit)
+                #[arg(long)]
+                max_frames: Option<usize>,
+                /// Play the frames in the terminal (space: pause, n: step, +/-: speed, q: quit) instead of writing a file
+                #[arg(long)]
+                tty: bool,
+                /// Directory to write renders to, and the manifest.jsonl listing them (default: the current directory)
+                #[arg(long)]
+                out_dir: Option<std::path::PathBuf>,
+                /// File names, from {year} {day} {name} {variant} {input} (a hash of the input) and {ext}
+                #[arg(long, default_value = aoc2025::harness::output::DEFAULT_TEMPLATE)]
+                name_template: String,
+            }
+
+            #[derive(Args)]
+            struct RenderTestArgs {
+                /// Write the goldens from what the renders draw now instead of checking against them
+                #[arg(long)]
+                bless: bool,
+                /// Only the render tests for this render (default: all of them)
+                name: Option<String>,
             }
 
             #[derive(Args)]
@@ -91,8 +184,25 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
                 all: bool,
                 /// Name of the solution to run (if not using --all)
                 name: Option<String>,
-                /// Input path (use '-' for stdin)
+                /// Input path (use '-' for stdin, default: input/2025/<day>.txt, fetched if missing)
                 input: Option<String>,
+                #[command(flatten)]
+                filter: FilterArgs,
+                /// Include solutions marked slow in --all
+                #[arg(long)]
+                slow: bool,
+                /// Kill a solution after this many seconds
+                #[arg(long)]
+                timeout: Option<f64>,
+                /// Limit each solution's memory (in MB)
+                #[arg(long)]
+                memory: Option<u64>,
+                /// Run --all solutions in this process instead of one process each (no timeout, memory cap, or panic recovery)
+                #[arg(long)]
+                in_process: bool,
+                /// Print newline delimited JSON events (start, progress, best, done, failed) instead of answers
+                #[arg(long)]
+                json: bool,
             }
 
             #[derive(Args)]
@@ -102,69 +212,96 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
                 all: bool,
                 /// Name of the solution to bench (if not using --all)
                 name: Option<String>,
-                /// Input path (use '-' for stdin)
+                /// Input path (use '-' for stdin, default: input/2025/<day>.txt, fetched if missing)
                 input: Option<String>,
+                #[command(flatten)]
+                filter: FilterArgs,
+                /// Include solutions marked slow in --all
+                #[arg(long)]
+                slow: bool,
                 /// Number of warmup runs (default: 3)
                 #[arg(long, default_value_t = 3)]
                 warmup: usize,
                 /// Number of benchmark iterations (default: 100)
                 #[arg(long, default_value_t = 100)]
                 iters: usize,
+                /// Output format: human, json, or csv (default: human)
+                #[arg(long, default_value = "human")]
+                format: aoc2025::harness::bench::BenchFormat,
+                /// Write the report to this file instead of stdout
+                #[arg(long)]
+                output: Option<String>,
+                /// Compare medians against a previous `--format json` report
+                #[arg(long)]
+                compare: Option<String>,
+                /// Percent slowdown (vs --compare) that counts as a regression (default: 10)
+                #[arg(long, default_value_t = 10.0)]
+                threshold: f64,
+            }
+
+            #[derive(Args)]
+            struct SubmitArgs {
+                /// Name of the solution whose answer to submit (part1* or part2*)
+                name: String,
+                /// Input path (use '-' for stdin, default: input/2025/<day>.txt, fetched if missing)
+                input: Option<String>,
+            }
+
+            #[derive(Args)]
+            struct VerifyArgs {
+                /// Input path (use '-' for stdin, default: input/2025/<day>.txt, fetched if missing)
+                input: Option<String>,
+                /// Also compare variants on this many mutated copies of the input
+                #[arg(long, default_value_t = 0)]
+                fuzz: usize,
+                /// Seed for --fuzz (default: based on the current time)
+                #[arg(long)]
+                seed: Option<u64>,
+                /// Include solutions marked slow
+                #[arg(long)]
+                slow: bool,
             }
 
             let cli = Cli::parse();
 
-            let read_input = |input_path: String| -> String {
-                if input_path == "-" { use std::io::Read; let mut s = String::new(); std::io::stdin().read_to_string(&mut s).expect("failed to read stdin"); s } else { std::fs::read_to_string(&input_path).unwrap_or_else(|e| panic!("failed to read {}: {}", input_path, e)) }
+            // Registered solutions for the day, narrowed down by --part/--tag (and skipping slow ones unless asked for)
+            let filtered_entries = |filter: &FilterArgs, include_slow: bool| -> Vec<&'static crate::__aoc::Entry> {
+                let (slow, entries): (Vec<_>, Vec<_>) = crate::__aoc::entries_for_day(day)
+                    .into_iter()
+                    .filter(|e| e.matches(filter.part, filter.tag.as_deref()))
+                    .partition(|e| e.slow && !include_slow);
+                if !slow.is_empty() { eprintln!("Skipping {} slow solution(s), use --slow to include them", slow.len()); }
+                entries
             };
 
-            let benchmark = |name: &str, func: fn(&str) -> String, input: &str, warmup: usize, iters: usize| {
-                // Warmup
-                for _ in 0..warmup { let _ = func(input); }
+            let input_config = aoc2025::harness::input::InputConfig::from_env();
 
-                // Benchmark
-                let mut times = Vec::with_capacity(iters);
-                for _ in 0..iters {
-                    let start = std::time::Instant::now();
-                    let _ = func(input);
-                    times.push(start.elapsed());
-                }
-
-                times.sort();
-                let min = times[0];
-                let max = times[iters - 1];
-                let median = times[iters / 2];
-
-                // Compute average and standard deviation (in nanoseconds)
-                let sum_ns: u128 = times.iter().map(|d| d.as_nanos()).sum();
-                let avg_ns = sum_ns as f64 / (iters as f64);
-                let avg = std::time::Duration::from_nanos(avg_ns as u64);
-
-                let var_ns = times
-                    .iter()
-                    .map(|d| {
-                        let x = d.as_nanos() as f64;
-                        let diff = x - avg_ns;
-                        diff * diff
-                    })
-                    .sum::<f64>()
-                    / (iters as f64);
-                let stddev_ns = var_ns.sqrt();
-                let stddev = std::time::Duration::from_nanos(stddev_ns as u64);
-
-                println!("{name}: {avg:?} ± {stddev:?} [min: {min:?}, max: {max:?}, median: {median:?}]");
+            let read_input = |input_path: Option<String>| -> String {
+                // No path given, use the cached input for the day (downloading it if needed)
+                let input_path = match input_path {
+                    Some(p) => p,
+                    None => match aoc2025::harness::input::resolve(&input_config, crate::__aoc::YEAR, day) {
+                        Ok(p) => p.to_string_lossy().to_string(),
+                        Err(e) => { eprintln!("No input path given and the default input is unavailable: {}", e); std::process::exit(2); }
+                    },
+                };
+                if input_path == "-" { use std::io::Read; let mut s = String::new(); std::io::stdin().read_to_string(&mut s).expect("failed to read stdin"); s } else { std::fs::read_to_string(&input_path).unwrap_or_else(|e| panic!("failed to read {}: {}", input_path, e)) }
             };
 
             match cli.command {
-                Commands::List => {
-                    let entries = crate::__aoc::entries_for_day(day);
+                Commands::List(filter) => {
+                    let entries = filtered_entries(&filter, true);
                     let render_entries = crate::__aoc::render_entries_for_day(day);
                     if entries.is_empty() && render_entries.is_empty() {
                         println!("No solutions registered for {}", day);
                     } else {
                         if !entries.is_empty() {
                             println!("Solutions:");
-                            for e in entries { println!("  {}", e.name); }
+                            let width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+                            for e in entries {
+                                let description = e.describe();
+                                if description.is_empty() { println!("  {}", e.name); } else { println!("  {:<width$}  ({})", e.name, description, width = width); }
+                            }
                         }
                         if !render_entries.is_empty() {
                             println!("\nRender:");
@@ -172,96 +309,267 @@ pub fn main_impl(input: TokenStream) -> TokenStream {
                         }
                     }
                 }
+                Commands::Fetch(args) => {
+                    let path = input_config.path_for(crate::__aoc::YEAR, day);
+                    let result = if args.force || !path.exists() {
+                        aoc2025::harness::input::fetch(&input_config, crate::__aoc::YEAR, day)
+                    } else {
+                        Ok(path)
+                    };
+                    match result {
+                        Ok(p) => println!("{}", p.display()),
+                        Err(e) => { eprintln!("{}", e); std::process::exit(1); }
+                    }
+                }
                 Commands::Render(args) => {
+                    if let Some(format) = args.format { aoc2025::harness::encode::set_format_override(format); }
+                    if let Some(max_frames) = args.max_frames { aoc2025::harness::frames::set_max_frames_override(max_frames); }
+                    if args.tty { aoc2025::harness::tty::enable(); }
+                    if let Some(out_dir) = args.out_dir { aoc2025::harness::output::set_out_dir(out_dir); }
+                    if let Err(e) = aoc2025::harness::output::set_template(&args.name_template) { eprintln!("{}", e); std::process::exit(2); }
                     if args.all {
-                        let input_path = match args.input {
-                            Some(ip) => ip,
-                            None => match args.name {
-                                Some(pos) => pos,
-                                None => {
-                                    eprintln!("Missing input path for --all. Provide an input path (use '-' for stdin) or --input <FILE>");
-                                    std::process::exit(2);
-                                }
-                            },
-                        };
-                        let input = read_input(input_path);
+                        let input = read_input(args.input.or(args.name));
                         let entries = crate::__aoc::render_entries_for_day(day);
                         if entries.is_empty() { eprintln!("No render solutions registered for {}", day); std::process::exit(3); }
-                        for e in entries { (e.func)(&input); }
+                        for e in entries { aoc2025::harness::output::begin(e.name, &input); (e.func)(&input); }
                     } else {
                         let name = match args.name {
                             Some(n) => n,
                             None => { eprintln!("Missing solution name. Try 'list' to see registered names."); std::process::exit(2); }
                         };
-                        let input_path = match args.input {
-                            Some(i) => i,
-                            None => { eprintln!("Missing input path. Provide an input path (use '-' for stdin) or --input <FILE>"); std::process::exit(2); }
-                        };
-                        let input = read_input(input_path);
-                        match crate::__aoc::get_render(day, &name) { Some(entry) => { (entry.func)(&input); } None => { eprintln!("No such render solution: {}. Try 'list'.", name); std::process::exit(3); } }
+                        let input = read_input(args.input);
+                        match crate::__aoc::get_render(day, &name) { Some(entry) => { aoc2025::harness::output::begin(entry.name, &input); (entry.func)(&input); } None => { eprintln!("No such render solution: {}. Try 'list'.", name); std::process::exit(3); } }
                     }
+                    // Spooled frames and the like, everything worth keeping is in the output directory by now
+                    aoc2025::harness::output::cleanup();
+                }
+                Commands::RenderTest(args) => {
+                    let entries: Vec<_> = crate::__aoc::render_test_entries_for_day(day).into_iter().filter(|e| args.name.as_deref().is_none_or(|n| n == e.render)).collect();
+                    if entries.is_empty() { eprintln!("No render tests registered for {}", day); std::process::exit(3); }
+                    let mut failed = 0;
+                    for e in entries {
+                        match (e.func)(args.bless) {
+                            Ok(message) => println!("{}: ok, {}", e.name, message),
+                            Err(message) => { failed += 1; println!("{}: FAILED\n{}", e.name, message); }
+                        }
+                    }
+                    if failed > 0 { eprintln!("{} render test(s) failed", failed); std::process::exit(1); }
                 }
                 Commands::Run(args) => {
-                    if args.all {
-                        // If --all is provided, require an input path. For backward compatibility we
-                        // accept that callers may have supplied the input as the positional `name`.
-                        let input_path = match args.input {
-                            Some(ip) => ip,
-                            None => match args.name {
-                                Some(pos) => pos,
-                                None => {
-                                    eprintln!("Missing input path for --all. Provide an input path (use '-' for stdin) or --input <FILE>");
-                                    std::process::exit(2);
-                                }
-                            },
+                    use aoc2025::harness::isolate::{run_isolated_with, Limits, Status};
+                    use aoc2025::harness::progress::{self, Event};
+
+                    let limits = Limits {
+                        timeout: args.timeout.map(std::time::Duration::from_secs_f64),
+                        memory_mb: args.memory,
+                    };
+                    let isolated = !args.in_process && (args.all || limits.timeout.is_some() || limits.memory_mb.is_some());
+
+                    // Isolated children pass their progress back to us, which then goes to the bar or --json
+                    let forwarding = std::env::var_os(progress::FORWARD_ENV).is_some();
+                    let sink = if forwarding { progress::forward_sink() } else if args.json { progress::json_sink() } else { progress::bar_sink() };
+                    let emit = |event: Event| if !forwarding { sink(&event) };
+
+                    // Run a single entry in a child process (this same binary), passing the input on stdin
+                    let run_child = |name: &str, input: &str| -> Status {
+                        let exe = std::env::current_exe().expect("cannot find current executable");
+                        let mut cmd = std::process::Command::new(exe);
+                        cmd.arg("run").arg(name).arg("-").env(progress::FORWARD_ENV, "1");
+                        run_isolated_with(cmd, input, &limits, |line| match progress::parse_forwarded(line) {
+                            Some(event) => { sink(&event); true }
+                            None => false,
+                        })
+                    };
+
+                    // Run one entry (in process or not), reporting start and finish; returns the answer or what went wrong
+                    let run_entry = |e: &'static crate::__aoc::Entry, input: &str, parsed: Option<&dyn std::any::Any>| -> Result<String, Status> {
+                        emit(Event::Start { name: e.name.to_string() });
+                        let start = std::time::Instant::now();
+                        let result = if isolated {
+                            match run_child(e.name, input) { Status::Ok(out) => Ok(out), status => Err(status) }
+                        } else {
+                            let _installed = progress::install(e.name, sink.clone());
+                            Ok(match (e.solve, parsed) { (Some(solve), Some(parsed)) => solve(parsed), _ => (e.func)(input) })
                         };
-                        let input = read_input(input_path);
-                        let entries = crate::__aoc::entries_for_day(day);
-                        if entries.is_empty() { eprintln!("No solutions registered for {}", day); std::process::exit(3); }
-                        for e in entries { let out = (e.func)(&input); println!("{}: {}", e.name, out); }
+                        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+                        emit(match &result {
+                            Ok(answer) => Event::Done { name: e.name.to_string(), answer: answer.clone(), elapsed_ms },
+                            Err(status) => Event::Failed { name: e.name.to_string(), status: status.to_string(), elapsed_ms },
+                        });
+                        result
+                    };
+
+                    if args.all {
+                        // With --all, the positional `name` is treated as the input path (if given).
+                        let input = read_input(args.input.or(args.name));
+                        let entries = filtered_entries(&args.filter, args.slow);
+                        if entries.is_empty() { eprintln!("No matching solutions registered for {}", day); std::process::exit(3); }
+
+                        // In process, parse once and share it between every solution that takes the generator's output
+                        let parsed = crate::__aoc::generator()
+                            .filter(|_| !isolated && entries.iter().any(|e| e.solve.is_some()))
+                            .map(|g| (g.func)(&input));
+
+                        let mut failed = false;
+                        for e in entries {
+                            let result = run_entry(e, &input, parsed.as_deref());
+                            failed |= result.is_err();
+                            if !args.json {
+                                match result { Ok(out) => println!("{}: {}", e.name, out), Err(status) => println!("{}: {}", e.name, status) }
+                            }
+                        }
+                        if failed { std::process::exit(1); }
                     } else {
                         let name = match args.name {
                             Some(n) => n,
                             None => { eprintln!("Missing solution name. Try 'list' to see registered names."); std::process::exit(2); }
                         };
-                        let input_path = match args.input {
-                            Some(i) => i,
-                            None => { eprintln!("Missing input path. Provide an input path (use '-' for stdin) or --input <FILE>"); std::process::exit(2); }
+                        let input = read_input(args.input);
+                        let entry = match crate::__aoc::get(day, &name) {
+                            Some(entry) => entry,
+                            None => { eprintln!("No such solution: {}. Try 'list'.", name); std::process::exit(3); }
                         };
-                        let input = read_input(input_path);
-                        match crate::__aoc::get(day, &name) { Some(entry) => { let out = (entry.func)(&input); println!("{}", out); } None => { eprintln!("No such solution: {}. Try 'list'.", name); std::process::exit(3); } }
+                        match run_entry(entry, &input, None) {
+                            Ok(out) => if !args.json { println!("{}", out) },
+                            Err(status) => { if !args.json { eprintln!("{}", status); } std::process::exit(1); }
+                        }
                     }
                 }
                 Commands::Bench(args) => {
+                    use aoc2025::harness::bench::{compare, BenchFormat, BenchReport};
+
                     // Validate iteration count
                     if args.iters == 0 { eprintln!("--iters must be >= 1"); std::process::exit(2); }
 
-                    if args.all {
-                        // If --all is used, require an input path (positional or via --input)
-                        let input_path = match args.input {
-                            Some(ip) => ip,
-                            None => match args.name {
-                                Some(pos) => pos,
-                                None => { eprintln!("Missing input path for --all. Provide an input path (use '-' for stdin) or --input <FILE>"); std::process::exit(2); }
-                            },
-                        };
-                        let input = read_input(input_path);
-                        let entries = crate::__aoc::entries_for_day(day);
-                        if entries.is_empty() { eprintln!("No solutions registered for {}", day); std::process::exit(3); }
-                        for e in entries { benchmark(e.name, e.func, &input, args.warmup, args.iters); }
+                    let (input, entries) = if args.all {
+                        // With --all, the positional `name` is treated as the input path (if given).
+                        let input = read_input(args.input.or(args.name));
+                        let entries = filtered_entries(&args.filter, args.slow);
+                        if entries.is_empty() { eprintln!("No matching solutions registered for {}", day); std::process::exit(3); }
+                        (input, entries)
                     } else {
                         let name = match args.name {
                             Some(n) => n,
                             None => { eprintln!("Missing solution name. Try 'list' to see registered names."); std::process::exit(2); }
                         };
-                        let input_path = match args.input {
-                            Some(i) => i,
-                            None => { eprintln!("Missing input path. Provide an input path (use '-' for stdin) or --input <FILE>"); std::process::exit(2); }
+                        let input = read_input(args.input);
+                        match crate::__aoc::get(day, &name) { Some(entry) => (input, vec![entry]), None => { eprintln!("No such solution: {}. Try 'list'.", name); std::process::exit(3); } }
+                    };
+
+                    let mut report = BenchReport::new(day, &input, args.warmup, args.iters);
+                    // Human output is printed as we go, since benchmarks can be slow
+                    let print_progress = args.format == BenchFormat::Human && args.output.is_none();
+
+                    // With a generator, parsing is timed once on its own and solutions taking its output are timed solving only
+                    let parsed = match crate::__aoc::generator().filter(|_| entries.iter().any(|e| e.solve.is_some())) {
+                        Some(g) => {
+                            let result = aoc2025::harness::bench::run(g.name, || (g.func)(&input), args.warmup, args.iters);
+                            if print_progress { println!("{} (parse)", result); }
+                            report.results.push(result);
+                            Some((g.func)(&input))
+                        }
+                        None => None,
+                    };
+
+                    for e in entries {
+                        let result = match (e.solve, &parsed) {
+                            (Some(solve), Some(parsed)) => aoc2025::harness::bench::run(e.name, || solve(&**parsed), args.warmup, args.iters),
+                            _ => aoc2025::harness::bench::run(e.name, || (e.func)(&input), args.warmup, args.iters),
                         };
-                        let input = read_input(input_path);
-                        match crate::__aoc::get(day, &name) { Some(entry) => { benchmark(entry.name, entry.func, &input, args.warmup, args.iters); } None => { eprintln!("No such solution: {}. Try 'list'.", name); std::process::exit(3); } }
+                        if print_progress {
+                            if e.solve.is_some() { println!("{} (solve)", result); } else { println!("{}", result); }
+                        }
+                        report.results.push(result);
+                    }
+
+                    match &args.output {
+                        Some(path) => std::fs::write(path, report.render(args.format)).unwrap_or_else(|e| { eprintln!("failed to write {}: {}", path, e); std::process::exit(1); }),
+                        None if args.format != BenchFormat::Human => print!("{}", report.render(args.format)),
+                        None => {}
+                    }
+
+                    if let Some(baseline_path) = &args.compare {
+                        let baseline = std::fs::read_to_string(baseline_path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|s| BenchReport::from_json(&s))
+                            .unwrap_or_else(|e| { eprintln!("failed to read baseline {}: {}", baseline_path, e); std::process::exit(1); });
+                        if baseline.input_hash != report.input_hash {
+                            eprintln!("Warning: baseline was run on a different input ({} vs {})", baseline.input_hash, report.input_hash);
+                        }
+
+                        let comparisons = compare(&report, &baseline, args.threshold / 100.0);
+                        for c in &comparisons { eprintln!("{}", c); }
+                        if comparisons.iter().any(|c| c.regressed) { std::process::exit(5); }
+                    }
+                }
+                Commands::Submit(args) => {
+                    use aoc2025::harness::submit::{level_for, submit, Ledger, Verdict};
+
+                    let entry = match crate::__aoc::get(day, &args.name) {
+                        Some(entry) => entry,
+                        None => { eprintln!("No such solution: {}. Try 'list'.", args.name); std::process::exit(3); }
+                    };
+                    let level = match entry.part.map(|p| p as u8).or_else(|| level_for(entry.name)) {
+                        Some(level) => level,
+                        None => { eprintln!("Cannot tell which part {} solves; names must start with part1 or part2", entry.name); std::process::exit(2); }
+                    };
+
+                    let input = read_input(args.input);
+                    let answer = (entry.func)(&input);
+                    println!("{}: {}", entry.name, answer);
+
+                    let ledger_path = Ledger::path_for(&input_config, crate::__aoc::YEAR, day);
+                    let mut ledger = Ledger::load(&ledger_path).unwrap_or_else(|e| { eprintln!("{}", e); std::process::exit(1); });
+                    if let Err(refusal) = ledger.check(level, &answer) {
+                        eprintln!("Not submitting: {}", refusal);
+                        std::process::exit(4);
+                    }
+
+                    let attempt = submit(&input_config, crate::__aoc::YEAR, day, level, &answer).unwrap_or_else(|e| { eprintln!("{}", e); std::process::exit(1); });
+                    match attempt.hint {
+                        Some(hint) => println!("{} ({})", attempt.verdict, hint),
+                        None => println!("{}", attempt.verdict),
+                    }
+
+                    // Rate limited attempts tell us nothing about the answer, so don't keep them
+                    if attempt.verdict != Verdict::TooRecent {
+                        ledger.record(attempt).unwrap_or_else(|e| { eprintln!("{}", e); std::process::exit(1); });
                     }
                 }
+                Commands::Verify(args) => {
+                    use aoc2025::harness::verify::{check_group, fuzz, group_by_part};
+
+                    let input = read_input(args.input);
+                    let (slow, entries): (Vec<_>, Vec<_>) = crate::__aoc::entries_for_day(day).into_iter().partition(|e| e.slow && !args.slow);
+                    if !slow.is_empty() { eprintln!("Skipping {} slow solution(s), use --slow to include them", slow.len()); }
+                    let solvers = entries.into_iter().map(|e| ((e.name, e.func), e.part)).collect::<Vec<_>>();
+                    if solvers.is_empty() { eprintln!("No solutions registered for {}", day); std::process::exit(3); }
+                    let groups = group_by_part(&solvers);
+
+                    let mut failed = false;
+                    for (part, solvers) in &groups {
+                        match check_group(part, solvers, &input, false) {
+                            Some(d) => { failed = true; print!("{}", d); }
+                            None => println!("{}: {} variant(s) agree", part, solvers.len()),
+                        }
+                    }
+
+                    if args.fuzz > 0 {
+                        let seed = args.seed.unwrap_or_else(|| std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).expect("Time went backwards").as_secs());
+                        println!("Fuzzing {} mutated inputs (seed {})", args.fuzz, seed);
+
+                        for (fuzzed_input, d) in fuzz(&groups, &input, args.fuzz, seed) {
+                            failed = true;
+                            // The pid keeps two runs with the same seed (or clock second) from writing over each other
+                            let path = std::env::temp_dir().join(format!("aoc2025_{}_{}_fuzz_{}_{}.txt", day, d.part, seed, std::process::id()));
+                            std::fs::write(&path, &fuzzed_input).expect("failed to save fuzzed input");
+                            print!("{}", d);
+                            println!("Input saved to {}", path.display());
+                        }
+                    }
+
+                    if failed { std::process::exit(6); }
+                }
             }
         }
     };
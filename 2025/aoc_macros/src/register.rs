@@ -1,10 +1,69 @@
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
-use syn::ItemFn;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Expr, FnArg, ItemFn, Lit, LitStr, Meta, Token, Type};
 use syn::parse_macro_input;
 
-pub fn register_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+// Everything that can go in #[aoc::register(...)]
+// part = 2, tags = ["bruteforce"], slow, example = "..." (or a const), expected_example = "...", expected = "..."
+#[derive(Default)]
+struct RegisterArgs {
+    part: Option<u32>,
+    tags: Vec<LitStr>,
+    slow: bool,
+    example: Option<Expr>,
+    expected_example: Option<LitStr>,
+    expected: Option<LitStr>,
+}
+
+fn lit_str(expr: &Expr) -> syn::Result<LitStr> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) => Ok(s.clone()),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+fn parse_args(attr: TokenStream) -> syn::Result<RegisterArgs> {
+    let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse(attr)?;
+    let mut args = RegisterArgs::default();
+
+    for meta in metas {
+        match meta {
+            Meta::Path(p) if p.is_ident("slow") => args.slow = true,
+            Meta::NameValue(nv) => {
+                let key = nv.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+                match key.as_str() {
+                    "part" => match &nv.value {
+                        Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) => match i.base10_parse()? {
+                            p @ (1 | 2) => args.part = Some(p),
+                            _ => return Err(syn::Error::new_spanned(i, "days only have parts 1 and 2")),
+                        },
+                        other => return Err(syn::Error::new_spanned(other, "expected a part number")),
+                    },
+                    "tags" => match &nv.value {
+                        Expr::Array(a) => args.tags = a.elems.iter().map(lit_str).collect::<syn::Result<_>>()?,
+                        other => return Err(syn::Error::new_spanned(other, "expected a list of tags: [\"a\", \"b\"]")),
+                    },
+                    "example" => args.example = Some(nv.value.clone()),
+                    "expected_example" => args.expected_example = Some(lit_str(&nv.value)?),
+                    "expected" => args.expected = Some(lit_str(&nv.value)?),
+                    _ => return Err(syn::Error::new_spanned(nv.path, format!("unknown register option `{}`", key))),
+                }
+            }
+            other => return Err(syn::Error::new_spanned(other, "expected `slow` or `key = value`")),
+        }
+    }
+
+    Ok(args)
+}
+
+pub fn register_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match parse_args(attr) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let func = parse_macro_input!(item as ItemFn);
     let fn_name = func.sig.ident.clone();
     let name_str = fn_name.to_string();
@@ -12,21 +71,127 @@ pub fn register_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let entry_ident: Ident = quote::format_ident!("__AOC_ENTRY_{}", fn_name.to_string().to_uppercase());
     let shim_ident: Ident = quote::format_ident!("__aoc_shim_{}", fn_name);
     let reg_ident: Ident = quote::format_ident!("__aoc_register_{}", fn_name);
+    let solve_ident: Ident = quote::format_ident!("__aoc_solve_{}", fn_name);
+
+    // Solutions taking &str get the raw input, anything else (&T) gets the output of the day's #[aoc::generator]
+    let parsed_type = match func.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) => match &*arg.ty {
+            Type::Reference(r) if !matches!(&*r.elem, Type::Path(p) if p.path.is_ident("str")) => Some((*r.elem).clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    // Without an explicit part, fall back to the naming convention (part1*, part2*)
+    let part = args.part.or_else(|| match name_str.strip_prefix("part")?.chars().next()? {
+        '1' => Some(1),
+        '2' => Some(2),
+        _ => None,
+    });
+    let part_tokens = match part {
+        Some(p) => quote! { Some(#p) },
+        None => quote! { None },
+    };
+    let option_tokens = |s: &Option<LitStr>| match s {
+        Some(s) => quote! { Some(#s) },
+        None => quote! { None },
+    };
+    let expected_example_tokens = option_tokens(&args.expected_example);
+    let expected_tokens = option_tokens(&args.expected);
+    let tags = &args.tags;
+    let slow = args.slow;
 
     let name_lit = name_str;
 
+    // An optional second argument gets a progress context: fn part2(input: &str, progress: &Progress)
+    let progress_arg = if func.sig.inputs.len() > 1 {
+        quote! { , &aoc2025::harness::progress::Progress::current() }
+    } else {
+        quote! {}
+    };
+
+    let (solver_fns, solve_tokens) = match &parsed_type {
+        None => (
+            quote! {
+                #[doc(hidden)]
+                fn #shim_ident(input: &str) -> String { #fn_name(input #progress_arg).into() }
+            },
+            quote! { None },
+        ),
+        Some(ty) => (
+            quote! {
+                #[doc(hidden)]
+                fn #solve_ident(parsed: &dyn std::any::Any) -> String {
+                    let parsed = parsed.downcast_ref::<#ty>().unwrap_or_else(|| panic!("{} takes a different type than the generator returns", #name_lit));
+                    #fn_name(parsed #progress_arg).into()
+                }
+
+                #[doc(hidden)]
+                fn #shim_ident(input: &str) -> String {
+                    let generator = crate::__aoc::generator().unwrap_or_else(|| panic!("{} takes parsed input, but there is no #[aoc::generator]", #name_lit));
+                    #solve_ident(&*(generator.func)(input))
+                }
+            },
+            quote! { Some(#solve_ident) },
+        ),
+    };
+
+    // Same tests aoc::test! would generate for the expected answers
+    let example_test = args.expected_example.as_ref().map(|expected| {
+        let test_name = quote::format_ident!("test_expected_example_{}", fn_name);
+        let input_binding = match &args.example {
+            Some(text) => quote! { let input = #text.to_string(); },
+            None => quote! {
+                let path = format!("input/{}/{}_example.txt", crate::__aoc::YEAR, crate::__aoc::DAY);
+                let input = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e));
+            },
+        };
+        quote! {
+            #[test]
+            fn #test_name() {
+                #input_binding
+                let result = #shim_ident(&input);
+                assert_eq!(result, #expected, "test failed for {}", #name_lit);
+            }
+        }
+    });
+    let input_test = args.expected.as_ref().map(|expected| {
+        let test_name = quote::format_ident!("test_expected_{}", fn_name);
+        quote! {
+            #[test]
+            fn #test_name() {
+                let path = format!("input/{}/{}.txt", crate::__aoc::YEAR, crate::__aoc::DAY);
+                let input = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e));
+                let result = #shim_ident(&input);
+                assert_eq!(result, #expected, "test failed for {}", #name_lit);
+            }
+        }
+    });
+
     let expanded = quote! {
         #func
 
-        #[doc(hidden)]
-        fn #shim_ident(input: &str) -> String { #fn_name(input).into() }
+        #solver_fns
 
         #[doc(hidden)]
-        static #entry_ident: crate::__aoc::Entry = crate::__aoc::Entry { day: crate::__aoc::DAY, name: #name_lit, func: #shim_ident };
+        static #entry_ident: crate::__aoc::Entry = crate::__aoc::Entry {
+            day: crate::__aoc::DAY,
+            name: #name_lit,
+            func: #shim_ident,
+            solve: #solve_tokens,
+            part: #part_tokens,
+            tags: &[#(#tags),*],
+            slow: #slow,
+            expected_example: #expected_example_tokens,
+            expected: #expected_tokens,
+        };
 
         #[doc(hidden)]
         #[::ctor::ctor]
         fn #reg_ident() { crate::__aoc::register(&#entry_ident); }
+
+        #example_test
+        #input_test
     };
 
     TokenStream::from(expanded)
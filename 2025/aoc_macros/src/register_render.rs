@@ -4,16 +4,38 @@ use quote::{format_ident, quote};
 use syn::Expr;
 use syn::parse::Parser;
 
+// Mirrors aoc2025::harness::encode::Format, only to reject unknown formats at compile time
+enum Format { Gif, Apng, Webp, Mp4, Html }
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Format::Gif),
+            "apng" => Ok(Format::Apng),
+            "webp" => Ok(Format::Webp),
+            "mp4" => Ok(Format::Mp4),
+            "html" => Ok(Format::Html),
+            _ => Err(format!("unknown format {:?}, expected gif, apng, webp, mp4, or html", s)),
+        }
+    }
+}
+
 pub fn register_render_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let parser = syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated;
     let args = parser
         .parse(attr.into())
-        .expect("expected #[aoc::register_render(scale=N, fps=F, sample=S)] (all parameters optional)");
+        .expect("expected #[aoc::register_render(scale=N, fps=F, sample=S, format=\"gif\", colors=C, max_frames=M, dedup)] (all parameters optional)");
 
     // Parse optional parameters by name
     let mut scale_lit = syn::LitInt::new("1", proc_macro2::Span::call_site());
     let mut fps_lit = syn::LitInt::new("30", proc_macro2::Span::call_site());
     let mut sample_lit = syn::LitInt::new("1", proc_macro2::Span::call_site());
+    let mut format_lit = syn::LitStr::new("mp4", proc_macro2::Span::call_site());
+    let mut colors_lit: Option<syn::LitInt> = None;
+    let mut max_frames_lit: Option<syn::LitInt> = None;
+    let mut dedup = false;
 
     for arg in args.iter() {
         match arg {
@@ -21,20 +43,39 @@ pub fn register_render_impl(attr: TokenStream, item: TokenStream) -> TokenStream
                 // Named parameter: "scale = 4", "fps = 30", "sample = 100"
                 if let Expr::Path(path) = left.as_ref() {
                     let param_name = path.path.get_ident().map(|i| i.to_string());
-                    if let Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Int(lit_int),
-                        ..
-                    }) = right.as_ref()
-                    {
-                        match param_name.as_deref() {
+                    match right.as_ref() {
+                        Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(lit_int),
+                            ..
+                        }) => match param_name.as_deref() {
                             Some("scale") => scale_lit = lit_int.clone(),
                             Some("fps") => fps_lit = lit_int.clone(),
                             Some("sample") => sample_lit = lit_int.clone(),
+                            Some("colors") => colors_lit = Some(lit_int.clone()),
+                            Some("max_frames") => max_frames_lit = Some(lit_int.clone()),
                             _ => {}
+                        },
+                        // Named parameter: format = "gif" (or apng, webp, mp4)
+                        Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit_str),
+                            ..
+                        }) if param_name.as_deref() == Some("format") => {
+                            if let Err(e) = lit_str.value().parse::<Format>() {
+                                return syn::Error::new_spanned(lit_str, e).to_compile_error().into();
+                            }
+                            format_lit = lit_str.clone();
                         }
+                        // Named parameter: dedup = true
+                        Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Bool(lit_bool),
+                            ..
+                        }) if param_name.as_deref() == Some("dedup") => dedup = lit_bool.value,
+                        _ => {}
                     }
                 }
             }
+            // Flag: dedup
+            Expr::Path(path) if path.path.is_ident("dedup") => dedup = true,
             _ => {}
         }
     }
@@ -54,11 +95,21 @@ pub fn register_render_impl(attr: TokenStream, item: TokenStream) -> TokenStream
 
     let name_lit = name_str;
 
+    let colors_tokens = match &colors_lit {
+        Some(c) => quote! { Some(#c) },
+        None => quote! { None },
+    };
+
+    let max_frames_tokens = match &max_frames_lit {
+        Some(m) => quote! { Some(#m) },
+        None => quote! { None },
+    };
+
     
     let render_sample_code = if sample_lit.base10_parse::<usize>().unwrap() > 1 {
-        quote! { current % __AOC_RENDER_SAMPLE == 0 }
+        quote! { current % __AOC_RENDER_SAMPLE == 0 && aoc2025::harness::frames::accepting() }
     } else {
-        quote! { true } 
+        quote! { aoc2025::harness::frames::accepting() }
     };
 
     let expanded = quote! {
@@ -68,23 +119,13 @@ pub fn register_render_impl(attr: TokenStream, item: TokenStream) -> TokenStream
             pub const __AOC_RENDER_SAMPLE: usize = #sample_lit;
 
             thread_local! {
-                static __AOC_RENDER_FRAMES: std::cell::RefCell<Vec<::image::RgbImage>> = std::cell::RefCell::new(Vec::new());
                 static __AOC_RENDER_FRAME_COUNTER: std::cell::Cell<usize> = std::cell::Cell::new(0);
             }
 
+            // Frames go straight to the sink installed by the function below, nothing is kept here
             #[allow(non_snake_case)]
-            pub fn __aoc_render_frames_push(frame: ::image::RgbImage) {
-                __AOC_RENDER_FRAMES.with(|frames| {
-                    log::info!("Rendering frame {}", frames.borrow().len());
-                    frames.borrow_mut().push(frame);
-                });
-            }
-
-            #[allow(non_snake_case)]
-            pub fn __aoc_render_frames_take() -> Vec<::image::RgbImage> {
-                __AOC_RENDER_FRAMES.with(|frames| {
-                    frames.borrow_mut().drain(..).collect()
-                })
+            pub fn __aoc_render_frames_push(frame: ::image::RgbImage, annotations: aoc2025::harness::frames::Annotations) {
+                aoc2025::harness::frames::push(frame, annotations);
             }
 
             #[allow(non_snake_case)]
@@ -99,75 +140,33 @@ pub fn register_render_impl(attr: TokenStream, item: TokenStream) -> TokenStream
 
         #fn_vis #fn_sig {
             use #module_ident::*;
-
-            // Execute the original function to collect frames
-            #fn_body
-
-            // Finalize video encoding using ffmpeg via subprocess
-            let frames_vec = __aoc_render_frames_take();
-
-            if !frames_vec.is_empty() {
-                let first_frame = &frames_vec[0];
-                let orig_width = first_frame.width();
-                let orig_height = first_frame.height();
-                let scale = #scale_lit;
-
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .expect("Time went backwards")
-                    .as_secs();
-                let temp_dir = std::path::PathBuf::from(format!("/tmp/aoc_render/{}", timestamp));
-                std::fs::create_dir_all(&temp_dir).expect("failed to create temp dir");
-
-                // Save frames without scaling - let ffmpeg handle it
-                for (idx, frame) in frames_vec.iter().enumerate() {
-                    let frame_path = temp_dir.join(format!("frame_{:04}.png", idx));
-                    frame.save(&frame_path).expect("failed to save frame");
-                }
-
-                // Use ffmpeg to create video from frames with scaling
-                let output_path = format!("aoc2025_{}_{}.mp4", crate::__aoc::DAY, stringify!(#fn_name));
-                let frame_pattern = temp_dir.join("frame_%04d.png").to_string_lossy().to_string();
-                let fps_str = format!("{}", #fps_lit);
-                let scale_filter = format!("scale={}:{}", orig_width * scale, orig_height * scale);
-
-                let mut cmd = std::process::Command::new("ffmpeg");
-                cmd.arg("-framerate").arg(&fps_str)
-                    .arg("-i").arg(&frame_pattern);
-
-                // Add scaling filter if scale != 1
-                if scale != 1 {
-                    cmd.arg("-vf").arg(format!("{}:flags=neighbor", scale_filter));
-                }
-
-                log::info!("Rendering video with ffmpeg");
-
-                cmd.arg("-c:v").arg("libx264")
-                    .arg("-pix_fmt").arg("yuv420p")
-                    .arg("-y")
-                    .arg("-movflags")
-                    .arg("+faststart")
-                    .arg(&output_path);
-
-                let output = cmd.output().expect("failed to run ffmpeg");
-
-                if !output.status.success() {
-                    log::error!("ffmpeg error: {}", String::from_utf8_lossy(&output.stderr));
-                } else {
-                    log::info!("ffmpeg succeeded");
-                    println!("Rendered {}", output_path);
-                }
-
-                // Clean up temp frames
-                for entry in std::fs::read_dir(&temp_dir).expect("failed to read temp dir") {
-                    if let Ok(entry) = entry {
-                        let path = entry.path();
-                        if path.extension().map_or(false, |ext| ext == "png") {
-                            let _ = std::fs::remove_file(&path);
+            use aoc2025::harness::{encode, frames, output, tty};
+
+            let options = encode::Options {
+                format: encode::format_override().unwrap_or_else(|| #format_lit.parse().expect("format was checked at compile time")),
+                fps: #fps_lit,
+                scale: #scale_lit,
+                colors: #colors_tokens,
+            };
+            let limits = frames::Limits { max_frames: frames::max_frames_override().or(#max_frames_tokens), dedup: #dedup };
+
+            // Frames are played in the terminal, or spooled as they're drawn and encoded when this drops (even if the render panics)
+            // Under aoc::render_test! they're already being captured, only the limits are ours
+            let _recording = match frames::capturing() {
+                true => { frames::set_limits(limits); None }
+                false => {
+                    let sink: Box<dyn frames::Sink> = match tty::enabled() {
+                        true => Box::new(tty::TtySink::new(options.fps)),
+                        false => {
+                            let output_path = output::path_for(crate::__aoc::YEAR, crate::__aoc::DAY, stringify!(#fn_name), options.format.extension()).expect("failed to create output directory");
+                            Box::new(frames::EncodeSink::new(options, output_path))
                         }
-                    }
+                    };
+                    Some(frames::record(sink, limits))
                 }
-            }
+            };
+
+            #fn_body
         }
 
         #[doc(hidden)]
@@ -3,46 +3,66 @@ use quote::quote;
 use syn::parse::Parser;
 use syn::{Expr, Token};
 
+// The optional named arguments after (width, height, f): force = true, label = expr and overlay = expr
+struct FrameOptions {
+    force_check: proc_macro2::TokenStream,
+    annotations: proc_macro2::TokenStream,
+}
+
+fn frame_options(args: &[Expr]) -> FrameOptions {
+    let mut force_check = quote! { __aoc_should_render_frame() };
+    let mut label = quote! { None };
+    let mut overlay = quote! { None };
+
+    for arg in args {
+        let Expr::Assign(syn::ExprAssign { left, right, .. }) = arg else {
+            continue;
+        };
+        let Expr::Path(path) = left.as_ref() else {
+            continue;
+        };
+
+        if path.path.is_ident("force") {
+            // Check if it's a boolean literal
+            if let Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(syn::LitBool { value: true, .. }),
+                ..
+            }) = right.as_ref()
+            {
+                force_check = quote! { true };
+            }
+        } else if path.path.is_ident("label") {
+            // Anything Display, only evaluated if the frame is drawn
+            label = quote! { Some((#right).to_string()) };
+        } else if path.path.is_ident("overlay") {
+            // An aoc2025::overlay::Overlay, drawn once the frame is scaled up
+            overlay = quote! { Some(#right) };
+        }
+    }
+
+    let annotations = quote! {
+        aoc2025::harness::frames::Annotations { label: #label, overlay: #overlay }
+    };
+    FrameOptions { force_check, annotations }
+}
+
 pub fn render_frame_impl(input: TokenStream) -> TokenStream {
     let parser = syn::punctuated::Punctuated::<Expr, Token![,]>::parse_terminated;
     let args = parser
         .parse(input.into())
-        .expect("expected aoc::render_frame!(width, height, closure) or aoc::render_frame!(width, height, closure, force = true)");
+        .expect("expected aoc::render_frame!(width, height, closure) or aoc::render_frame!(width, height, closure, force = true, label = expr, overlay = expr)");
 
     assert!(
-        args.len() == 3 || args.len() == 4,
-        "expected 3 or 4 arguments to aoc::render_frame!(width, height, closure[, force = true])"
+        (3..=6).contains(&args.len()),
+        "expected 3 to 6 arguments to aoc::render_frame!(width, height, closure[, force = true][, label = expr][, overlay = expr])"
     );
 
     let width_expr = &args[0];
     let height_expr = &args[1];
     let closure_expr = &args[2];
 
-    // Parse optional force parameter
-    let force_check = if args.len() > 3 {
-        match &args[3] {
-            Expr::Assign(syn::ExprAssign { right, .. }) => {
-                // Check if it's a boolean literal
-                match right.as_ref() {
-                    Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Bool(lit_bool),
-                        ..
-                    }) => {
-                        let val = lit_bool.value;
-                        if val {
-                            quote! { true }
-                        } else {
-                            quote! { __aoc_should_render_frame() }
-                        }
-                    }
-                    _ => quote! { __aoc_should_render_frame() },
-                }
-            }
-            _ => quote! { __aoc_should_render_frame() },
-        }
-    } else {
-        quote! { __aoc_should_render_frame() }
-    };
+    let args = args.iter().cloned().collect::<Vec<_>>();
+    let FrameOptions { force_check, annotations } = frame_options(&args[3..]);
 
     let expanded = quote! {
         {
@@ -54,13 +74,14 @@ pub fn render_frame_impl(input: TokenStream) -> TokenStream {
                 let mut frame = ::image::RgbImage::new(width as u32, height as u32);
                 for y in 0..height {
                     for x in 0..width {
-                        let (r, g, b) = f(x, y);
+                        // Either (r, g, b) or [r, g, b], so colormap colours go straight in
+                        let [r, g, b] = aoc2025::colormap::IntoRgb::into_rgb(f(x, y));
                         frame.put_pixel(x as u32, y as u32, ::image::Rgb([r, g, b]));
                     }
                 }
 
-                // Store frame in the shared thread-local
-                __aoc_render_frames_push(frame);
+                // Hand the frame to whatever the render is streaming to
+                __aoc_render_frames_push(frame, #annotations);
             }
         }
     };
@@ -73,42 +94,19 @@ pub fn render_svg_frame_impl(input: TokenStream) -> TokenStream {
     let parser = syn::punctuated::Punctuated::<Expr, Token![,]>::parse_terminated;
     let args = parser
         .parse(input.into())
-        .expect("expected aoc::render_svg_frame!(width, height, svg_data) or aoc::render_svg_frame!(width, height, svg_data, force = true)");
+        .expect("expected aoc::render_svg_frame!(width, height, svg_data) or aoc::render_svg_frame!(width, height, svg_data, force = true, label = expr, overlay = expr)");
 
     assert!(
-        args.len() == 3 || args.len() == 4,
-        "expected 3 or 4 arguments to aoc::render_svg_frame!(width, height, svg_data[, force = true])"
+        (3..=6).contains(&args.len()),
+        "expected 3 to 6 arguments to aoc::render_svg_frame!(width, height, svg_data[, force = true][, label = expr][, overlay = expr])"
     );
 
     let width_expr = &args[0];
     let height_expr = &args[1];
     let svg_data_expr = &args[2];
 
-    // Parse optional force parameter
-    let force_check = if args.len() > 3 {
-        match &args[3] {
-            Expr::Assign(syn::ExprAssign { right, .. }) => {
-                // Check if it's a boolean literal
-                match right.as_ref() {
-                    Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Bool(lit_bool),
-                        ..
-                    }) => {
-                        let val = lit_bool.value;
-                        if val {
-                            quote! { true }
-                        } else {
-                            quote! { __aoc_should_render_frame() }
-                        }
-                    }
-                    _ => quote! { __aoc_should_render_frame() },
-                }
-            }
-            _ => quote! { __aoc_should_render_frame() },
-        }
-    } else {
-        quote! { __aoc_should_render_frame() }
-    };
+    let args = args.iter().cloned().collect::<Vec<_>>();
+    let FrameOptions { force_check, annotations } = frame_options(&args[3..]);
 
     let expanded = quote! {
         {
@@ -117,39 +115,11 @@ pub fn render_svg_frame_impl(input: TokenStream) -> TokenStream {
                 let height = #height_expr;
                 let svg_data = #svg_data_expr;
 
-                // Use ImageMagick to rasterize SVG to PNG
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .expect("Time went backwards")
-                    .as_secs();
-                let temp_dir = std::path::PathBuf::from(format!("/tmp/aoc_render/{}", timestamp));
-                std::fs::create_dir_all(&temp_dir).expect("failed to create temp dir");
-
-                let svg_file = temp_dir.join("frame.svg");
-                let png_file = temp_dir.join("frame.png");
-                
-                std::fs::write(&svg_file, &svg_data).expect("failed to write SVG frame");
-
-                // Use ImageMagick's magick command to convert SVG to PNG with dimensions
-                let mut cmd = std::process::Command::new("magick");
-                cmd.current_dir(&temp_dir)
-                    .arg("frame.svg")
-                    .arg("-resize")
-                    .arg(format!("{}x{}", width, height))
-                    .arg("frame.png");
-
-                let output = cmd.output().expect("failed to execute magick");
-
-                if !output.status.success() {
-                    log::error!("magick conversion failed: {}", String::from_utf8_lossy(&output.stderr));
-                    panic!("failed to rasterize SVG with ImageMagick");
+                // A bad frame shouldn't throw away the rest of the animation
+                match aoc2025::harness::svg::rasterize(&svg_data, width as u32, height as u32) {
+                    Ok(frame) => __aoc_render_frames_push(frame, #annotations),
+                    Err(e) => log::error!("skipping SVG frame: {}", e),
                 }
-
-                let frame = ::image::open(&png_file)
-                    .expect("failed to load rasterized PNG")
-                    .to_rgb8();
-
-                __aoc_render_frames_push(frame);
             }
         }
     };
@@ -28,14 +28,21 @@ pub fn render_image_impl(input: TokenStream) -> TokenStream {
             let mut img = ::image::RgbImage::new(width as u32, height as u32);
             for y in 0..height {
                 for x in 0..width {
-                    let (r, g, b) = f(x, y);
+                    // Either (r, g, b) or [r, g, b], so colormap colours go straight in
+                    let [r, g, b] = aoc2025::colormap::IntoRgb::into_rgb(f(x, y));
                     img.put_pixel(x as u32, y as u32, ::image::Rgb([r, g, b]));
                 }
             }
 
-            let filename = format!("aoc2025_{}_{}_render.png", crate::__aoc::DAY, stringify!(#name_expr));
-            img.save(&filename).expect("failed to save image");
-            println!("Rendered {}", filename);
+            // Under aoc::render_test! the image is checked like a frame rather than saved
+            if aoc2025::harness::frames::capturing() {
+                aoc2025::harness::frames::push(img, Default::default());
+            } else {
+                let filename = aoc2025::harness::output::path_for(crate::__aoc::YEAR, crate::__aoc::DAY, concat!(stringify!(#name_expr), "_render"), "png").expect("failed to create output directory");
+                img.save(&filename).expect("failed to save image");
+                aoc2025::harness::output::record(&filename, "png", None);
+                println!("Rendered {}", filename.display());
+            }
         }
     };
 
@@ -60,9 +67,13 @@ pub fn render_svg_impl(input: TokenStream) -> TokenStream {
         {
             let mut svg_data = #closure_expr.to_string();
             
-            let filename = format!("aoc2025_{}_{}_render.svg", crate::__aoc::DAY, stringify!(#name_expr));
-            std::fs::write(&filename, svg_data).expect("failed to save SVG image");
-            println!("Rendered {}", filename);
+            // Nothing to compare under aoc::render_test!, but nothing should be written either
+            if !aoc2025::harness::frames::capturing() {
+                let filename = aoc2025::harness::output::path_for(crate::__aoc::YEAR, crate::__aoc::DAY, concat!(stringify!(#name_expr), "_render"), "svg").expect("failed to create output directory");
+                std::fs::write(&filename, svg_data).expect("failed to save SVG image");
+                aoc2025::harness::output::record(&filename, "svg", None);
+                println!("Rendered {}", filename.display());
+            }
         }
     };
 
@@ -0,0 +1,149 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::parse_macro_input;
+use syn::{Expr, Ident, LitInt, LitStr};
+
+// Goldens are named with the shared input hash, which has to come out the same on every toolchain (unlike DefaultHasher)
+use input_hash::input_hash;
+
+enum InputSpec {
+    File(LitStr),
+    Text(LitStr),
+    // text = EXAMPLE, a &str const the day already has
+    Const(syn::Path),
+}
+
+struct RenderTestInput {
+    input_spec: InputSpec,
+    renders: Vec<Expr>,
+    picks: Vec<proc_macro2::TokenStream>,
+    compare: proc_macro2::TokenStream,
+}
+
+// first, last, 3, every = 10
+fn parse_pick(input: ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    if input.peek(LitInt) {
+        let i: LitInt = input.parse()?;
+        return Ok(quote! { aoc2025::harness::golden::Pick::Index(#i) });
+    }
+    let ident: Ident = input.parse()?;
+    match ident.to_string().as_str() {
+        "first" => Ok(quote! { aoc2025::harness::golden::Pick::First }),
+        "last" => Ok(quote! { aoc2025::harness::golden::Pick::Last }),
+        "every" => {
+            input.parse::<syn::Token![=]>()?;
+            let n: LitInt = input.parse()?;
+            if n.base10_parse::<usize>()? == 0 {
+                return Err(syn::Error::new_spanned(n, "every = 0 picks nothing"));
+            }
+            Ok(quote! { aoc2025::harness::golden::Pick::Every(#n) })
+        }
+        other => Err(syn::Error::new_spanned(ident, format!("expected first, last, a frame number, or every = N, got `{}`", other))),
+    }
+}
+
+impl Parse for RenderTestInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let input_spec = match ident.to_string().as_str() {
+            "file" => InputSpec::File(input.parse()?),
+            "text" if input.peek(LitStr) => InputSpec::Text(input.parse()?),
+            "text" => InputSpec::Const(input.parse()?),
+            other => return Err(syn::Error::new_spanned(ident, format!("expected `file` or `text`, got `{}`", other))),
+        };
+
+        input.parse::<syn::Token![,]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let renders: Vec<Expr> = content.parse_terminated(Expr::parse, syn::Token![,])?.into_iter().collect();
+
+        // Defaults: the first and last frames, pixel for pixel
+        let mut picks = vec![quote! { aoc2025::harness::golden::Pick::First }, quote! { aoc2025::harness::golden::Pick::Last }];
+        let mut compare = quote! { aoc2025::harness::golden::Compare::Pixels };
+
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() { break; }
+            let ident: Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                "frames" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    picks = content.parse_terminated(parse_pick, syn::Token![,])?.into_iter().collect();
+                }
+                "hash" => {
+                    // hash (identical hashes) or hash = N (at most N bits apart)
+                    let tolerance = match input.peek(syn::Token![=]) {
+                        true => { input.parse::<syn::Token![=]>()?; input.parse::<LitInt>()? }
+                        false => LitInt::new("0", ident.span()),
+                    };
+                    compare = quote! { aoc2025::harness::golden::Compare::Hash(#tolerance) };
+                }
+                other => return Err(syn::Error::new_spanned(ident, format!("expected `frames` or `hash`, got `{}`", other))),
+            }
+        }
+
+        Ok(RenderTestInput { input_spec, renders, picks, compare })
+    }
+}
+
+pub fn render_test_impl(input: TokenStream) -> TokenStream {
+    let RenderTestInput { input_spec, renders, picks, compare } = parse_macro_input!(input as RenderTestInput);
+
+    let (source_tag, input_binding) = match &input_spec {
+        InputSpec::File(p) => {
+            let path = p.value();
+            let last = path.rsplit('/').next().unwrap_or(&path);
+            let sanitized: String = last.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+            (format!("file_{}_{}", sanitized, input_hash(&path)), quote! { let input = std::fs::read_to_string(#p).map_err(|e| format!("failed to read {}: {}", #p, e))?; })
+        }
+        InputSpec::Text(t) => (format!("text_{}", input_hash(&t.value())), quote! { let input = #t.to_string(); }),
+        // The const's value isn't known here, so its name stands in for the hash
+        InputSpec::Const(c) => {
+            let name = c.segments.last().map_or(String::new(), |s| s.ident.to_string().to_lowercase());
+            (format!("text_{}", name), quote! { let input = #c.to_string(); })
+        }
+    };
+
+    let tests = renders.iter().map(|render_expr| {
+        let render = crate::expr_to_string(render_expr);
+        let name = format!("{}_{}", render, source_tag);
+        let run_ident = format_ident!("__aoc_render_test_{}", name);
+        let test_ident = format_ident!("test_render_{}_{}", source_tag, render);
+        let entry_ident = format_ident!("__AOC_RENDER_TEST_ENTRY_{}", name.to_uppercase());
+        let reg_ident = format_ident!("__aoc_register_render_test_{}", name);
+
+        quote! {
+            #[doc(hidden)]
+            fn #run_ident(bless: bool) -> Result<String, String> {
+                #input_binding
+                let entry = crate::__aoc::get_render(crate::__aoc::DAY, #render).ok_or_else(|| format!("render {} not found", #render))?;
+                let test = aoc2025::harness::golden::Test {
+                    dir: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(crate::__aoc::DAY),
+                    actual: aoc2025::harness::golden::actual_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), crate::__aoc::DAY),
+                    name: #name,
+                    picks: &[#(#picks),*],
+                    compare: #compare,
+                };
+                aoc2025::harness::golden::check(&test, &input, entry.func, bless)
+            }
+
+            #[test]
+            fn #test_ident() {
+                if let Err(e) = #run_ident(aoc2025::harness::golden::bless_from_env()) { panic!("render test failed for {}:\n{}", #render, e); }
+            }
+
+            #[doc(hidden)]
+            static #entry_ident: crate::__aoc::RenderTestEntry = crate::__aoc::RenderTestEntry { day: crate::__aoc::DAY, name: #name, render: #render, func: #run_ident };
+
+            #[doc(hidden)]
+            #[::ctor::ctor]
+            fn #reg_ident() { crate::__aoc::register_render_test(&#entry_ident); }
+        }
+    });
+
+    TokenStream::from(quote! { #(#tests)* })
+}
@@ -1,5 +1,7 @@
 aoc::main!(day3);
 
+use aoc2025::harness::progress::Progress;
+
 #[aoc::register]
 fn part1(input: &str) -> impl Into<String> {
     input
@@ -90,19 +92,26 @@ fn part2(input: &str) -> impl Into<String> {
         .to_string()
 }
 
-#[allow(dead_code)]
-// #[aoc::register]
-fn part2_bruteforce(input: &str) -> impl Into<String> {
-    input
+#[aoc::register(slow, tags = ["bruteforce"])]
+fn part2_bruteforce(input: &str, progress: &Progress) -> impl Into<String> {
+    let lines = input
         .lines()
         .filter(|line| !line.is_empty())
-        .map(|line| {
+        .collect::<Vec<_>>();
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(line_index, line)| {
             let digits = line.chars().map(|c| ((c as u8) - b'0')).collect::<Vec<_>>();
             let n = digits.len();
 
             let mut max_value = 0;
 
             for i1 in 0..n {
+                // Each line takes a while, so count the first digit's position as part of the way through it
+                progress.fraction((line_index as f64 + i1 as f64 / n as f64) / lines.len() as f64);
+
                 for i2 in (i1 + 1)..n {
                     for i3 in (i2 + 1)..n {
                         for i4 in (i3 + 1)..n {
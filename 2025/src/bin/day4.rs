@@ -2,7 +2,20 @@ use aoc2025::grid::Grid;
 
 aoc::main!(day4);
 
-#[aoc::register]
+const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+#[aoc::register(example = EXAMPLE, expected_example = "13", expected = "1393")]
 fn part1(input: &str) -> impl Into<String> {
     let g = Grid::read(input, |c| c == '@');
 
@@ -12,7 +25,7 @@ fn part1(input: &str) -> impl Into<String> {
         .to_string()
 }
 
-#[aoc::register]
+#[aoc::register(example = EXAMPLE, expected_example = "43", expected = "8643")]
 fn part2(input: &str) -> impl Into<String> {
     let mut g = Grid::read(input, |c| c == '@');
     let initial_count = g.iter().filter(|(_, _, v)| *v).count();
@@ -33,7 +46,7 @@ fn part2(input: &str) -> impl Into<String> {
     }
 }
 
-#[aoc::register]
+#[aoc::register(part = 2, example = EXAMPLE, expected_example = "43", expected = "8643")]
 fn part2_no_map(input: &str) -> impl Into<String> {
     let mut g = Grid::read(input, |c| c == '@');
     let mut count = 0;
@@ -93,16 +106,21 @@ fn part2_render(input: &str) {
             }
         }
 
-        aoc::render_frame!(g.width() as usize, g.height() as usize, |x, y| {
-            if removed.contains(&(x as isize, y as isize)) {
-                return (255, 0, 0);
-            }
+        aoc::render_frame!(
+            g.width() as usize,
+            g.height() as usize,
+            |x, y| {
+                if removed.contains(&(x as isize, y as isize)) {
+                    return (255, 0, 0);
+                }
 
-            match g.get(x as isize, y as isize) {
-                Some(true) => (0, 0, 0),
-                _ => (255, 255, 255),
-            }
-        });
+                match g.get(x as isize, y as isize) {
+                    Some(true) => (0, 0, 0),
+                    _ => (255, 255, 255),
+                }
+            },
+            label = format!("removed {} rolls", removed.len())
+        );
 
         if removed.is_empty() {
             break;
@@ -196,19 +214,14 @@ fn part2_render_floodfill(input: &str) {
     }
 }
 
-aoc::test!(
-    text = "\
-123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  
-", 
-    [part1] => "13",
-    [part2, part2_no_map] => "43"
+aoc::render_test!(
+    text = EXAMPLE,
+    [part2_render, part2_render_floodfill],
+    frames = [first, last, every = 4]
 );
 
-aoc::test!(
-    file = "input/2025/day4.txt",
-    [part1] => "1393",
-    [part2, part2_no_map] => "8643"
+aoc::render_test!(
+    text = EXAMPLE,
+    [part2_image],
+    hash = 2
 );
@@ -33,22 +33,24 @@ impl From<&str> for Puzzle {
     }
 }
 
-#[aoc::register]
-fn part1(input: &str) -> impl Into<String> {
-    let puzzle = Puzzle::from(input);
+// Both parts share the parsed ranges and ids
+#[aoc::generator]
+fn parse(input: &str) -> Puzzle {
+    Puzzle::from(input)
+}
 
+#[aoc::register]
+fn part1(puzzle: &Puzzle) -> impl Into<String> {
     puzzle
         .ids
-        .into_iter()
-        .filter(|id| puzzle.ranges.iter().any(|(a, b)| id >= a && id <= b))
+        .iter()
+        .filter(|&id| puzzle.ranges.iter().any(|(a, b)| id >= a && id <= b))
         .count()
         .to_string()
 }
 
 #[aoc::register]
-fn part2(input: &str) -> impl Into<String> {
-    let puzzle = Puzzle::from(input);
-
+fn part2(puzzle: &Puzzle) -> impl Into<String> {
     let mut ranges = puzzle.ranges.clone();
 
     // Merge overlapping and included ranges until nothing more can be merged
@@ -1,8 +1,10 @@
+use grids::{Bounds, Dense, GridLike, Pos};
+
+// A thin layer over grids::Dense with the isize getters (and Copy values) the solutions here expect
+// It's GridLike too, for flood_fill, to_string, render and converting to the other backends
 #[derive(Clone, PartialEq, Eq)]
 pub struct Grid<T> {
-    width: isize,
-    height: isize,
-    data: Vec<T>,
+    cells: Dense<T>,
 }
 
 impl<T> Grid<T>
@@ -10,96 +12,84 @@ where
     T: Copy,
 {
     pub fn read(s: &str, f: impl Fn(char) -> T) -> Grid<T> {
-        let mut width = 0;
-        let mut height = 0;
-        let mut data = vec![];
-
-        for line in s.lines() {
-            if line.is_empty() {
-                continue;
-            }
-
-            height += 1;
-
-            let mut line_width = 0;
-            for c in line.chars() {
-                data.push(f(c));
-                line_width += 1;
-            }
-
-            if height == 1 {
-                width = line_width
-            } else {
-                assert_eq!(width, line_width, "Line {line} is the wrong width");
-            }
-        }
-
         Grid {
-            width,
-            height,
-            data,
+            cells: Dense::read(s, f),
         }
     }
 
     pub fn width(&self) -> isize {
-        self.width
+        self.cells.width() as isize
     }
 
     pub fn height(&self) -> isize {
-        self.height
+        self.cells.height() as isize
     }
 
     pub fn get(&self, x: isize, y: isize) -> Option<T> {
-        if self.in_bounds(x, y) {
-            Some(self.data[(x + y * self.width) as usize])
-        } else {
-            None
-        }
+        self.cells.get((x, y)).copied()
     }
 
     pub fn set(&mut self, x: isize, y: isize, v: T) {
-        if self.in_bounds(x, y) {
-            self.data[(x + y * self.width) as usize] = v;
-        } else {
+        if !self.cells.set((x, y), v) {
             panic!("Set out of bounds");
         }
     }
 
     pub fn in_bounds(&self, x: isize, y: isize) -> bool {
-        x >= 0 && x < self.width && y >= 0 && y < self.height
+        self.cells.in_bounds((x, y))
     }
 
+    // Row by row
     pub fn iter(&self) -> impl Iterator<Item = (isize, isize, T)> {
-        (0..self.width)
-            .flat_map(move |x| (0..self.height).map(move |y| (x, y, self.get(x, y).unwrap())))
+        self.cells.iter().map(|((x, y), v)| (x, y, *v))
     }
 
     pub fn map(&self, f: impl Fn(isize, isize, T) -> T) -> Grid<T> {
         Grid {
-            width: self.width,
-            height: self.height,
-            data: self.iter().map(|(x, y, v)| f(x, y, v)).collect::<Vec<_>>(),
+            cells: self.cells.map(|(x, y), v| f(x, y, *v)),
         }
     }
 
+    // All 8 directions, None for the ones off the edge
     pub fn neighbors(&self, x: isize, y: isize) -> impl Iterator<Item = Option<T>> {
-        [
-            (-1_isize, -1_isize),
-            (0, -1),
-            (1, -1),
-            (-1, 0),
-            (1, 0),
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-        ]
-        .into_iter()
-        .map(move |(xd, yd)| self.get(x + xd, y + yd))
+        grids::ALL_DIRECTIONS
+            .into_iter()
+            .map(move |(xd, yd)| self.get(x + xd, y + yd))
     }
 
     pub fn ortho_neighbors(&self, x: isize, y: isize) -> impl Iterator<Item = Option<T>> {
-        [(0_isize, -1_isize), (-1, 0), (1, 0), (0, 1)]
+        grids::ORTHOGONAL
             .into_iter()
             .map(move |(xd, yd)| self.get(x + xd, y + yd))
     }
+
+    pub fn as_dense(&self) -> &Dense<T> {
+        &self.cells
+    }
+}
+
+impl<T> From<Dense<T>> for Grid<T> {
+    fn from(cells: Dense<T>) -> Self {
+        Grid { cells }
+    }
+}
+
+impl<T> GridLike for Grid<T> {
+    type Item = T;
+
+    fn bounds(&self) -> Bounds {
+        self.cells.bounds()
+    }
+
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        self.cells.set(p, value)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.cells()
+    }
 }
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use input_hash::input_hash;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchFormat {
+    Human,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for BenchFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(BenchFormat::Human),
+            "json" => Ok(BenchFormat::Json),
+            "csv" => Ok(BenchFormat::Csv),
+            _ => Err(format!(
+                "unknown format {s:?}, expected human, json, or csv"
+            )),
+        }
+    }
+}
+
+// Timings for a single solution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub name: String,
+    pub avg_ns: u64,
+    pub stddev_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+    pub median_ns: u64,
+    // In the order they were run (not sorted)
+    pub times_ns: Vec<u64>,
+}
+
+impl BenchResult {
+    pub fn from_times(name: &str, times: &[Duration]) -> BenchResult {
+        assert!(!times.is_empty(), "need at least one timing");
+
+        let times_ns = times
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        let mut sorted = times_ns.clone();
+        sorted.sort();
+
+        let n = sorted.len();
+        let avg = sorted.iter().map(|&t| t as f64).sum::<f64>() / n as f64;
+        let var = sorted
+            .iter()
+            .map(|&t| {
+                let diff = t as f64 - avg;
+                diff * diff
+            })
+            .sum::<f64>()
+            / n as f64;
+
+        BenchResult {
+            name: name.to_string(),
+            avg_ns: avg as u64,
+            stddev_ns: var.sqrt() as u64,
+            min_ns: sorted[0],
+            max_ns: sorted[n - 1],
+            median_ns: sorted[n / 2],
+            times_ns,
+        }
+    }
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = Duration::from_nanos;
+        write!(
+            f,
+            "{}: {:?} ± {:?} [min: {:?}, max: {:?}, median: {:?}]",
+            self.name,
+            d(self.avg_ns),
+            d(self.stddev_ns),
+            d(self.min_ns),
+            d(self.max_ns),
+            d(self.median_ns)
+        )
+    }
+}
+
+// Everything from one `bench` invocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub day: String,
+    pub input_hash: String,
+    pub git_rev: String,
+    pub timestamp: u64,
+    pub warmup: usize,
+    pub iters: usize,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    pub fn new(day: &str, input: &str, warmup: usize, iters: usize) -> BenchReport {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        BenchReport {
+            day: day.to_string(),
+            input_hash: input_hash(input),
+            git_rev: git_rev(),
+            timestamp,
+            warmup,
+            iters,
+            results: vec![],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize bench report")
+    }
+
+    pub fn from_json(s: &str) -> Result<BenchReport, String> {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    }
+
+    // One row per iteration so the raw data can be loaded into anything
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("day,name,iteration,ns,input_hash,git_rev\n");
+        for result in &self.results {
+            for (i, ns) in result.times_ns.iter().enumerate() {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    self.day, result.name, i, ns, self.input_hash, self.git_rev
+                ));
+            }
+        }
+        out
+    }
+
+    pub fn render(&self, format: BenchFormat) -> String {
+        match format {
+            BenchFormat::Human => self.results.iter().map(|r| format!("{r}\n")).collect(),
+            BenchFormat::Json => self.to_json(),
+            BenchFormat::Csv => self.to_csv(),
+        }
+    }
+}
+
+// Time a solution (or a generator): `warmup` untimed runs then `iters` timed runs
+// Whatever `func` returns is dropped outside of the timing
+pub fn run<T>(name: &str, mut func: impl FnMut() -> T, warmup: usize, iters: usize) -> BenchResult {
+    for _ in 0..warmup {
+        let _ = func();
+    }
+
+    let mut times = Vec::with_capacity(iters);
+    for _ in 0..iters {
+        let start = Instant::now();
+        let out = func();
+        times.push(start.elapsed());
+        drop(out);
+    }
+
+    BenchResult::from_times(name, &times)
+}
+
+pub fn git_rev() -> String {
+    let rev = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+
+    let dirty = std::process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .is_some_and(|o| !o.stdout.is_empty());
+
+    match rev {
+        Some(rev) if dirty => format!("{rev}-dirty"),
+        Some(rev) => rev,
+        None => "unknown".to_string(),
+    }
+}
+
+// How one solution's median compares to the baseline
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline_ns: Option<u64>,
+    pub current_ns: u64,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    // Relative change in median, positive is slower
+    pub fn change(&self) -> Option<f64> {
+        self.baseline_ns
+            .filter(|&b| b > 0)
+            .map(|b| self.current_ns as f64 / b as f64 - 1.0)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let current = Duration::from_nanos(self.current_ns);
+        match (self.baseline_ns, self.change()) {
+            (Some(baseline), Some(change)) => write!(
+                f,
+                "{}: {:?} -> {:?} ({:+.1}%){}",
+                self.name,
+                Duration::from_nanos(baseline),
+                current,
+                change * 100.0,
+                if self.regressed { " REGRESSED" } else { "" }
+            ),
+            _ => write!(f, "{}: {:?} (no baseline)", self.name, current),
+        }
+    }
+}
+
+// Flag any solution whose median is more than `threshold` (0.1 = 10%) slower than the baseline
+pub fn compare(current: &BenchReport, baseline: &BenchReport, threshold: f64) -> Vec<Comparison> {
+    current
+        .results
+        .iter()
+        .map(|r| {
+            let baseline_ns = baseline
+                .results
+                .iter()
+                .find(|b| b.name == r.name)
+                .map(|b| b.median_ns);
+
+            let mut comparison = Comparison {
+                name: r.name.clone(),
+                baseline_ns,
+                current_ns: r.median_ns,
+                regressed: false,
+            };
+            comparison.regressed = comparison.change().is_some_and(|c| c > threshold);
+            comparison
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn report(results: Vec<BenchResult>) -> BenchReport {
+        BenchReport {
+            day: "day1".to_string(),
+            input_hash: input_hash("test"),
+            git_rev: "abc1234".to_string(),
+            timestamp: 0,
+            warmup: 0,
+            iters: 3,
+            results,
+        }
+    }
+
+    fn result(name: &str, times: &[u64]) -> BenchResult {
+        let times = times
+            .iter()
+            .map(|&t| Duration::from_nanos(t))
+            .collect::<Vec<_>>();
+        BenchResult::from_times(name, &times)
+    }
+
+    #[test]
+    fn test_stats() {
+        let r = result("part1", &[30, 10, 20]);
+        assert_eq!(r.min_ns, 10);
+        assert_eq!(r.max_ns, 30);
+        assert_eq!(r.median_ns, 20);
+        assert_eq!(r.avg_ns, 20);
+        assert_eq!(r.times_ns, vec![30, 10, 20]);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut calls = 0;
+        let r = run("part1", || calls += 1, 2, 5);
+        assert_eq!(calls, 7);
+        assert_eq!(r.times_ns.len(), 5);
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let original = report(vec![result("part1", &[1, 2, 3])]);
+        let parsed = BenchReport::from_json(&original.to_json()).unwrap();
+        assert_eq!(parsed.input_hash, original.input_hash);
+        assert_eq!(parsed.results[0].times_ns, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = report(vec![result("part1", &[5, 7])]).to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[2],
+            format!("day1,part1,1,7,{},abc1234", input_hash("test"))
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = report(vec![result("part1", &[100]), result("part2", &[100])]);
+        let current = report(vec![
+            result("part1", &[105]),
+            result("part2", &[150]),
+            result("part2_new", &[1]),
+        ]);
+
+        let comparisons = compare(&current, &baseline, 0.1);
+        assert!(!comparisons[0].regressed);
+        assert!(comparisons[1].regressed);
+        assert!(!comparisons[2].regressed);
+        assert_eq!(comparisons[2].baseline_ns, None);
+    }
+}
@@ -0,0 +1,693 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use image::RgbImage;
+use overlay::Overlay;
+use scrubber::Scrubber;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Apng,
+    Webp,
+    Mp4,
+    // A page with every frame and a slider to scrub through them
+    Html,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Gif => "gif",
+            Format::Apng => "png",
+            Format::Webp => "webp",
+            Format::Mp4 => "mp4",
+            Format::Html => "html",
+        }
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Format::Gif),
+            "apng" => Ok(Format::Apng),
+            "webp" => Ok(Format::Webp),
+            "mp4" => Ok(Format::Mp4),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown format {s:?}, expected gif, apng, webp, mp4, or html"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Gif => "gif",
+            Format::Apng => "apng",
+            Format::Webp => "webp",
+            Format::Mp4 => "mp4",
+            Format::Html => "html",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// `render --format` wins over whatever the #[aoc::register_render] attribute asked for
+static FORMAT_OVERRIDE: OnceLock<Format> = OnceLock::new();
+
+pub fn set_format_override(format: Format) {
+    let _ = FORMAT_OVERRIDE.set(format);
+}
+
+pub fn format_override() -> Option<Format> {
+    FORMAT_OVERRIDE.get().copied()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub format: Format,
+    pub fps: u32,
+    // Nearest neighbour, so pixels stay crisp
+    pub scale: u32,
+    // Reduce every frame to one shared palette of this many colors (GIF always uses at most 256)
+    pub colors: Option<usize>,
+}
+
+// Anything an animation can be encoded from
+// Building a palette takes an extra pass, so the frames have to be readable more than once
+pub trait Frames {
+    fn len(&self) -> usize;
+    fn dimensions(&self) -> (u32, u32);
+    // Every frame in order, stopping at the first error
+    fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()>;
+
+    // The caption given to render_frame!, if any
+    fn label(&self, _index: usize) -> Option<&str> {
+        None
+    }
+
+    // Text and shapes from render_frame!, drawn after scaling
+    fn overlay(&self, _index: usize) -> Option<&Overlay> {
+        None
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Frames for [RgbImage] {
+    fn len(&self) -> usize {
+        <[RgbImage]>::len(self)
+    }
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.first().map_or((0, 0), |f| f.dimensions())
+    }
+
+    fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()> {
+        self.iter().try_for_each(f)
+    }
+}
+
+impl Frames for Vec<RgbImage> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.as_slice().dimensions()
+    }
+
+    fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()> {
+        self.as_slice().try_for_each(f)
+    }
+}
+
+// The frames as they're written: scaled up, and reduced to the palette if there is one
+struct Prepared<'a, F: ?Sized> {
+    frames: &'a F,
+    scale: u32,
+    palette: Option<&'a Palette>,
+}
+
+impl<F: Frames + ?Sized> Frames for Prepared<'_, F> {
+    fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    fn dimensions(&self) -> (u32, u32) {
+        let (width, height) = self.frames.dimensions();
+        let scale = self.scale.max(1);
+        (width * scale, height * scale)
+    }
+
+    fn label(&self, index: usize) -> Option<&str> {
+        self.frames.label(index)
+    }
+
+    fn overlay(&self, index: usize) -> Option<&Overlay> {
+        self.frames.overlay(index)
+    }
+
+    fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()> {
+        let mut index = 0;
+        self.frames.try_for_each(&mut |frame| {
+            let mut frame = scale(frame, self.scale);
+            if let Some(overlay) = self.frames.overlay(index) {
+                let (width, height) = frame.dimensions();
+                overlay.draw(width, height, self.scale, &mut |x, y, color| {
+                    frame.put_pixel(x, y, image::Rgb(color))
+                });
+            }
+            if let Some(palette) = self.palette {
+                palette.apply(&mut frame);
+            }
+            index += 1;
+            f(&frame)
+        })
+    }
+}
+
+// Encode the frames to `path` (which should already have the format's extension)
+// Frames are read one at a time, so they can come from somewhere bigger than memory (see frames::Spool)
+// Returns the format actually used: mp4 falls back to gif if ffmpeg isn't installed
+pub fn encode(
+    frames: &(impl Frames + ?Sized),
+    options: &Options,
+    path: &Path,
+) -> Result<Format, String> {
+    if frames.is_empty() {
+        return Err("no frames to encode".to_string());
+    }
+    let failed = |e: io::Error| format!("failed to write {}: {e}", path.display());
+
+    let colors = match options.format {
+        Format::Gif => Some(options.colors.unwrap_or(256).min(256)),
+        _ => options.colors,
+    };
+    // Overlays add colors of their own, so the palette has to come from the frames as they'll be written
+    let overlaid = (0..frames.len()).any(|i| frames.overlay(i).is_some());
+    let palette = match overlaid {
+        true => colors.map(|colors| {
+            let prepared = Prepared {
+                frames,
+                scale: options.scale,
+                palette: None,
+            };
+            Palette::new(&prepared, colors)
+        }),
+        false => colors.map(|colors| Palette::new(frames, colors)),
+    }
+    .transpose()
+    .map_err(failed)?;
+
+    // GIF stores palette indices rather than colors, so it uses the palette itself
+    // HTML scales in the browser, which keeps the page small, unless there's an overlay to draw at full size
+    let prepared = Prepared {
+        frames,
+        scale: match options.format {
+            Format::Html if !overlaid => 1,
+            _ => options.scale,
+        },
+        palette: palette.as_ref().filter(|_| options.format != Format::Gif),
+    };
+
+    let result = match options.format {
+        Format::Gif => encode_gif(
+            &prepared,
+            palette.as_ref().expect("gif always has a palette"),
+            options.fps,
+            path,
+        ),
+        Format::Apng => encode_apng(&prepared, options.fps, path),
+        Format::Webp => encode_webp(&prepared, options.fps, path),
+        Format::Mp4 => match encode_mp4(&prepared, options.fps, path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let gif_path = path.with_extension("gif");
+                log::warn!("ffmpeg not found, writing {} instead", gif_path.display());
+                let options = Options {
+                    format: Format::Gif,
+                    ..*options
+                };
+                return encode(frames, &options, &gif_path);
+            }
+            other => other,
+        },
+        Format::Html => encode_html(
+            &prepared,
+            options.fps,
+            if overlaid { 1 } else { options.scale },
+            path,
+        ),
+    };
+
+    result.map(|_| options.format).map_err(failed)
+}
+
+pub fn scale(frame: &RgbImage, scale: u32) -> RgbImage {
+    if scale <= 1 {
+        return frame.clone();
+    }
+    image::imageops::resize(
+        frame,
+        frame.width() * scale,
+        frame.height() * scale,
+        image::imageops::FilterType::Nearest,
+    )
+}
+
+// One palette for the whole animation, so colors don't flicker between frames
+// Exact if the frames have few enough colors (most renders), otherwise NeuQuant over a sample of the pixels
+pub struct Palette {
+    pub colors: Vec<[u8; 3]>,
+    exact: HashMap<[u8; 3], u8>,
+    quantizer: Option<color_quant::NeuQuant>,
+}
+
+impl Palette {
+    pub fn new(frames: &(impl Frames + ?Sized), colors: usize) -> io::Result<Palette> {
+        let colors = colors.clamp(2, 256);
+
+        let mut exact = HashMap::new();
+        frames.try_for_each(&mut |frame| {
+            for pixel in frame.pixels() {
+                if exact.len() > colors {
+                    break;
+                }
+                let len = exact.len();
+                exact.entry(pixel.0).or_insert(len);
+            }
+            Ok(())
+        })?;
+
+        if exact.len() <= colors {
+            let mut palette = vec![[0; 3]; exact.len()];
+            for (color, &i) in &exact {
+                palette[i] = *color;
+            }
+            return Ok(Palette {
+                colors: palette,
+                exact: exact.into_iter().map(|(c, i)| (c, i as u8)).collect(),
+                quantizer: None,
+            });
+        }
+
+        // Big animations have a lot of pixels, NeuQuant doesn't need all of them
+        let (width, height) = frames.dimensions();
+        let total = frames.len() * width as usize * height as usize;
+        let step = (total / 500_000).max(1);
+        let mut sample = vec![];
+        let mut index = 0;
+        frames.try_for_each(&mut |frame| {
+            for pixel in frame.pixels() {
+                if index % step == 0 {
+                    sample.extend_from_slice(&[pixel.0[0], pixel.0[1], pixel.0[2], 255]);
+                }
+                index += 1;
+            }
+            Ok(())
+        })?;
+
+        let quantizer = color_quant::NeuQuant::new(10, colors, &sample);
+        let palette = quantizer
+            .color_map_rgb()
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect();
+        Ok(Palette {
+            colors: palette,
+            exact: HashMap::new(),
+            quantizer: Some(quantizer),
+        })
+    }
+
+    pub fn index_of(&self, color: [u8; 3]) -> u8 {
+        match &self.quantizer {
+            Some(q) => q.index_of(&[color[0], color[1], color[2], 255]) as u8,
+            None => self.exact[&color],
+        }
+    }
+
+    pub fn apply(&self, frame: &mut RgbImage) {
+        if self.quantizer.is_none() {
+            return;
+        }
+        for pixel in frame.pixels_mut() {
+            pixel.0 = self.colors[self.index_of(pixel.0) as usize];
+        }
+    }
+}
+
+fn encode_gif(frames: &dyn Frames, palette: &Palette, fps: u32, path: &Path) -> io::Result<()> {
+    let (width, height) = frames.dimensions();
+    let mut flat = palette.colors.iter().flatten().copied().collect::<Vec<_>>();
+    // GIF palettes are powers of two, at least 2 colors
+    let size = flat.len().div_ceil(3).next_power_of_two().max(2);
+    flat.resize(size * 3, 0);
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder =
+        gif::Encoder::new(file, width as u16, height as u16, &flat).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    // GIF delays are in hundredths of a second
+    let delay = (100 / fps.max(1)).max(1) as u16;
+    frames.try_for_each(&mut |frame| {
+        let indices = frame
+            .pixels()
+            .map(|p| palette.index_of(p.0))
+            .collect::<Vec<_>>();
+        let mut gif_frame =
+            gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None);
+        gif_frame.delay = delay;
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)
+    })
+}
+
+fn encode_apng(frames: &dyn Frames, fps: u32, path: &Path) -> io::Result<()> {
+    let (width, height) = frames.dimensions();
+    let file = BufWriter::new(File::create(path)?);
+
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(io::Error::other)?;
+    encoder
+        .set_frame_delay(1, fps.max(1) as u16)
+        .map_err(io::Error::other)?;
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    frames.try_for_each(&mut |frame| {
+        writer
+            .write_image_data(frame.as_raw())
+            .map_err(io::Error::other)
+    })?;
+    writer.finish().map_err(io::Error::other)
+}
+
+// image-webp only writes single images, so encode each frame losslessly and wrap them in the animation chunks
+// https://developers.google.com/speed/webp/docs/riff_container#animation
+fn encode_webp(frames: &dyn Frames, fps: u32, path: &Path) -> io::Result<()> {
+    let (width, height) = frames.dimensions();
+    let duration = 1000 / fps.max(1);
+
+    let u24 = |v: u32| [v as u8, (v >> 8) as u8, (v >> 16) as u8];
+    let chunk = |out: &mut BufWriter<File>, fourcc: &[u8; 4], data: &[u8]| -> io::Result<u32> {
+        out.write_all(fourcc)?;
+        out.write_all(&(data.len() as u32).to_le_bytes())?;
+        out.write_all(data)?;
+        if data.len() % 2 == 1 {
+            out.write_all(&[0])?;
+        }
+        Ok(8 + data.len().next_multiple_of(2) as u32)
+    };
+
+    // The RIFF size goes in once everything else is written
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(b"RIFF\0\0\0\0WEBP")?;
+    let mut size = 4;
+
+    let mut vp8x = vec![0x02, 0, 0, 0]; // animation flag
+    vp8x.extend_from_slice(&u24(width - 1));
+    vp8x.extend_from_slice(&u24(height - 1));
+    size += chunk(&mut file, b"VP8X", &vp8x)?;
+
+    // Background color (BGRA), then loop forever
+    size += chunk(&mut file, b"ANIM", &[0, 0, 0, 0, 0, 0])?;
+
+    frames.try_for_each(&mut |frame| {
+        let mut single = vec![];
+        image_webp::WebPEncoder::new(&mut single)
+            .encode(frame.as_raw(), width, height, image_webp::ColorType::Rgb8)
+            .map_err(io::Error::other)?;
+        // Skip the RIFF header, what's left is the frame's VP8L chunk
+        let image_chunk = &single[12..];
+
+        let mut anmf = vec![];
+        anmf.extend_from_slice(&u24(0)); // x / 2
+        anmf.extend_from_slice(&u24(0)); // y / 2
+        anmf.extend_from_slice(&u24(width - 1));
+        anmf.extend_from_slice(&u24(height - 1));
+        anmf.extend_from_slice(&u24(duration));
+        anmf.push(0x02); // don't blend, every frame covers the whole canvas
+        anmf.extend_from_slice(image_chunk);
+        size += chunk(&mut file, b"ANMF", &anmf)?;
+        Ok(())
+    })?;
+
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&size.to_le_bytes())?;
+    file.flush()
+}
+
+// A single standalone page to step through the frames, each with its label underneath (see the scrubber crate)
+fn encode_html(frames: &dyn Frames, fps: u32, scale: u32, path: &Path) -> io::Result<()> {
+    let (width, height) = frames.dimensions();
+    let mut scrubber = Scrubber::new(width, height).fps(fps).scale(scale);
+    frames.try_for_each(&mut |frame| {
+        let label = frames.label(scrubber.len());
+        scrubber.push(frame.as_raw(), label)
+    })?;
+    scrubber.save(path)
+}
+
+// Frames are written as PNGs to a temporary directory and handed to ffmpeg
+fn encode_mp4(frames: &dyn Frames, fps: u32, path: &Path) -> io::Result<()> {
+    let temp_dir = super::output::temp_path("mp4")?;
+    std::fs::create_dir_all(&temp_dir)?;
+
+    let result = (|| {
+        let mut idx = 0;
+        frames.try_for_each(&mut |frame| {
+            frame
+                .save(temp_dir.join(format!("frame_{idx:04}.png")))
+                .map_err(io::Error::other)?;
+            idx += 1;
+            Ok(())
+        })?;
+
+        log::info!("Rendering video with ffmpeg");
+        let output = std::process::Command::new("ffmpeg")
+            .arg("-framerate")
+            .arg(fps.to_string())
+            .arg("-i")
+            .arg(temp_dir.join("frame_%04d.png"))
+            // x264 needs even dimensions
+            .arg("-vf")
+            .arg("pad=ceil(iw/2)*2:ceil(ih/2)*2")
+            .arg("-c:v")
+            .arg("libx264")
+            .arg("-pix_fmt")
+            .arg("yuv420p")
+            .arg("-y")
+            .arg("-movflags")
+            .arg("+faststart")
+            .arg(path)
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "ffmpeg failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        }
+    })();
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frames() -> Vec<RgbImage> {
+        (0..3)
+            .map(|i| {
+                RgbImage::from_fn(4, 3, |x, _| {
+                    if x == i {
+                        image::Rgb([255, 0, 0])
+                    } else {
+                        image::Rgb([0, 0, 40])
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("aoc2025_encode_{}_{name}", std::process::id()))
+    }
+
+    fn options(format: Format) -> Options {
+        Options {
+            format,
+            fps: 10,
+            scale: 2,
+            colors: None,
+        }
+    }
+
+    #[test]
+    fn test_scale() {
+        let scaled = scale(&frames()[0], 3);
+        assert_eq!(scaled.dimensions(), (12, 9));
+        assert_eq!(scaled.get_pixel(2, 0).0, [255, 0, 0]);
+        assert_eq!(scaled.get_pixel(3, 0).0, [0, 0, 40]);
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::new(&frames(), 256).unwrap();
+        assert_eq!(palette.colors.len(), 2);
+        let red = palette.index_of([255, 0, 0]);
+        assert_eq!(palette.colors[red as usize], [255, 0, 0]);
+
+        // More colors than fit, every pixel has to end up in the palette
+        let gradient = vec![RgbImage::from_fn(64, 64, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 4) as u8, 0])
+        })];
+        let palette = Palette::new(&gradient, 16).unwrap();
+        assert_eq!(palette.colors.len(), 16);
+        let mut frame = gradient[0].clone();
+        palette.apply(&mut frame);
+        assert!(frame.pixels().all(|p| palette.colors.contains(&p.0)));
+    }
+
+    #[test]
+    fn test_gif() {
+        let path = temp_path("test.gif");
+        assert_eq!(
+            encode(&frames(), &options(Format::Gif), &path),
+            Ok(Format::Gif)
+        );
+
+        let file = File::open(&path).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(file).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (8, 6));
+        let mut count = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_apng() {
+        let path = temp_path("test.png");
+        assert_eq!(
+            encode(&frames(), &options(Format::Apng), &path),
+            Ok(Format::Apng)
+        );
+
+        let decoder = png::Decoder::new(std::io::BufReader::new(File::open(&path).unwrap()));
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (8, 6));
+        assert_eq!(info.animation_control.unwrap().num_frames, 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_webp() {
+        let path = temp_path("test.webp");
+        assert_eq!(
+            encode(&frames(), &options(Format::Webp), &path),
+            Ok(Format::Webp)
+        );
+
+        let file = std::io::BufReader::new(File::open(&path).unwrap());
+        let mut decoder = image_webp::WebPDecoder::new(file).unwrap();
+        assert!(decoder.is_animated());
+        assert_eq!(decoder.dimensions(), (8, 6));
+        assert_eq!(decoder.num_frames(), 3);
+
+        let mut buf = vec![0; decoder.output_buffer_size().unwrap()];
+        decoder.read_frame(&mut buf).unwrap();
+        // The first column (scaled to two pixels) is red in the first frame
+        assert_eq!(&buf[..3], &[255, 0, 0]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_html() {
+        let path = temp_path("test.html");
+        assert_eq!(
+            encode(&frames(), &options(Format::Html), &path),
+            Ok(Format::Html)
+        );
+
+        let page = std::fs::read_to_string(&path).unwrap();
+        assert!(page.contains(r#"<canvas id="canvas" width="4" height="3">"#));
+        assert_eq!(page.matches(r#""src":"data:image/png;base64,"#).count(), 3);
+        assert_eq!(page.matches(r#""key":true"#).count(), 1);
+        assert!(!page.contains("{{"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // The same overlay on every frame
+    struct Overlaid(Vec<RgbImage>, Overlay);
+
+    impl Frames for Overlaid {
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn dimensions(&self) -> (u32, u32) {
+            self.0.dimensions()
+        }
+
+        fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()> {
+            self.0.try_for_each(f)
+        }
+
+        fn overlay(&self, _index: usize) -> Option<&Overlay> {
+            Some(&self.1)
+        }
+    }
+
+    #[test]
+    fn test_overlay() {
+        let green = [0, 255, 0];
+        let frames = Overlaid(frames(), Overlay::new().line((0, 2), (3, 2), green));
+
+        // Drawn after scaling, so the line is one pixel thick through the middle of the bottom row of cells
+        let prepared = Prepared {
+            frames: &frames,
+            scale: 2,
+            palette: None,
+        };
+        prepared
+            .try_for_each(&mut |frame| {
+                assert_eq!(frame.get_pixel(3, 5).0, green);
+                assert_ne!(frame.get_pixel(3, 4).0, green);
+                Ok(())
+            })
+            .unwrap();
+
+        // Green isn't in the frames themselves, but still has to make it into the palette
+        let path = temp_path("overlay.gif");
+        assert_eq!(
+            encode(&frames, &options(Format::Gif), &path),
+            Ok(Format::Gif)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
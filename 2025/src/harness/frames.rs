@@ -0,0 +1,436 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use image::RgbImage;
+use overlay::Overlay;
+
+use super::encode::{self, Frames, Options};
+use super::output;
+
+// What render_frame!(.., label = .., overlay = ..) attached to a frame, besides its pixels
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Annotations {
+    // A caption shown alongside the frame (render --tty, --format html)
+    pub label: Option<String>,
+    // Drawn on the frame once it's scaled up
+    pub overlay: Option<Overlay>,
+}
+
+// Where a render's frames go as they're produced
+pub trait Sink: Send {
+    fn push(&mut self, frame: RgbImage, annotations: Annotations) -> Result<(), String>;
+    // False once the sink doesn't want any more frames (the viewer was quit, say)
+    fn accepting(&self) -> bool {
+        true
+    }
+    // Called once after the last frame, even if the render panicked partway through
+    fn finish(self: Box<Self>) -> Result<(), String>;
+}
+
+// Frames appended to a temporary file as raw RGB, so only the one being written or read is in memory
+pub struct Spool {
+    path: PathBuf,
+    file: File,
+    dimensions: (u32, u32),
+    len: usize,
+    // Small enough to keep in memory
+    annotations: Vec<Annotations>,
+}
+
+impl Spool {
+    pub fn new(dimensions: (u32, u32)) -> io::Result<Spool> {
+        let path = output::temp_path("frames")?;
+        Ok(Spool {
+            file: File::create(&path)?,
+            path,
+            dimensions,
+            len: 0,
+            annotations: vec![],
+        })
+    }
+
+    pub fn push(&mut self, frame: &RgbImage, annotations: Annotations) -> Result<(), String> {
+        if frame.dimensions() != self.dimensions {
+            let (width, height) = self.dimensions;
+            return Err(format!(
+                "frame {} is {}x{}, but the animation is {width}x{height}",
+                self.len,
+                frame.width(),
+                frame.height()
+            ));
+        }
+        self.file
+            .write_all(frame.as_raw())
+            .map_err(|e| format!("failed to spool frame: {e}"))?;
+        self.len += 1;
+        self.annotations.push(annotations);
+        Ok(())
+    }
+}
+
+impl Frames for Spool {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    fn label(&self, index: usize) -> Option<&str> {
+        self.annotations.get(index)?.label.as_deref()
+    }
+
+    fn overlay(&self, index: usize) -> Option<&Overlay> {
+        self.annotations.get(index)?.overlay.as_ref()
+    }
+
+    fn try_for_each(&self, f: &mut dyn FnMut(&RgbImage) -> io::Result<()>) -> io::Result<()> {
+        let (width, height) = self.dimensions;
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut frame = RgbImage::new(width, height);
+        for _ in 0..self.len {
+            reader.read_exact(&mut frame)?;
+            f(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+// The default sink: spool the frames and encode them to a file once the render is done
+pub struct EncodeSink {
+    options: Options,
+    path: PathBuf,
+    spool: Option<Spool>,
+}
+
+impl EncodeSink {
+    pub fn new(options: Options, path: PathBuf) -> EncodeSink {
+        EncodeSink {
+            options,
+            path,
+            spool: None,
+        }
+    }
+}
+
+impl Sink for EncodeSink {
+    fn push(&mut self, frame: RgbImage, annotations: Annotations) -> Result<(), String> {
+        if self.spool.is_none() {
+            let spool = Spool::new(frame.dimensions())
+                .map_err(|e| format!("failed to create frame spool: {e}"))?;
+            self.spool = Some(spool);
+        }
+        self.spool.as_mut().unwrap().push(&frame, annotations)
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), String> {
+        // Nothing was drawn, nothing to write
+        let Some(spool) = &self.spool else {
+            return Ok(());
+        };
+
+        log::info!("Encoding {} frames as {}", spool.len(), self.options.format);
+        let format = encode::encode(spool, &self.options, &self.path)?;
+        let path = self.path.with_extension(format.extension());
+        output::record(&path, &format.to_string(), Some(spool.len()));
+        println!("Rendered {}", path.display());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    // Stop recording after this many frames (the render itself carries on)
+    pub max_frames: Option<usize>,
+    // Drop frames identical to the one before
+    pub dedup: bool,
+}
+
+// `render --max-frames` wins over the attribute, like `--format`
+static MAX_FRAMES_OVERRIDE: OnceLock<usize> = OnceLock::new();
+
+pub fn set_max_frames_override(max_frames: usize) {
+    let _ = MAX_FRAMES_OVERRIDE.set(max_frames);
+}
+
+pub fn max_frames_override() -> Option<usize> {
+    MAX_FRAMES_OVERRIDE.get().copied()
+}
+
+struct Recorder {
+    sink: Box<dyn Sink>,
+    limits: Limits,
+    recorded: usize,
+    duplicates: usize,
+    last: Option<(RgbImage, Annotations)>,
+}
+
+impl Recorder {
+    fn at_max(&self) -> bool {
+        self.limits
+            .max_frames
+            .is_some_and(|max| self.recorded >= max)
+    }
+
+    fn full(&self) -> bool {
+        self.at_max() || !self.sink.accepting()
+    }
+
+    fn push(&mut self, frame: RgbImage, annotations: Annotations) {
+        if self.full() {
+            return;
+        }
+        if self.limits.dedup
+            && self
+                .last
+                .as_ref()
+                .is_some_and(|(f, a)| *f == frame && *a == annotations)
+        {
+            self.duplicates += 1;
+            return;
+        }
+
+        log::info!("Rendering frame {}", self.recorded);
+        let last = self
+            .limits
+            .dedup
+            .then(|| (frame.clone(), annotations.clone()));
+        match self.sink.push(frame, annotations) {
+            Ok(()) => {
+                self.recorded += 1;
+                self.last = last;
+                if self.at_max() {
+                    log::warn!("Reached {} frames, ignoring the rest", self.recorded);
+                }
+            }
+            Err(e) => log::error!("skipping frame: {e}"),
+        }
+    }
+}
+
+// Frames can be drawn from any thread (a rayon render, say), so this isn't a thread local
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+// A panicking render could have poisoned the lock, the frames are still worth keeping
+fn recorder() -> std::sync::MutexGuard<'static, Option<Recorder>> {
+    RECORDER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// While this is alive, push() sends frames to `sink`
+// Dropping it (including while unwinding from a panic) finishes the sink
+pub struct Recording;
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let Some(recorder) = recorder().take() else {
+            return;
+        };
+        if recorder.duplicates > 0 {
+            log::info!("Dropped {} duplicate frames", recorder.duplicates);
+        }
+        if std::thread::panicking() {
+            log::warn!(
+                "Render panicked, keeping the {} frames drawn so far",
+                recorder.recorded
+            );
+        }
+        if let Err(e) = recorder.sink.finish() {
+            log::error!("{e}");
+        }
+    }
+}
+
+pub fn record(sink: Box<dyn Sink>, limits: Limits) -> Recording {
+    *recorder() = Some(Recorder {
+        sink,
+        limits,
+        recorded: 0,
+        duplicates: 0,
+        last: None,
+    });
+    Recording
+}
+
+pub fn push(frame: RgbImage, annotations: Annotations) {
+    match recorder().as_mut() {
+        Some(recorder) => recorder.push(frame, annotations),
+        None => log::warn!("frame drawn outside of a render, ignoring it"),
+    }
+}
+
+// Once max_frames is reached there's no point drawing any more
+pub fn accepting() -> bool {
+    recorder().as_ref().is_some_and(|r| !r.full())
+}
+
+// Only one thing can record at a time, anything that might run alongside another (tests) takes this first
+pub static EXCLUSIVE: Mutex<()> = Mutex::new(());
+
+// aoc::render_test! records the frames itself, the render shouldn't swap in its own sink
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+pub fn capturing() -> bool {
+    CAPTURING.load(Ordering::Relaxed)
+}
+
+// The render's own max_frames and dedup, which still apply while capturing
+pub fn set_limits(limits: Limits) {
+    if let Some(recorder) = recorder().as_mut() {
+        recorder.limits = limits;
+    }
+}
+
+struct Collect(Arc<Mutex<Vec<RgbImage>>>);
+
+impl Sink for Collect {
+    fn push(&mut self, frame: RgbImage, _: Annotations) -> Result<(), String> {
+        self.0.lock().unwrap().push(frame);
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Frames kept in memory as they are, before scaling or encoding
+pub struct Capture {
+    frames: Arc<Mutex<Vec<RgbImage>>>,
+    recording: Option<Recording>,
+}
+
+pub fn capture() -> Capture {
+    let frames = Arc::new(Mutex::new(vec![]));
+    let recording = record(Box::new(Collect(frames.clone())), Limits::default());
+    CAPTURING.store(true, Ordering::Relaxed);
+    Capture {
+        frames,
+        recording: Some(recording),
+    }
+}
+
+impl Capture {
+    pub fn frames(mut self) -> Vec<RgbImage> {
+        self.recording.take();
+        std::mem::take(&mut *self.frames.lock().unwrap())
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        self.recording.take();
+        CAPTURING.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    // Keeps frames in memory so the tests can look at them
+    struct Collect(Arc<Mutex<Vec<RgbImage>>>, Arc<Mutex<bool>>);
+
+    impl Sink for Collect {
+        fn push(&mut self, frame: RgbImage, _: Annotations) -> Result<(), String> {
+            self.0.lock().unwrap().push(frame);
+            Ok(())
+        }
+
+        fn finish(self: Box<Self>) -> Result<(), String> {
+            *self.1.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    fn solid(value: u8) -> RgbImage {
+        RgbImage::from_pixel(2, 2, image::Rgb([value, 0, 0]))
+    }
+
+    // The recorder is global, so everything using it lives in one test
+    #[test]
+    fn test_recording() {
+        let _lock = EXCLUSIVE.lock().unwrap_or_else(|e| e.into_inner());
+        let frames = Arc::new(Mutex::new(vec![]));
+        let finished = Arc::new(Mutex::new(false));
+        let limits = Limits {
+            max_frames: Some(3),
+            dedup: true,
+        };
+
+        let recording = record(Box::new(Collect(frames.clone(), finished.clone())), limits);
+        for value in [1, 1, 2, 2, 1, 3, 4] {
+            push(solid(value), Annotations::default());
+        }
+        assert!(!accepting());
+        drop(recording);
+
+        let values = frames
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|f| f.get_pixel(0, 0).0[0])
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 1]);
+        assert!(*finished.lock().unwrap());
+
+        // Finished even though the render panicked
+        let frames = Arc::new(Mutex::new(vec![]));
+        let finished = Arc::new(Mutex::new(false));
+        let sink = Collect(frames.clone(), finished.clone());
+        let result = std::panic::catch_unwind(move || {
+            let _recording = record(Box::new(sink), Limits::default());
+            push(solid(1), Annotations::default());
+            panic!("render failed");
+        });
+        assert!(result.is_err());
+        assert_eq!(frames.lock().unwrap().len(), 1);
+        assert!(*finished.lock().unwrap());
+        assert!(!accepting());
+    }
+
+    #[test]
+    fn test_spool() {
+        let _lock = output::TEMP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut spool = Spool::new((2, 2)).unwrap();
+        let two = Annotations {
+            label: Some("two".to_string()),
+            overlay: None,
+        };
+        spool.push(&solid(1), Annotations::default()).unwrap();
+        spool.push(&solid(2), two).unwrap();
+        assert!(
+            spool
+                .push(&RgbImage::new(3, 2), Annotations::default())
+                .is_err()
+        );
+        assert_eq!(spool.label(1), Some("two"));
+
+        // Readable more than once
+        for _ in 0..2 {
+            let mut values = vec![];
+            spool
+                .try_for_each(&mut |f| {
+                    values.push(f.get_pixel(1, 1).0[0]);
+                    Ok(())
+                })
+                .unwrap();
+            assert_eq!(values, vec![1, 2]);
+        }
+
+        let path = spool.path.clone();
+        drop(spool);
+        assert!(!path.exists());
+        output::cleanup();
+    }
+}
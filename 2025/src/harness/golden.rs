@@ -0,0 +1,306 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use image::RgbImage;
+
+use super::frames;
+
+// Golden image tests for renders, run by aoc::render_test!
+// A render is run on a small input with its frames captured (not encoded), and the chosen frames are compared
+// against PNGs (or perceptual hashes) saved under tests/golden/<day>/. Blessing writes them instead.
+
+// `AOC_BLESS=1 cargo test` updates the goldens rather than checking them (as does `render-test --bless`)
+pub const BLESS_ENV: &str = "AOC_BLESS";
+
+pub fn bless_from_env() -> bool {
+    std::env::var_os(BLESS_ENV).is_some_and(|v| v != "0" && !v.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pick {
+    First,
+    Last,
+    Index(usize),
+    // 0, n, 2n, ...
+    Every(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compare {
+    // Every pixel the same as the golden PNG
+    Pixels,
+    // Perceptual hashes at most this many bits apart, so small changes (a colour tweak, a moved label) still pass
+    Hash(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct Test<'a> {
+    // Goldens are named <name>_<frame>.png (or .hash)
+    pub dir: PathBuf,
+    // Where frames that don't match go, named like their goldens, to diff against them
+    pub actual: PathBuf,
+    pub name: &'a str,
+    pub picks: &'a [Pick],
+    pub compare: Compare,
+}
+
+// Under the target directory, so frames that didn't match are never mistaken for goldens (or checked in)
+pub fn actual_dir(manifest_dir: &Path, day: &str) -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| manifest_dir.join("target"), PathBuf::from)
+        .join("golden-actual")
+        .join(day)
+}
+
+// Which frames `picks` select out of `len`, in order and without repeats
+pub fn select(picks: &[Pick], len: usize) -> Result<Vec<usize>, String> {
+    if len == 0 {
+        return Err("the render didn't draw any frames".to_string());
+    }
+    let mut selected = BTreeSet::new();
+    for pick in picks {
+        match *pick {
+            Pick::First => {
+                selected.insert(0);
+            }
+            Pick::Last => {
+                selected.insert(len - 1);
+            }
+            Pick::Index(i) if i < len => {
+                selected.insert(i);
+            }
+            Pick::Index(i) => return Err(format!("frame {i} picked, but only {len} were drawn")),
+            Pick::Every(n) => selected.extend((0..len).step_by(n.max(1))),
+        }
+    }
+    Ok(selected.into_iter().collect())
+}
+
+// A 64 bit difference hash: shrink to 9x8 in grey and note whether each pixel is brighter than the one to its right
+pub fn perceptual_hash(frame: &RgbImage) -> u64 {
+    let grey = image::imageops::grayscale(frame);
+    let small = image::imageops::resize(&grey, 9, 8, image::imageops::FilterType::Triangle);
+    let mut hash = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            let brighter = small.get_pixel(x, y).0[0] > small.get_pixel(x + 1, y).0[0];
+            hash = (hash << 1) | brighter as u64;
+        }
+    }
+    hash
+}
+
+// The render's frames, in memory and unscaled
+// Renders share one recorder, so only one is captured at a time even when the tests run in parallel
+pub fn capture(render: impl FnOnce()) -> Vec<RgbImage> {
+    let _lock = frames::EXCLUSIVE.lock().unwrap_or_else(|e| e.into_inner());
+
+    let capture = frames::capture();
+    render();
+    capture.frames()
+}
+
+// Run `render` on `input` and check (or with `bless`, write) its goldens
+// Ok says what happened, Err lists every frame that didn't match
+pub fn check(test: &Test, input: &str, render: fn(&str), bless: bool) -> Result<String, String> {
+    let frames = capture(|| render(input));
+    let selected = select(test.picks, frames.len())?;
+    let extension = match test.compare {
+        Compare::Pixels => "png",
+        Compare::Hash(_) => "hash",
+    };
+    let file = |i: usize| format!("{}_{i:04}.{extension}", test.name);
+    let golden = |i: usize| test.dir.join(file(i));
+
+    if bless {
+        std::fs::create_dir_all(&test.dir).map_err(|e| format!("{}: {e}", test.dir.display()))?;
+        remove_stale(test, &selected, extension);
+        for &i in &selected {
+            let path = golden(i);
+            let written = match test.compare {
+                Compare::Pixels => frames[i].save(&path).map_err(|e| e.to_string()),
+                Compare::Hash(_) => {
+                    std::fs::write(&path, format!("{:016x}\n", perceptual_hash(&frames[i])))
+                        .map_err(|e| e.to_string())
+                }
+            };
+            written.map_err(|e| format!("{}: {e}", path.display()))?;
+        }
+        return Ok(format!(
+            "blessed {} of {} frames in {}",
+            selected.len(),
+            frames.len(),
+            test.dir.display()
+        ));
+    }
+
+    let failures = selected
+        .iter()
+        .filter_map(|&i| {
+            compare(
+                test.compare,
+                &frames[i],
+                &golden(i),
+                &test.actual.join(file(i)),
+            )
+            .err()
+        })
+        .collect::<Vec<_>>();
+    match failures.is_empty() {
+        true => Ok(format!(
+            "{} of {} frames match",
+            selected.len(),
+            frames.len()
+        )),
+        false => Err(format!(
+            "{} (set {BLESS_ENV}=1 or run `render-test --bless` to accept the new frames)",
+            failures.join("\n")
+        )),
+    }
+}
+
+fn compare(compare: Compare, frame: &RgbImage, path: &Path, actual: &Path) -> Result<(), String> {
+    let missing = |e| format!("{}: no golden ({e})", path.display());
+    match compare {
+        Compare::Pixels => {
+            let golden = image::open(path)
+                .map_err(|e| missing(e.to_string()))?
+                .to_rgb8();
+            if golden.dimensions() != frame.dimensions() {
+                return Err(format!(
+                    "{}: expected {:?}, drew {:?}",
+                    path.display(),
+                    golden.dimensions(),
+                    frame.dimensions()
+                ));
+            }
+            let different = golden
+                .pixels()
+                .zip(frame.pixels())
+                .filter(|(a, b)| a != b)
+                .count();
+            if different == 0 {
+                return Ok(());
+            }
+            let _ = std::fs::create_dir_all(actual.parent().unwrap());
+            let _ = frame.save(actual);
+            Err(format!(
+                "{}: {different} pixels differ, see {}",
+                path.display(),
+                actual.display()
+            ))
+        }
+        Compare::Hash(tolerance) => {
+            let text = std::fs::read_to_string(path).map_err(|e| missing(e.to_string()))?;
+            let golden = u64::from_str_radix(text.trim(), 16)
+                .map_err(|e| format!("{}: bad hash: {e}", path.display()))?;
+            let distance = (golden ^ perceptual_hash(frame)).count_ones();
+            match distance <= tolerance {
+                true => Ok(()),
+                false => Err(format!(
+                    "{}: perceptual hash is {distance} bits off (allowed {tolerance})",
+                    path.display()
+                )),
+            }
+        }
+    }
+}
+
+// Goldens for frames this test no longer picks, or from before it changed how it compares
+fn remove_stale(test: &Test, selected: &[usize], current: &str) {
+    let Ok(entries) = std::fs::read_dir(&test.dir) else {
+        return;
+    };
+    let prefix = format!("{}_", test.name);
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(rest) = file_name.strip_prefix(&prefix) else {
+            continue;
+        };
+        let (index, extension) = rest.split_once('.').unwrap_or((rest, ""));
+        let keep = extension == current
+            && index.len() == 4
+            && index.parse().is_ok_and(|i: usize| selected.contains(&i));
+        if !keep && index.chars().all(|c| c.is_ascii_digit()) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solid(value: u8) -> RgbImage {
+        RgbImage::from_pixel(4, 4, image::Rgb([value, value, value]))
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select(&[Pick::First, Pick::Last], 5), Ok(vec![0, 4]));
+        assert_eq!(
+            select(&[Pick::Every(2), Pick::Last], 6),
+            Ok(vec![0, 2, 4, 5])
+        );
+        assert_eq!(select(&[Pick::First, Pick::Last], 1), Ok(vec![0]));
+        assert!(select(&[Pick::Index(5)], 5).is_err());
+        assert!(select(&[Pick::First], 0).is_err());
+    }
+
+    #[test]
+    fn test_perceptual_hash() {
+        let gradient = RgbImage::from_fn(32, 32, |x, _| image::Rgb([255 - x as u8 * 8, 0, 0]));
+        let mut speck = gradient.clone();
+        speck.put_pixel(3, 3, image::Rgb([0, 255, 0]));
+
+        assert_eq!(perceptual_hash(&gradient), u64::MAX);
+        assert_eq!(perceptual_hash(&solid(10)), 0);
+        assert!((perceptual_hash(&gradient) ^ perceptual_hash(&speck)).count_ones() <= 2);
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = std::env::temp_dir().join(format!("aoc_golden_test_{}", std::process::id()));
+        let actual = dir.join("actual");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        fn render(input: &str) {
+            for c in input.bytes() {
+                frames::push(solid(c), Default::default());
+            }
+        }
+
+        let test = Test {
+            dir: dir.clone(),
+            actual: actual.clone(),
+            name: "render",
+            picks: &[Pick::First, Pick::Last],
+            compare: Compare::Pixels,
+        };
+        assert!(check(&test, "abc", render, false).is_err());
+        check(&test, "abc", render, true).unwrap();
+        assert!(dir.join("render_0000.png").exists());
+        assert!(dir.join("render_0002.png").exists());
+        check(&test, "abc", render, false).unwrap();
+        assert!(check(&test, "abd", render, false).is_err());
+        assert!(actual.join("render_0002.png").exists());
+        assert!(!dir.join("render_0002.actual.png").exists());
+
+        // A bless picking fewer frames tidies up after itself
+        let test = Test {
+            picks: &[Pick::First],
+            ..test
+        };
+        check(&test, "abc", render, true).unwrap();
+        assert!(!dir.join("render_0002.png").exists());
+
+        let test = Test {
+            compare: Compare::Hash(0),
+            ..test
+        };
+        check(&test, "abc", render, true).unwrap();
+        check(&test, "abc", render, false).unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = "github.com/jpverkamp/advent-of-code";
+
+#[derive(Debug)]
+pub enum InputError {
+    BadDay(String),
+    MissingSession,
+    Http(String),
+    Io(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::BadDay(day) => write!(f, "cannot determine day number from {day:?}"),
+            InputError::MissingSession => write!(
+                f,
+                "no session token; set AOC_SESSION or write it to {}",
+                default_session_file().display()
+            ),
+            InputError::Http(msg) => write!(f, "failed to download input: {msg}"),
+            InputError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// Where inputs live and where to get them from
+// Everything can be overridden from the environment:
+// - AOC_INPUT_DIR: root of the input cache (default: input)
+// - AOC_URL: base URL of the puzzle server (default: https://adventofcode.com)
+// - AOC_SESSION: session cookie value
+// - AOC_SESSION_FILE: file containing the session cookie (default: ~/.config/aoc/session)
+#[derive(Debug, Clone)]
+pub struct InputConfig {
+    pub input_dir: PathBuf,
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl InputConfig {
+    pub fn from_env() -> InputConfig {
+        let input_dir = std::env::var("AOC_INPUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("input"));
+
+        let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+
+        let session = std::env::var("AOC_SESSION").ok().or_else(|| {
+            let path = std::env::var("AOC_SESSION_FILE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| default_session_file());
+            std::fs::read_to_string(path).ok()
+        });
+        let session = session
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+
+        InputConfig {
+            input_dir,
+            base_url,
+            session,
+        }
+    }
+
+    // input/2025/day1.txt
+    pub fn path_for(&self, year: u32, day: &str) -> PathBuf {
+        self.input_dir
+            .join(year.to_string())
+            .join(format!("{day}.txt"))
+    }
+
+    pub fn url_for(&self, year: u32, day: &str) -> Result<String, InputError> {
+        let n = day_number(day).ok_or_else(|| InputError::BadDay(day.to_string()))?;
+        Ok(format!(
+            "{}/{year}/day/{n}/input",
+            self.base_url.trim_end_matches('/')
+        ))
+    }
+}
+
+pub fn default_session_file() -> PathBuf {
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|h| Path::new(&h).join(".config")))
+        .unwrap_or_else(|_| PathBuf::from("."));
+    config_dir.join("aoc").join("session")
+}
+
+// Extract the numeric part of a day name: day1 -> 1, day12 -> 12
+pub fn day_number(day: &str) -> Option<u32> {
+    let digits = day
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok().filter(|n| (1..=25).contains(n))
+}
+
+// Download the input for a day and store it in the cache, overwriting anything already there
+pub fn fetch(config: &InputConfig, year: u32, day: &str) -> Result<PathBuf, InputError> {
+    let url = config.url_for(year, day)?;
+    let session = config.session.as_ref().ok_or(InputError::MissingSession)?;
+
+    log::info!("Fetching {url}");
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| InputError::Http(e.to_string()))?;
+    let body = response
+        .into_string()
+        .map_err(|e| InputError::Http(e.to_string()))?;
+
+    let path = config.path_for(year, day);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| InputError::Io(parent.to_path_buf(), e))?;
+    }
+    std::fs::write(&path, body).map_err(|e| InputError::Io(path.clone(), e))?;
+
+    Ok(path)
+}
+
+// Return the path of the cached input for a day, downloading it first if it's missing
+pub fn resolve(config: &InputConfig, year: u32, day: &str) -> Result<PathBuf, InputError> {
+    let path = config.path_for(year, day);
+    if path.exists() {
+        Ok(path)
+    } else {
+        fetch(config, year, day)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    // Serve a single request with the given body, returning the request head that was received
+    fn stub_server(body: &'static str) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+
+        (url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aoc_input_test_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_day_number() {
+        assert_eq!(day_number("day1"), Some(1));
+        assert_eq!(day_number("day12"), Some(12));
+        assert_eq!(day_number("dayN"), None);
+        assert_eq!(day_number("day26"), None);
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let (url, server) = stub_server("1 2 3\n");
+        let config = InputConfig {
+            input_dir: temp_dir("fetch"),
+            base_url: url,
+            session: Some("cafebabe".to_string()),
+        };
+
+        let path = resolve(&config, 2025, "day3").unwrap();
+        assert_eq!(path, config.input_dir.join("2025").join("day3.txt"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+        let head = server.join().unwrap();
+        assert_eq!(head[0], "GET /2025/day/3/input HTTP/1.1");
+        assert!(head.iter().any(|l| l == "Cookie: session=cafebabe"));
+
+        // Second resolve hits the cache; the stub server is gone so a request would fail
+        assert_eq!(resolve(&config, 2025, "day3").unwrap(), path);
+
+        std::fs::remove_dir_all(&config.input_dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let config = InputConfig {
+            input_dir: temp_dir("nosession"),
+            base_url: "http://127.0.0.1:1".to_string(),
+            session: None,
+        };
+        assert!(matches!(
+            resolve(&config, 2025, "day1"),
+            Err(InputError::MissingSession)
+        ));
+    }
+}
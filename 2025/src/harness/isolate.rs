@@ -0,0 +1,238 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    pub memory_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok(String),
+    Panic(String),
+    Timeout(Duration),
+    Oom,
+    Failed(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok(out) => write!(f, "{out}"),
+            Status::Panic(msg) => write!(f, "PANIC ({msg})"),
+            Status::Timeout(after) => write!(f, "TIMEOUT (after {after:?})"),
+            Status::Oom => write!(f, "OOM"),
+            Status::Failed(msg) => write!(f, "FAILED ({msg})"),
+        }
+    }
+}
+
+// Run a command in its own process, feeding it `input` on stdin
+// If it runs past the timeout it's killed; if a memory cap is set, the child's address space is limited
+pub fn run_isolated(cmd: Command, input: &str, limits: &Limits) -> Status {
+    run_isolated_with(cmd, input, limits, |_| false)
+}
+
+// Same, but each line the child writes to stderr is passed to `on_stderr` as it arrives
+// Lines it returns true for are consumed (and left out of panic/error detection)
+pub fn run_isolated_with(
+    mut cmd: Command,
+    input: &str,
+    limits: &Limits,
+    mut on_stderr: impl FnMut(&str) -> bool,
+) -> Status {
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    if let Some(mb) = limits.memory_mb {
+        use std::os::unix::process::CommandExt;
+        let bytes = mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+        // Safety: only calls setrlimit, which is async-signal-safe
+        unsafe {
+            cmd.pre_exec(move || {
+                let limit = libc::rlimit {
+                    rlim_cur: bytes,
+                    rlim_max: bytes,
+                };
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return Status::Failed(e.to_string()),
+    };
+
+    // Feed stdin and drain stdout/stderr on their own threads so a chatty child can't block on a full pipe
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    let writer = std::thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let stdout_reader = std::thread::spawn(move || {
+        let mut s = String::new();
+        let _ = stdout.read_to_string(&mut s);
+        s
+    });
+
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let (stderr_tx, stderr_rx) = std::sync::mpsc::channel();
+    let stderr_reader = std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            let Ok(line) = line else { break };
+            if stderr_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stderr = String::new();
+    let mut handle_stderr = |line: String| {
+        if !on_stderr(&line) {
+            stderr.push_str(&line);
+            stderr.push('\n');
+        }
+    };
+
+    let start = Instant::now();
+    let status = loop {
+        stderr_rx.try_iter().for_each(&mut handle_stderr);
+
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) => {}
+            Err(e) => return Status::Failed(e.to_string()),
+        }
+
+        if limits.timeout.is_some_and(|t| start.elapsed() > t) {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+
+        std::thread::sleep(Duration::from_millis(5));
+    };
+
+    let _ = writer.join();
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let _ = stderr_reader.join();
+    stderr_rx.try_iter().for_each(&mut handle_stderr);
+
+    let Some(status) = status else {
+        return Status::Timeout(limits.timeout.unwrap_or_default());
+    };
+
+    if status.success() {
+        return Status::Ok(stdout.trim_end().to_string());
+    }
+
+    // Rust's default allocation error handler prints this before aborting
+    if stderr.contains("memory allocation of") {
+        return Status::Oom;
+    }
+
+    // Killed without us asking: most likely the kernel's OOM killer
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if status.signal() == Some(libc::SIGKILL) {
+            return Status::Oom;
+        }
+    }
+
+    if let Some(line) = stderr.lines().find(|l| l.contains("panicked at")) {
+        // The message is on the line after `thread 'main' panicked at src/bin/day1.rs:1:1:`
+        let message = stderr
+            .lines()
+            .skip_while(|l| *l != line)
+            .nth(1)
+            .filter(|l| !l.starts_with("note:"))
+            .unwrap_or(line);
+        return Status::Panic(message.trim().to_string());
+    }
+
+    Status::Failed(
+        stderr
+            .lines()
+            .rfind(|l| !l.trim().is_empty())
+            .map_or_else(|| status.to_string(), |l| l.trim().to_string()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sh(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    #[test]
+    fn test_ok() {
+        let status = run_isolated(Command::new("cat"), "hello\n", &Limits::default());
+        assert_eq!(status, Status::Ok("hello".to_string()));
+    }
+
+    #[test]
+    fn test_stderr_lines() {
+        let mut seen = vec![];
+        let status = run_isolated_with(
+            sh("echo one >&2; echo two >&2; exit 1"),
+            "",
+            &Limits::default(),
+            |line| {
+                seen.push(line.to_string());
+                line == "two"
+            },
+        );
+        assert_eq!(seen, vec!["one", "two"]);
+        assert_eq!(status, Status::Failed("one".to_string()));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(50)),
+            memory_mb: None,
+        };
+        let start = Instant::now();
+        let status = run_isolated(sh("exec sleep 10"), "", &limits);
+        assert_eq!(status, Status::Timeout(Duration::from_millis(50)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_panic() {
+        let status = run_isolated(
+            sh(
+                "echo \"thread 'main' panicked at src/bin/day1.rs:1:1:\" >&2; echo 'oh no' >&2; exit 101",
+            ),
+            "",
+            &Limits::default(),
+        );
+        assert_eq!(status, Status::Panic("oh no".to_string()));
+    }
+
+    #[test]
+    fn test_oom() {
+        let status = run_isolated(
+            sh("echo 'memory allocation of 1024 bytes failed' >&2; kill -ABRT $$"),
+            "",
+            &Limits::default(),
+        );
+        assert_eq!(status, Status::Oom);
+    }
+}
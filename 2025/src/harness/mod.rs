@@ -0,0 +1,15 @@
+// Runtime support for the code generated by aoc::main!
+// The macro handles argument parsing; anything with real logic (network, files) lives here so it can be tested.
+
+pub mod bench;
+pub mod encode;
+pub mod frames;
+pub mod golden;
+pub mod input;
+pub mod isolate;
+pub mod output;
+pub mod progress;
+pub mod submit;
+pub mod svg;
+pub mod tty;
+pub mod verify;
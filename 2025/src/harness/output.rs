@@ -0,0 +1,236 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use input_hash::input_hash;
+
+// Where renders end up, what they're called, and a record of everything written
+//
+// Files go in `--out-dir` (the current directory by default) named by `--name-template`, and each one gets
+// a line in manifest.jsonl there. Scratch files (spooled frames, PNGs for ffmpeg) go in a temporary
+// directory of this run's own, removed when the run is done.
+
+pub const DEFAULT_TEMPLATE: &str = "aoc{year}_{day}_{name}.{ext}";
+pub const MANIFEST: &str = "manifest.jsonl";
+
+const PLACEHOLDERS: [&str; 6] = ["year", "day", "name", "variant", "input", "ext"];
+
+static OUT_DIR: OnceLock<PathBuf> = OnceLock::new();
+static TEMPLATE: OnceLock<String> = OnceLock::new();
+
+pub fn set_out_dir(dir: PathBuf) {
+    let _ = OUT_DIR.set(dir);
+}
+
+pub fn out_dir() -> PathBuf {
+    OUT_DIR.get().cloned().unwrap_or_default()
+}
+
+// Checked up front, a typo shouldn't only turn up once a long render has finished
+pub fn set_template(template: &str) -> Result<(), String> {
+    expand(template, &Fields::default())?;
+    let _ = TEMPLATE.set(template.to_string());
+    Ok(())
+}
+
+pub fn template() -> &'static str {
+    TEMPLATE.get().map_or(DEFAULT_TEMPLATE, |t| t.as_str())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    pub year: u32,
+    pub day: String,
+    // The image or render
+    pub name: String,
+    // The registered solution or render that was running, empty outside `render`
+    pub variant: String,
+    // A hash of the input (the same one benches record), empty outside `render`
+    pub input: String,
+    pub ext: String,
+}
+
+// {year}, {day}, {name}, {variant}, {input} and {ext}, with {{ and }} for literal braces
+pub fn expand(template: &str, fields: &Fields) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let end = match (rest.starts_with('{'), rest.find('}')) {
+            (true, Some(end)) => end,
+            _ => return Err(format!("unmatched brace in name template {template:?}")),
+        };
+        out.push_str(&match &rest[1..end] {
+            "year" => fields.year.to_string(),
+            "day" => fields.day.clone(),
+            "name" => fields.name.clone(),
+            "variant" => fields.variant.clone(),
+            "input" => fields.input.clone(),
+            "ext" => fields.ext.clone(),
+            other => {
+                return Err(format!(
+                    "unknown placeholder {{{other}}} in name template, expected one of {}",
+                    PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+                ));
+            }
+        });
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// What's running, set by `render` before each render so files can be named after it
+#[derive(Debug, Clone, Default)]
+struct Context {
+    variant: String,
+    input: String,
+}
+
+static CONTEXT: Mutex<Option<Context>> = Mutex::new(None);
+
+pub fn begin(variant: &str, input: &str) {
+    *CONTEXT.lock().unwrap() = Some(Context {
+        variant: variant.to_string(),
+        input: input_hash(input),
+    });
+}
+
+// Where to write `name` (the output directory and anything the template puts in between are created)
+pub fn path_for(year: u32, day: &str, name: &str, ext: &str) -> io::Result<PathBuf> {
+    let context = CONTEXT.lock().unwrap().clone().unwrap_or_default();
+    let fields = Fields {
+        year,
+        day: day.to_string(),
+        name: name.to_string(),
+        variant: context.variant,
+        input: context.input,
+        ext: ext.to_string(),
+    };
+    let path = out_dir().join(expand(template(), &fields).map_err(io::Error::other)?);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+// Seconds and pid: unique on this machine, and sorts by when the run started
+pub fn run_id() -> &'static str {
+    static RUN_ID: OnceLock<String> = OnceLock::new();
+    RUN_ID.get_or_init(|| {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        format!("{secs}-{}", std::process::id())
+    })
+}
+
+static TEMP_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Somewhere nothing else is using for a scratch file or directory, in this run's temporary directory
+pub fn temp_path(prefix: &str) -> io::Result<PathBuf> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    let mut temp_dir = TEMP_DIR.lock().unwrap();
+    if temp_dir.is_none() {
+        // create_dir (not _all) fails if it's already there, so a leftover from an old run is never reused
+        let mut attempt = 0;
+        let dir = loop {
+            let dir = std::env::temp_dir().join(format!("aoc2025_{}_{attempt}", run_id()));
+            match std::fs::create_dir(&dir) {
+                Ok(()) => break dir,
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e),
+            }
+        };
+        *temp_dir = Some(dir);
+    }
+
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    Ok(temp_dir.as_ref().unwrap().join(format!("{prefix}_{n}")))
+}
+
+// Tests that use the temporary directory hold this, so one cleaning up doesn't pull it out from under another
+#[cfg(test)]
+pub(crate) static TEMP_LOCK: Mutex<()> = Mutex::new(());
+
+// Remove this run's temporary directory and anything left in it
+pub fn cleanup() {
+    if let Some(dir) = TEMP_DIR.lock().unwrap().take() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+// Add a line for `path` to the manifest in the output directory
+// `format` is the encoder that wrote it (apng, say, where the extension only says png); `frames` is None for still images
+pub fn record(path: &Path, format: &str, frames: Option<usize>) {
+    let context = CONTEXT.lock().unwrap().clone().unwrap_or_default();
+    let line = serde_json::json!({
+        "run": run_id(),
+        "path": path,
+        "format": format,
+        "frames": frames,
+        "variant": context.variant,
+        "input": context.input,
+    });
+
+    let manifest = out_dir().join(MANIFEST);
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&manifest)
+        .and_then(|mut f| writeln!(f, "{line}"));
+    if let Err(e) = result {
+        log::error!("failed to update {}: {}", manifest.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let fields = Fields {
+            year: 2025,
+            day: "day4".to_string(),
+            name: "part2_render".to_string(),
+            variant: "part2_render".to_string(),
+            input: "1234abcd".to_string(),
+            ext: "gif".to_string(),
+        };
+
+        assert_eq!(
+            expand(DEFAULT_TEMPLATE, &fields).unwrap(),
+            "aoc2025_day4_part2_render.gif"
+        );
+        assert_eq!(
+            expand("{day}/{variant}-{input}{{1}}.{ext}", &fields).unwrap(),
+            "day4/part2_render-1234abcd{1}.gif"
+        );
+        assert!(expand("{nmae}.{ext}", &fields).is_err());
+        assert!(expand("{name", &fields).is_err());
+    }
+
+    #[test]
+    fn test_temp_path() {
+        let _lock = TEMP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let a = temp_path("test").unwrap();
+        let b = temp_path("test").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.parent(), b.parent());
+
+        let dir = a.parent().unwrap().to_path_buf();
+        assert!(dir.is_dir());
+        cleanup();
+        assert!(!dir.exists());
+    }
+}
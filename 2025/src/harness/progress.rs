@@ -0,0 +1,248 @@
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+// Isolated children write their events to stderr with this prefix so the parent can pick them out
+pub const FORWARD_PREFIX: &str = "aoc-progress: ";
+// Set on isolated children so they forward events instead of drawing a bar
+pub const FORWARD_ENV: &str = "AOC_PROGRESS_FORWARD";
+
+// Don't flood the terminal (or the pipe) with progress from tight loops
+const MIN_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Start {
+        name: String,
+    },
+    Progress {
+        name: String,
+        fraction: f64,
+    },
+    Best {
+        name: String,
+        answer: String,
+    },
+    Done {
+        name: String,
+        answer: String,
+        elapsed_ms: f64,
+    },
+    Failed {
+        name: String,
+        status: String,
+        elapsed_ms: f64,
+    },
+}
+
+impl Event {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize event")
+    }
+}
+
+pub type Sink = Arc<dyn Fn(&Event) + Send + Sync>;
+
+static CURRENT: RwLock<Option<(String, Sink)>> = RwLock::new(None);
+
+// While this is alive, Progress::current() reports to `sink` as `name`
+pub struct Installed;
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        *CURRENT.write().unwrap() = None;
+    }
+}
+
+pub fn install(name: &str, sink: Sink) -> Installed {
+    *CURRENT.write().unwrap() = Some((name.to_string(), sink));
+    Installed
+}
+
+// Handed to solutions registered with a second argument: fn part2(input: &str, progress: &Progress)
+// Does nothing if no runner is listening (tests, bench, verify)
+pub struct Progress {
+    target: Option<(String, Sink)>,
+    last: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    pub fn current() -> Progress {
+        Progress {
+            target: CURRENT.read().unwrap().clone(),
+            last: Mutex::new(None),
+        }
+    }
+
+    // How far along we are, 0.0 to 1.0
+    pub fn fraction(&self, fraction: f64) {
+        let Some((name, sink)) = &self.target else {
+            return;
+        };
+
+        let mut last = self.last.lock().unwrap();
+        if fraction < 1.0 && last.is_some_and(|t| t.elapsed() < MIN_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+
+        sink(&Event::Progress {
+            name: name.clone(),
+            fraction: fraction.clamp(0.0, 1.0),
+        });
+    }
+
+    pub fn step(&self, done: usize, total: usize) {
+        if total > 0 {
+            self.fraction(done as f64 / total as f64);
+        }
+    }
+
+    // The best answer found so far (for searches that improve over time)
+    pub fn best(&self, answer: impl fmt::Display) {
+        if let Some((name, sink)) = &self.target {
+            sink(&Event::Best {
+                name: name.clone(),
+                answer: answer.to_string(),
+            });
+        }
+    }
+}
+
+// Newline delimited JSON on stdout
+pub fn json_sink() -> Sink {
+    Arc::new(|event| {
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", event.to_json());
+        let _ = stdout.flush();
+    })
+}
+
+// Used by isolated children, see FORWARD_PREFIX
+pub fn forward_sink() -> Sink {
+    Arc::new(|event| eprintln!("{FORWARD_PREFIX}{}", event.to_json()))
+}
+
+pub fn parse_forwarded(line: &str) -> Option<Event> {
+    serde_json::from_str(line.strip_prefix(FORWARD_PREFIX)?).ok()
+}
+
+// A single line progress bar on stderr, cleared once the solution finishes
+// Draws nothing if stderr isn't a terminal
+pub fn bar_sink() -> Sink {
+    if !std::io::stderr().is_terminal() {
+        return Arc::new(|_| {});
+    }
+
+    let best = Mutex::new(None::<String>);
+    let fraction = Mutex::new(0.0);
+    Arc::new(move |event| {
+        let name = match event {
+            Event::Start { .. } | Event::Done { .. } | Event::Failed { .. } => {
+                *best.lock().unwrap() = None;
+                *fraction.lock().unwrap() = 0.0;
+                eprint!("\r\x1b[2K");
+                return;
+            }
+            Event::Progress { name, fraction: f } => {
+                *fraction.lock().unwrap() = *f;
+                name
+            }
+            Event::Best { name, answer } => {
+                *best.lock().unwrap() = Some(answer.clone());
+                name
+            }
+        };
+
+        let line = bar_line(
+            name,
+            *fraction.lock().unwrap(),
+            best.lock().unwrap().as_deref(),
+        );
+        eprint!("\r\x1b[2K{line}");
+        let _ = std::io::stderr().flush();
+    })
+}
+
+// part2 [##########----------]  50.0% best: 1234
+pub fn bar_line(name: &str, fraction: f64, best: Option<&str>) -> String {
+    const WIDTH: usize = 20;
+    let filled = ((fraction * WIDTH as f64).round() as usize).min(WIDTH);
+    let mut line = format!(
+        "{name} [{}{}] {:5.1}%",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        fraction * 100.0
+    );
+    if let Some(best) = best {
+        line.push_str(&format!(" best: {best}"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collect() -> (Sink, Arc<Mutex<Vec<Event>>>) {
+        let events = Arc::new(Mutex::new(vec![]));
+        let inner = events.clone();
+        let sink: Sink = Arc::new(move |e| inner.lock().unwrap().push(e.clone()));
+        (sink, events)
+    }
+
+    #[test]
+    fn test_json() {
+        let event = Event::Progress {
+            name: "part2".to_string(),
+            fraction: 0.5,
+        };
+        assert_eq!(
+            event.to_json(),
+            r#"{"event":"progress","name":"part2","fraction":0.5}"#
+        );
+    }
+
+    #[test]
+    fn test_forwarded() {
+        let event = Event::Best {
+            name: "part1".to_string(),
+            answer: "42".to_string(),
+        };
+        let line = format!("{FORWARD_PREFIX}{}", event.to_json());
+        assert_eq!(parse_forwarded(&line), Some(event));
+        assert_eq!(parse_forwarded("thread 'main' panicked at"), None);
+    }
+
+    #[test]
+    fn test_progress() {
+        let (sink, events) = collect();
+        let progress = Progress {
+            target: Some(("part1".to_string(), sink)),
+            last: Mutex::new(None),
+        };
+
+        // Only the first of a burst of updates gets through, but finishing and best answers always do
+        progress.step(1, 100);
+        progress.step(2, 100);
+        progress.best(17);
+        progress.step(100, 100);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[1], Event::Best { ref answer, .. } if answer == "17"));
+        assert!(matches!(events[2], Event::Progress { fraction, .. } if fraction == 1.0));
+    }
+
+    #[test]
+    fn test_bar_line() {
+        assert_eq!(
+            bar_line("part2", 0.5, Some("1234")),
+            "part2 [##########----------]  50.0% best: 1234"
+        );
+    }
+}
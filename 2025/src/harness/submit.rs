@@ -0,0 +1,410 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::input::{InputConfig, InputError, USER_AGENT, day_number};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooRecent => "too-recent",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-recent" => Ok(Verdict::TooRecent),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too-high"),
+            Hint::TooLow => write!(f, "too-low"),
+        }
+    }
+}
+
+impl std::str::FromStr for Hint {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too-high" => Ok(Hint::TooHigh),
+            "too-low" => Ok(Hint::TooLow),
+            _ => Err(()),
+        }
+    }
+}
+
+// A single submission, as stored in the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub timestamp: u64,
+    pub level: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub hint: Option<Hint>,
+}
+
+// Why an answer was not sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(u64),
+    AtOrAboveTooHigh(i128),
+    AtOrBelowTooLow(i128),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {answer}"),
+            Refusal::KnownWrong(timestamp) => {
+                write!(f, "already submitted at {timestamp} and was wrong")
+            }
+            Refusal::AtOrAboveTooHigh(bound) => write!(f, "{bound} was already too high"),
+            Refusal::AtOrBelowTooLow(bound) => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+// Every attempt for one day, one line per attempt:
+// timestamp <tab> level <tab> verdict <tab> hint (or -) <tab> answer
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    pub path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    // input/2025/day1.ledger
+    pub fn path_for(config: &InputConfig, year: u32, day: &str) -> PathBuf {
+        config.path_for(year, day).with_extension("ledger")
+    }
+
+    pub fn load(path: &Path) -> Result<Ledger, InputError> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(s) => s.lines().filter_map(parse_line).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(InputError::Io(path.to_path_buf(), e)),
+        };
+
+        Ok(Ledger {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), InputError> {
+        use std::io::Write;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| InputError::Io(parent.to_path_buf(), e))?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| InputError::Io(self.path.clone(), e))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            attempt.timestamp,
+            attempt.level,
+            attempt.verdict,
+            attempt.hint.map_or("-".to_string(), |h| h.to_string()),
+            attempt.answer
+        )
+        .map_err(|e| InputError::Io(self.path.clone(), e))?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    // Decide if an answer is worth sending based on what we've already learned
+    pub fn check(&self, level: u8, answer: &str) -> Result<(), Refusal> {
+        let attempts = self.attempts.iter().filter(|a| a.level == level);
+
+        let mut too_high: Option<i128> = None;
+        let mut too_low: Option<i128> = None;
+
+        for attempt in attempts {
+            match attempt.verdict {
+                Verdict::Correct => return Err(Refusal::AlreadySolved(attempt.answer.clone())),
+                Verdict::Wrong if attempt.answer == answer => {
+                    return Err(Refusal::KnownWrong(attempt.timestamp));
+                }
+                _ => {}
+            }
+
+            if let Ok(value) = attempt.answer.parse::<i128>() {
+                match attempt.hint {
+                    Some(Hint::TooHigh) => {
+                        too_high = Some(too_high.map_or(value, |b| b.min(value)))
+                    }
+                    Some(Hint::TooLow) => too_low = Some(too_low.map_or(value, |b| b.max(value))),
+                    None => {}
+                }
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(bound) = too_high.filter(|&b| value >= b) {
+                return Err(Refusal::AtOrAboveTooHigh(bound));
+            }
+            if let Some(bound) = too_low.filter(|&b| value <= b) {
+                return Err(Refusal::AtOrBelowTooLow(bound));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<Attempt> {
+    let mut parts = line.splitn(5, '\t');
+    let timestamp = parts.next()?.parse().ok()?;
+    let level = parts.next()?.parse().ok()?;
+    let verdict = parts.next()?.parse().ok()?;
+    let hint = parts.next()?.parse().ok();
+    let answer = parts.next()?.to_string();
+
+    Some(Attempt {
+        timestamp,
+        level,
+        answer,
+        verdict,
+        hint,
+    })
+}
+
+// Solutions are named part1, part1_regex, part2_bruteforce, etc
+pub fn level_for(name: &str) -> Option<u8> {
+    if name.starts_with("part1") {
+        Some(1)
+    } else if name.starts_with("part2") {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+// Pull the verdict out of the HTML response
+pub fn parse_verdict(body: &str) -> (Verdict, Option<Hint>) {
+    let verdict = if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::TooRecent
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+
+    let hint = if body.contains("your answer is too high") {
+        Some(Hint::TooHigh)
+    } else if body.contains("your answer is too low") {
+        Some(Hint::TooLow)
+    } else {
+        None
+    };
+
+    (verdict, hint)
+}
+
+// Send an answer and return what the server thought of it
+pub fn submit(
+    config: &InputConfig,
+    year: u32,
+    day: &str,
+    level: u8,
+    answer: &str,
+) -> Result<Attempt, InputError> {
+    let n = day_number(day).ok_or_else(|| InputError::BadDay(day.to_string()))?;
+    let session = config.session.as_ref().ok_or(InputError::MissingSession)?;
+    let url = format!(
+        "{}/{year}/day/{n}/answer",
+        config.base_url.trim_end_matches('/')
+    );
+
+    log::info!("Submitting {answer} for level {level} to {url}");
+    let response = ureq::post(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &level.to_string()), ("answer", answer)])
+        .map_err(|e| InputError::Http(e.to_string()))?;
+    let body = response
+        .into_string()
+        .map_err(|e| InputError::Http(e.to_string()))?;
+
+    let (verdict, hint) = parse_verdict(&body);
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs();
+
+    Ok(Attempt {
+        timestamp,
+        level,
+        answer: answer.to_string(),
+        verdict,
+        hint,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn attempt(answer: &str, verdict: Verdict, hint: Option<Hint>) -> Attempt {
+        Attempt {
+            timestamp: 0,
+            level: 1,
+            answer: answer.to_string(),
+            verdict,
+            hint,
+        }
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict("<p>That's not the right answer; your answer is too low.</p>"),
+            (Verdict::Wrong, Some(Hint::TooLow))
+        );
+        assert_eq!(
+            parse_verdict("<p>That's the right answer!</p>"),
+            (Verdict::Correct, None)
+        );
+    }
+
+    #[test]
+    fn test_ledger_check() {
+        let ledger = Ledger {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt("100", Verdict::Wrong, Some(Hint::TooHigh)),
+                attempt("10", Verdict::Wrong, Some(Hint::TooLow)),
+                attempt("42", Verdict::Wrong, None),
+            ],
+        };
+
+        assert_eq!(ledger.check(1, "42"), Err(Refusal::KnownWrong(0)));
+        assert_eq!(ledger.check(1, "150"), Err(Refusal::AtOrAboveTooHigh(100)));
+        assert_eq!(ledger.check(1, "5"), Err(Refusal::AtOrBelowTooLow(10)));
+        assert_eq!(ledger.check(1, "50"), Ok(()));
+        assert_eq!(ledger.check(2, "150"), Ok(()));
+    }
+
+    #[test]
+    fn test_ledger_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("aoc_ledger_test_{}.ledger", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut ledger = Ledger::load(&path).unwrap();
+        assert!(ledger.attempts.is_empty());
+        ledger
+            .record(attempt("12", Verdict::Wrong, Some(Hint::TooLow)))
+            .unwrap();
+        ledger
+            .record(attempt("13", Verdict::Correct, None))
+            .unwrap();
+
+        let reloaded = Ledger::load(&path).unwrap();
+        assert_eq!(reloaded.attempts, ledger.attempts);
+        assert_eq!(
+            reloaded.check(1, "14"),
+            Err(Refusal::AlreadySolved("13".to_string()))
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = InputConfig {
+            input_dir: PathBuf::from("input"),
+            base_url: format!("http://{}", listener.local_addr().unwrap()),
+            session: Some("cafebabe".to_string()),
+        };
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            let length: usize = head
+                .iter()
+                .find_map(|l| l.strip_prefix("Content-Length: "))
+                .unwrap()
+                .parse()
+                .unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response =
+                "<article><p>That's not the right answer; your answer is too high.</p></article>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            (head[0].clone(), String::from_utf8(body).unwrap())
+        });
+
+        let result = submit(&config, 2025, "day7", 2, "1234").unwrap();
+        assert_eq!(result.verdict, Verdict::Wrong);
+        assert_eq!(result.hint, Some(Hint::TooHigh));
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /2025/day/7/answer HTTP/1.1");
+        assert_eq!(body, "level=2&answer=1234");
+    }
+}
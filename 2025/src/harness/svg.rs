@@ -0,0 +1,66 @@
+use image::RgbImage;
+use resvg::tiny_skia::{Color, Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+
+// Rasterize an SVG to exactly width x height, scaled to fit (keeping its aspect ratio) and centred on white
+pub fn rasterize(svg: &str, width: u32, height: u32) -> Result<RgbImage, String> {
+    let tree = Tree::from_str(svg, &Options::default()).map_err(|e| format!("invalid SVG: {e}"))?;
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or_else(|| format!("can't rasterize SVG to {width}x{height}"))?;
+    pixmap.fill(Color::WHITE);
+
+    let size = tree.size();
+    let scale = (width as f32 / size.width()).min(height as f32 / size.height());
+    let dx = (width as f32 - size.width() * scale) / 2.0;
+    let dy = (height as f32 - size.height() * scale) / 2.0;
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale).post_translate(dx, dy),
+        &mut pixmap.as_mut(),
+    );
+
+    // The background is opaque, so premultiplied and straight alpha are the same thing here
+    let rgb = pixmap
+        .data()
+        .chunks_exact(4)
+        .flat_map(|p| [p[0], p[1], p[2]])
+        .collect();
+    Ok(RgbImage::from_raw(width, height, rgb).expect("pixmap size matches image size"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rasterize() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <rect x="0" y="0" width="5" height="10" fill="red"/>
+        </svg>"#;
+
+        // Scaled up 4x, so the red half is 20 pixels wide
+        let image = rasterize(svg, 40, 40).unwrap();
+        assert_eq!(image.dimensions(), (40, 40));
+        assert_eq!(image.get_pixel(5, 20).0, [255, 0, 0]);
+        assert_eq!(image.get_pixel(35, 20).0, [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_rasterize_keeps_aspect_ratio() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">
+            <rect width="10" height="10" fill="blue"/>
+        </svg>"#;
+
+        // A square in a wide frame gets white bars either side
+        let image = rasterize(svg, 40, 20).unwrap();
+        assert_eq!(image.get_pixel(20, 10).0, [0, 0, 255]);
+        assert_eq!(image.get_pixel(2, 10).0, [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_rasterize_malformed() {
+        let err = rasterize("<svg><rect", 10, 10).unwrap_err();
+        assert!(err.starts_with("invalid SVG"), "{err}");
+        assert!(rasterize("<svg/>", 0, 10).is_err());
+    }
+}
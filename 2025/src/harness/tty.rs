@@ -0,0 +1,405 @@
+use std::fmt::Write as _;
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use image::RgbImage;
+
+use super::frames::{Annotations, Sink};
+
+// `render --tty` plays frames in the terminal instead of writing a file
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+const HELP: &str = "space: pause  n: step  +/-: speed  q: quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+    // Ctrl-C, which raw mode turns into a plain byte
+    Interrupt,
+    Other,
+}
+
+impl Key {
+    pub fn from_byte(b: u8) -> Key {
+        match b {
+            b' ' | b'p' => Key::Pause,
+            b'n' | b'.' => Key::Step,
+            b'+' | b'=' => Key::Faster,
+            b'-' | b'_' => Key::Slower,
+            b'q' => Key::Quit,
+            0x03 => Key::Interrupt,
+            _ => Key::Other,
+        }
+    }
+}
+
+// Two pixels per character: the upper half block takes the top pixel as foreground and the bottom as background
+// Frames bigger than `cols` x `rows` characters are sampled down to fit
+pub fn draw(frame: &RgbImage, cols: u32, rows: u32) -> String {
+    let (width, height) = frame.dimensions();
+    let step = width
+        .div_ceil(cols.max(1))
+        .max(height.div_ceil(rows.max(1) * 2))
+        .max(1);
+
+    let mut out = String::new();
+    for y in (0..height).step_by(2 * step as usize) {
+        for x in (0..width).step_by(step as usize) {
+            let [r, g, b] = frame.get_pixel(x, y).0;
+            let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+            match y + step < height {
+                true => {
+                    let [r, g, b] = frame.get_pixel(x, y + step).0;
+                    let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+                }
+                false => out.push_str("\x1b[49m"),
+            }
+            out.push('▀');
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+    out
+}
+
+// Columns and rows of the terminal on stdout
+#[cfg(unix)]
+fn terminal_size() -> Option<(u32, u32)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0).then_some((size.ws_col as u32, size.ws_row as u32))
+}
+
+#[cfg(not(unix))]
+fn terminal_size() -> Option<(u32, u32)> {
+    None
+}
+
+// Keypresses from /dev/tty rather than stdin, which might be the puzzle input
+// Read on a thread of its own, started the first time anything asks; None if there's no terminal
+// Raw mode is termios, so elsewhere there are never any keys and frames just play through
+#[cfg_attr(not(unix), allow(dead_code))]
+struct Keys {
+    #[cfg(unix)]
+    tty: std::fs::File,
+    #[cfg(unix)]
+    original: libc::termios,
+    presses: Mutex<Receiver<u8>>,
+}
+
+#[cfg(unix)]
+fn keys() -> Option<&'static Keys> {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::sync::{OnceLock, mpsc};
+
+    static KEYS: OnceLock<Option<Keys>> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let tty = File::options()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .ok()?;
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(tty.as_raw_fd(), &mut original) } != 0 {
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut reader = tty.try_clone().ok()?;
+        std::thread::spawn(move || {
+            let mut byte = [0];
+            while reader.read_exact(&mut byte).is_ok() && tx.send(byte[0]).is_ok() {}
+        });
+
+        Some(Keys {
+            tty,
+            original,
+            presses: Mutex::new(rx),
+        })
+    })
+    .as_ref()
+}
+
+#[cfg(not(unix))]
+fn keys() -> Option<&'static Keys> {
+    None
+}
+
+impl Keys {
+    // Keys arrive as they're pressed, without echoing, and Ctrl-C comes through as a key
+    #[cfg(unix)]
+    fn raw(&self) {
+        use std::os::fd::AsRawFd;
+
+        let mut raw = self.original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        unsafe { libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &raw) };
+    }
+
+    #[cfg(unix)]
+    fn restore(&self) {
+        use std::os::fd::AsRawFd;
+
+        unsafe { libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.original) };
+    }
+
+    #[cfg(not(unix))]
+    fn raw(&self) {}
+
+    #[cfg(not(unix))]
+    fn restore(&self) {}
+
+    fn next(&self, timeout: Option<Duration>) -> Option<Key> {
+        let presses = self.presses.lock().unwrap();
+        let byte = match timeout {
+            Some(timeout) => match presses.recv_timeout(timeout) {
+                Ok(b) => b,
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+            },
+            None => presses.recv().ok()?,
+        };
+        Some(Key::from_byte(byte))
+    }
+}
+
+// Plays frames in the alternate screen, one every 1/fps seconds unless paused
+// Usable on its own (to watch a grid while debugging, say), not just from `render --tty`
+pub struct Viewer {
+    active: bool,
+    base_delay: Duration,
+    delay: Duration,
+    paused: bool,
+    quit: bool,
+    frame: usize,
+    label: Option<String>,
+    keys: Option<&'static Keys>,
+}
+
+impl Viewer {
+    pub fn new(fps: u32) -> Viewer {
+        let keys = keys();
+        if let Some(keys) = keys {
+            keys.raw();
+            // Anything typed while nothing was watching
+            while keys.next(Some(Duration::ZERO)).is_some() {}
+        }
+        print!("\x1b[?1049h\x1b[?25l");
+        let _ = std::io::stdout().flush();
+
+        let delay = Duration::from_secs(1) / fps.max(1);
+        Viewer {
+            active: true,
+            base_delay: delay,
+            delay,
+            paused: false,
+            quit: false,
+            frame: 0,
+            label: None,
+            keys,
+        }
+    }
+
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    fn status(&self) -> String {
+        let speed = self.base_delay.as_secs_f64() / self.delay.as_secs_f64();
+        format!(
+            "\x1b[2Kframe {}{}  {speed}x{}  {HELP}",
+            self.frame,
+            self.label
+                .as_ref()
+                .map_or(String::new(), |l| format!(": {l}")),
+            if self.paused { "  paused" } else { "" }
+        )
+    }
+
+    fn draw(&self, frame: &RgbImage) {
+        let (cols, rows) = terminal_size().unwrap_or((80, 24));
+        let mut stdout = std::io::stdout().lock();
+        let _ = write!(
+            stdout,
+            "\x1b[H{}{}",
+            draw(frame, cols, rows.saturating_sub(1)),
+            self.status()
+        );
+        let _ = stdout.flush();
+    }
+
+    fn redraw_status(&self) {
+        print!("\r{}", self.status());
+        let _ = std::io::stdout().flush();
+    }
+
+    // Draw the frame, then wait until it's time for the next one (or the user steps to it)
+    pub fn show(&mut self, frame: &RgbImage, label: Option<&str>) {
+        if self.quit {
+            return;
+        }
+        self.frame += 1;
+        self.label = label.map(|l| l.to_string());
+        self.draw(frame);
+
+        let Some(keys) = self.keys else {
+            std::thread::sleep(self.delay);
+            return;
+        };
+
+        let mut deadline = Instant::now() + self.delay;
+        loop {
+            let timeout = match self.paused {
+                true => None,
+                false => Some(deadline.saturating_duration_since(Instant::now())),
+            };
+            let Some(key) = keys.next(timeout) else {
+                return;
+            };
+
+            match key {
+                Key::Pause => self.paused = !self.paused,
+                Key::Step if self.paused => return,
+                Key::Faster => self.delay = (self.delay / 2).max(Duration::from_millis(1)),
+                Key::Slower => self.delay = (self.delay * 2).min(Duration::from_secs(10)),
+                Key::Quit => {
+                    self.quit = true;
+                    return;
+                }
+                Key::Interrupt => {
+                    self.close();
+                    std::process::exit(130);
+                }
+                Key::Step | Key::Other => {}
+            }
+            if !self.paused {
+                deadline = deadline.min(Instant::now() + self.delay);
+            }
+            self.redraw_status();
+        }
+    }
+
+    // Leave the last frame up until a key is pressed, otherwise it vanishes with the alternate screen
+    pub fn hold(&mut self) {
+        if let (Some(keys), false) = (self.keys, self.quit) {
+            print!("\r\x1b[2Kframe {}  done, press any key", self.frame);
+            let _ = std::io::stdout().flush();
+            if keys.next(None) == Some(Key::Interrupt) {
+                self.close();
+                std::process::exit(130);
+            }
+        }
+    }
+
+    // Back to the normal screen and cooked terminal, safe to call more than once
+    pub fn close(&mut self) {
+        if !std::mem::replace(&mut self.active, false) {
+            return;
+        }
+        print!("\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+        if let Some(keys) = self.keys {
+            keys.restore();
+        }
+    }
+}
+
+impl Drop for Viewer {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+// `render --tty`: show frames as they're drawn instead of encoding them
+pub struct TtySink {
+    viewer: Viewer,
+}
+
+impl TtySink {
+    pub fn new(fps: u32) -> TtySink {
+        TtySink {
+            viewer: Viewer::new(fps),
+        }
+    }
+}
+
+impl Sink for TtySink {
+    // Overlays are left out, the terminal is too coarse for text to survive
+    fn push(&mut self, frame: RgbImage, annotations: Annotations) -> Result<(), String> {
+        self.viewer.show(&frame, annotations.label.as_deref());
+        Ok(())
+    }
+
+    fn accepting(&self) -> bool {
+        !self.viewer.quit()
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), String> {
+        self.viewer.hold();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_draw() {
+        let frame = RgbImage::from_fn(2, 3, |x, y| image::Rgb([x as u8, y as u8, 0]));
+        let lines = draw(&frame, 80, 24)
+            .split("\r\n")
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>();
+
+        // Three pixel rows fit in two lines, the last with only a top half
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "\x1b[38;2;0;0;0m\x1b[48;2;0;1;0m▀\x1b[38;2;1;0;0m\x1b[48;2;1;1;0m▀\x1b[0m"
+        );
+        assert_eq!(
+            lines[1],
+            "\x1b[38;2;0;2;0m\x1b[49m▀\x1b[38;2;1;2;0m\x1b[49m▀\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_draw_fits_terminal() {
+        let frame = RgbImage::new(100, 100);
+        let drawn = draw(&frame, 10, 50);
+        let lines = drawn
+            .split("\r\n")
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|l| l.matches('▀').count() == 10));
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(Key::from_byte(b' '), Key::Pause);
+        assert_eq!(Key::from_byte(b'n'), Key::Step);
+        assert_eq!(Key::from_byte(b'+'), Key::Faster);
+        assert_eq!(Key::from_byte(b'-'), Key::Slower);
+        assert_eq!(Key::from_byte(b'q'), Key::Quit);
+        assert_eq!(Key::from_byte(3), Key::Interrupt);
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::PanicHookInfo;
+use std::sync::{Mutex, MutexGuard};
+
+pub type Solver = (&'static str, fn(&str) -> String);
+
+// part1_regex -> part1, part2 -> part2
+pub fn part_prefix(name: &str) -> &str {
+    name.split('_').next().unwrap_or(name)
+}
+
+// The part a variant is registered for (#[aoc::register(part = 2)]), or else the one in its name
+pub fn part_name(name: &'static str, part: Option<u32>) -> &'static str {
+    match part {
+        Some(1) => "part1",
+        Some(2) => "part2",
+        _ => part_prefix(name),
+    }
+}
+
+// Group variants by the part they solve, keeping the order they were given in
+pub fn group_by_part(solvers: &[(Solver, Option<u32>)]) -> BTreeMap<&'static str, Vec<Solver>> {
+    let mut groups: BTreeMap<&'static str, Vec<Solver>> = BTreeMap::new();
+    for &((name, func), part) in solvers {
+        groups
+            .entry(part_name(name, part))
+            .or_default()
+            .push((name, func));
+    }
+    groups
+}
+
+// Run a solver, turning a panic into an error so one bad variant doesn't stop the rest
+pub fn run_caught(func: fn(&str) -> String, input: &str) -> Result<String, String> {
+    std::panic::catch_unwind(|| func(input)).map_err(|e| {
+        e.downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub part: String,
+    pub reference: (String, Result<String, String>),
+    pub others: Vec<(String, Result<String, String>)>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |r: &Result<String, String>| match r {
+            Ok(s) => s.clone(),
+            Err(e) => format!("<panic: {e}>"),
+        };
+
+        let (ref_name, ref_out) = &self.reference;
+        writeln!(f, "{}: variants disagree with {}", self.part, ref_name)?;
+        for (name, out) in &self.others {
+            writeln!(f, "--- {ref_name}")?;
+            writeln!(f, "+++ {name}")?;
+            write!(f, "{}", diff(&show(ref_out), &show(out)))?;
+        }
+        Ok(())
+    }
+}
+
+// Run every variant in a group on the same input
+// The reference is the variant named exactly like the part (part1) if there is one, otherwise the first
+// With ignore_panics, variants that panic are left out (useful for fuzzed inputs that may not parse)
+pub fn check_group(
+    part: &str,
+    solvers: &[Solver],
+    input: &str,
+    ignore_panics: bool,
+) -> Option<Disagreement> {
+    let outputs = solvers
+        .iter()
+        .map(|&(name, func)| (name.to_string(), run_caught(func, input)))
+        .filter(|(_, out)| !ignore_panics || out.is_ok())
+        .collect::<Vec<_>>();
+    if outputs.is_empty() {
+        return None;
+    }
+
+    let reference_index = outputs.iter().position(|(n, _)| n == part).unwrap_or(0);
+
+    let reference = outputs[reference_index].clone();
+    let others = outputs
+        .into_iter()
+        .enumerate()
+        .filter(|(i, (_, out))| *i != reference_index && *out != reference.1)
+        .map(|(_, o)| o)
+        .collect::<Vec<_>>();
+
+    if others.is_empty() {
+        None
+    } else {
+        Some(Disagreement {
+            part: part.to_string(),
+            reference,
+            others,
+        })
+    }
+}
+
+// Silences panics until dropped, then puts back whatever hook was there, however fuzz ends
+// The hook is global, so the lock keeps two fuzz runs (parallel tests, say) from restoring each other's silent hook
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+struct QuietPanics {
+    hook: Option<PanicHook>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl QuietPanics {
+    fn new() -> QuietPanics {
+        static LOCK: Mutex<()> = Mutex::new(());
+        let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        QuietPanics {
+            hook: Some(hook),
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        if let Some(hook) = self.hook.take() {
+            std::panic::set_hook(hook);
+        }
+    }
+}
+
+// Run each group on `iterations` mutated copies of the input
+// Returns the first disagreement found for each group along with the input that caused it
+pub fn fuzz(
+    groups: &BTreeMap<&'static str, Vec<Solver>>,
+    input: &str,
+    iterations: usize,
+    seed: u64,
+) -> Vec<(String, Disagreement)> {
+    let mut rng = Rng::new(seed);
+    let mut found: BTreeMap<&str, (String, Disagreement)> = BTreeMap::new();
+
+    // Mutated inputs will often make solutions panic; don't spam the terminal with it
+    let _quiet = QuietPanics::new();
+
+    for _ in 0..iterations {
+        let mutated = mutate(input, &mut rng);
+        for (part, solvers) in groups {
+            if solvers.len() < 2 || found.contains_key(part) {
+                continue;
+            }
+            if let Some(d) = check_group(part, solvers, &mutated, true) {
+                found.insert(part, (mutated.clone(), d));
+            }
+        }
+    }
+
+    found.into_values().collect()
+}
+
+// A minimal line diff: unchanged lines are indented, changed lines are marked with - and +
+pub fn diff(a: &str, b: &str) -> String {
+    let a = a.lines().collect::<Vec<_>>();
+    let b = b.lines().collect::<Vec<_>>();
+
+    let mut out = String::new();
+    for i in 0..a.len().max(b.len()) {
+        match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) if x == y => out.push_str(&format!(" {x}\n")),
+            (x, y) => {
+                if let Some(x) = x {
+                    out.push_str(&format!("-{x}\n"));
+                }
+                if let Some(y) = y {
+                    out.push_str(&format!("+{y}\n"));
+                }
+            }
+        }
+    }
+    out
+}
+
+// xorshift64*, good enough to shuffle inputs around without pulling in rand
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+// Make a slightly different input that (usually) keeps the original's format:
+// change a digit, or drop, duplicate, or swap lines
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut lines = input.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+    if lines.is_empty() {
+        return input.to_string();
+    }
+
+    let mutations = 1 + rng.below(3);
+    for _ in 0..mutations {
+        match rng.below(4) {
+            0 => {
+                let digits = lines
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(y, l)| {
+                        l.char_indices()
+                            .filter(|(_, c)| c.is_ascii_digit())
+                            .map(move |(x, _)| (y, x))
+                    })
+                    .collect::<Vec<_>>();
+                if digits.is_empty() {
+                    continue;
+                }
+                let (y, x) = digits[rng.below(digits.len())];
+                let d = (b'0' + rng.below(10) as u8) as char;
+                lines[y].replace_range(x..x + 1, &d.to_string());
+            }
+            1 if lines.len() > 1 => {
+                lines.remove(rng.below(lines.len()));
+            }
+            2 => {
+                let i = rng.below(lines.len());
+                lines.insert(i, lines[i].clone());
+            }
+            _ => {
+                let i = rng.below(lines.len());
+                let j = rng.below(lines.len());
+                lines.swap(i, j);
+            }
+        }
+    }
+
+    let mut out = lines.join("\n");
+    if input.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn count(input: &str) -> String {
+        input.lines().count().to_string()
+    }
+
+    fn count_plus_one(input: &str) -> String {
+        (input.lines().count() + 1).to_string()
+    }
+
+    fn explode(_: &str) -> String {
+        panic!("boom")
+    }
+
+    #[test]
+    fn test_group_by_part() {
+        let solvers: Vec<(Solver, Option<u32>)> = vec![
+            (("part1", count), None),
+            (("part1_regex", count), None),
+            (("part2", count), None),
+            (("flood", count), Some(2)),
+        ];
+        let groups = group_by_part(&solvers);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["part1"].len(), 2);
+        assert_eq!(groups["part2"].len(), 2);
+    }
+
+    #[test]
+    fn test_check_group() {
+        let agree: Vec<Solver> = vec![("part1_fast", count), ("part1", count)];
+        assert!(check_group("part1", &agree, "a\nb\n", false).is_none());
+
+        let disagree: Vec<Solver> = vec![
+            ("part1_fast", count_plus_one),
+            ("part1", count),
+            ("part1_broken", explode),
+        ];
+        let d = check_group("part1", &disagree, "a\nb\n", false).unwrap();
+        assert_eq!(d.reference.0, "part1");
+        assert_eq!(d.others.len(), 2);
+        assert_eq!(d.others[1].1, Err("boom".to_string()));
+        assert!(d.to_string().contains("-2\n+3\n"));
+
+        let d = check_group("part1", &disagree, "a\nb\n", true).unwrap();
+        assert_eq!(d.others.len(), 1);
+    }
+
+    #[test]
+    fn test_fuzz() {
+        let solvers: Vec<(Solver, Option<u32>)> = vec![
+            (("part1", count), None),
+            (("part1_plus", count_plus_one), None),
+        ];
+        let groups = group_by_part(&solvers);
+        let found = fuzz(&groups, "1\n2\n", 3, 7);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1.part, "part1");
+    }
+
+    #[test]
+    fn test_mutate_is_deterministic() {
+        let input = "123\n456\n789\n";
+        let a = mutate(input, &mut Rng::new(42));
+        let b = mutate(input, &mut Rng::new(42));
+        assert_eq!(a, b);
+        assert!(a.ends_with('\n'));
+    }
+}
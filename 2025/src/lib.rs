@@ -1,5 +1,15 @@
 #![feature(impl_trait_in_assoc_type)]
 pub mod grid;
+pub mod harness;
+
+// Text and shapes for render_frame!(.., overlay = ..)
+pub use overlay;
+
+// Colormaps and palettes, closures passed to render_frame! and render_image! can return their [u8; 3]s directly
+pub use colormap;
+
+// The grid backends (dense, sparse, bitset) behind grid::Grid, for when a solution wants one directly
+pub use grids;
 
 pub mod line2d;
 pub mod point2d;
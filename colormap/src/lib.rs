@@ -0,0 +1,261 @@
+// Colours for renders: perceptual colormaps for numbers, a categorical palette for kinds of tile,
+// and a scale that maps a grid's values onto a colormap
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub type Color = [u8; 3];
+
+// Anything a render closure might hand back, so render_frame! and friends take either
+pub trait IntoRgb {
+    fn into_rgb(self) -> Color;
+}
+
+impl IntoRgb for Color {
+    fn into_rgb(self) -> Color {
+        self
+    }
+}
+
+impl IntoRgb for (u8, u8, u8) {
+    fn into_rgb(self) -> Color {
+        [self.0, self.1, self.2]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Colormap {
+    // Dark blue through green to yellow, the safe default
+    #[default]
+    Viridis,
+    // Black through purple and orange to pale yellow, good for heat and distances
+    Magma,
+    // A rainbow without the muddy bits, for when adjacent values need telling apart
+    Turbo,
+}
+
+impl Colormap {
+    // `t` runs from 0 to 1, anything outside is clamped
+    pub fn color(self, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let rgb = match self {
+            Colormap::Viridis => polynomial(&VIRIDIS, t),
+            Colormap::Magma => polynomial(&MAGMA, t),
+            Colormap::Turbo => polynomial(&TURBO, t),
+        };
+        rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    pub fn reversed(self) -> Reversed {
+        Reversed(self)
+    }
+}
+
+// High values at the dark end instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reversed(pub Colormap);
+
+impl Reversed {
+    pub fn color(self, t: f64) -> Color {
+        self.0.color(1.0 - t)
+    }
+}
+
+// Polynomial fits to the real maps, close enough that nobody can tell and no lookup tables to carry around
+// Viridis and magma are Matt Zucker's (shadertoy WlfXRN), turbo is Google's own approximation (a bit darker at the ends)
+type Coefficients = [[f64; 3]];
+
+const VIRIDIS: [[f64; 3]; 7] = [
+    [0.2777273272234177, 0.005407344544966578, 0.3340998053353061],
+    [0.1050930431085774, 1.404613529898575, 1.384590162594685],
+    [-0.3308618287255563, 0.214847559468213, 0.09509516302823659],
+    [-4.634230498983486, -5.799100973351585, -19.33244095627987],
+    [6.228269936347081, 14.17993336680509, 56.69055260068105],
+    [4.776384997670288, -13.74514537774601, -65.35303263337234],
+    [-5.435455855934631, 4.645852612178535, 26.3124352495832],
+];
+
+const MAGMA: [[f64; 3]; 7] = [
+    [
+        -0.002136485053939582,
+        -0.000749655052795221,
+        -0.005386127855323933,
+    ],
+    [0.2516605407371642, 0.6775232436837668, 2.494026599312351],
+    [8.353717279216625, -3.577719514958484, 0.3144679030132573],
+    [-27.66873308576866, 14.26473078096533, -13.64921318813922],
+    [52.17613981234068, -27.94360607168351, 12.94416944238394],
+    [-50.76852536473588, 29.04658282127291, 4.23415299384598],
+    [18.65570506591883, -11.48977351997711, -5.601961508734096],
+];
+
+const TURBO: [[f64; 3]; 6] = [
+    [0.13572138, 0.09140261, 0.10667330],
+    [4.61539260, 2.19418839, 12.64194608],
+    [-42.66032258, 4.84296658, -60.58204836],
+    [132.13108234, -14.18503333, 110.36276771],
+    [-152.94239396, 4.27729857, -89.90310912],
+    [59.28637943, 2.82956604, 27.34824973],
+];
+
+fn polynomial(coefficients: &Coefficients, t: f64) -> [f64; 3] {
+    // Horner, highest power first
+    coefficients.iter().rev().fold([0.0; 3], |acc, c| {
+        [acc[0] * t + c[0], acc[1] * t + c[1], acc[2] * t + c[2]]
+    })
+}
+
+// Tableau 10: distinct from each other, and none of them shouts
+pub const CATEGORICAL: [Color; 10] = [
+    [0x4e, 0x79, 0xa7],
+    [0xf2, 0x8e, 0x2b],
+    [0xe1, 0x57, 0x59],
+    [0x76, 0xb7, 0xb2],
+    [0x59, 0xa1, 0x4f],
+    [0xed, 0xc9, 0x48],
+    [0xb0, 0x7a, 0xa1],
+    [0xff, 0x9d, 0xa7],
+    [0x9c, 0x75, 0x5f],
+    [0xba, 0xb0, 0xac],
+];
+
+// Wraps around after ten, so e.g. an enum's discriminant or a region id can go straight in
+pub fn categorical(i: usize) -> Color {
+    CATEGORICAL[i % CATEGORICAL.len()]
+}
+
+// Hands out categorical colours in the order things are first seen, for tiles that don't have a handy index
+// The same value always gets the same colour, so it can be shared across frames
+#[derive(Debug, Clone)]
+pub struct Categories<T> {
+    seen: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Categories<T> {
+    fn default() -> Self {
+        Categories {
+            seen: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> Categories<T> {
+    pub fn new() -> Categories<T> {
+        Categories::default()
+    }
+
+    pub fn color(&mut self, value: T) -> Color {
+        let next = self.seen.len();
+        categorical(*self.seen.entry(value).or_insert(next))
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+// Maps values in min..=max onto a colormap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    pub min: f64,
+    pub max: f64,
+    pub colormap: Colormap,
+}
+
+impl Scale {
+    pub fn new(min: f64, max: f64, colormap: Colormap) -> Scale {
+        Scale { min, max, colormap }
+    }
+
+    // Range over whatever values turn up, e.g. every cell of a grid
+    // Infinities and NaNs (unreachable cells, say) are left out of the range
+    pub fn fit(values: impl IntoIterator<Item = f64>, colormap: Colormap) -> Scale {
+        let (min, max) = values
+            .into_iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        match min <= max {
+            true => Scale::new(min, max, colormap),
+            false => Scale::new(0.0, 0.0, colormap),
+        }
+    }
+
+    // Where `value` falls between min and max, 0 to 1
+    pub fn position(&self, value: f64) -> f64 {
+        match self.max > self.min {
+            true => ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0),
+            // Everything's the same, so put it in the middle rather than at an extreme
+            false => 0.5,
+        }
+    }
+
+    pub fn color(&self, value: f64) -> Color {
+        self.colormap.color(self.position(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: Color, b: Color) -> bool {
+        a.iter()
+            .zip(b)
+            .all(|(&a, b)| (a as i32 - b as i32).abs() <= 6)
+    }
+
+    #[test]
+    fn test_colormaps() {
+        // The ends of the real maps
+        assert!(close(Colormap::Viridis.color(0.0), [68, 1, 84]));
+        assert!(close(Colormap::Viridis.color(1.0), [253, 231, 37]));
+        assert!(close(Colormap::Magma.color(0.0), [0, 0, 4]));
+        assert!(close(Colormap::Magma.color(1.0), [252, 253, 191]));
+        // Google's turbo fit drifts a little at the ends, but the middle is right
+        assert!(close(Colormap::Turbo.color(0.5), [150, 250, 80]));
+
+        // Clamped rather than extrapolated
+        assert_eq!(Colormap::Viridis.color(-1.0), Colormap::Viridis.color(0.0));
+        assert_eq!(Colormap::Viridis.color(2.0), Colormap::Viridis.color(1.0));
+        assert_eq!(
+            Colormap::Magma.reversed().color(0.0),
+            Colormap::Magma.color(1.0)
+        );
+    }
+
+    #[test]
+    fn test_categories() {
+        let mut categories = Categories::new();
+        let a = categories.color('#');
+        let b = categories.color('.');
+        assert_ne!(a, b);
+        assert_eq!(categories.color('#'), a);
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categorical(10), categorical(0));
+    }
+
+    #[test]
+    fn test_scale() {
+        let scale = Scale::fit([3.0, f64::INFINITY, 7.0, 5.0], Colormap::Viridis);
+        assert_eq!((scale.min, scale.max), (3.0, 7.0));
+        assert_eq!(scale.position(5.0), 0.5);
+        assert_eq!(scale.color(3.0), Colormap::Viridis.color(0.0));
+        assert_eq!(scale.color(100.0), Colormap::Viridis.color(1.0));
+
+        // All the same value
+        let flat = Scale::fit([2.0, 2.0], Colormap::Viridis);
+        assert_eq!(flat.color(2.0), Colormap::Viridis.color(0.5));
+    }
+
+    #[test]
+    fn test_into_rgb() {
+        assert_eq!((1, 2, 3).into_rgb(), [1, 2, 3]);
+        assert_eq!([4, 5, 6].into_rgb(), [4, 5, 6]);
+    }
+}
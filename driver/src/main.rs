@@ -0,0 +1,255 @@
+// One driver for every year with a Rust workspace (2022-2025)
+//
+// aoc list --year 2024
+// aoc run --year 2023
+// aoc run --year 2024 --part 2
+// aoc run --day 5 --variant brute
+// aoc new --year 2025 --day 12
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand};
+
+mod scaffold;
+mod solution;
+mod years;
+
+use solution::{Filter, Outcome, Solution};
+
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    /// Root of the repository (default: search upwards from the current directory)
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// List every solution that matches the filters
+    List(FilterArgs),
+    /// Run every solution that matches the filters and print a table of answers and timings
+    Run(FilterArgs),
+    /// Start a new day from the year's template
+    New(NewArgs),
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[arg(long)]
+    year: u32,
+    #[arg(long)]
+    day: u32,
+    /// Binary name (2022 only, NN-name)
+    #[arg(long)]
+    name: Option<String>,
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    #[arg(long)]
+    year: Option<u32>,
+    #[arg(long)]
+    day: Option<u32>,
+    #[arg(long)]
+    part: Option<u32>,
+    /// Only variants containing this string (use '-' for just the main solution of each part)
+    #[arg(long)]
+    variant: Option<String>,
+    /// Include solutions marked slow
+    #[arg(long)]
+    slow: bool,
+}
+
+impl From<FilterArgs> for Filter {
+    fn from(args: FilterArgs) -> Filter {
+        Filter {
+            year: args.year,
+            day: args.day,
+            part: args.part,
+            variant: args.variant,
+            slow: args.slow,
+        }
+    }
+}
+
+fn find_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .find(|p| p.join("2025").join("Cargo.toml").exists() && p.join("2022").exists())
+        .map(Path::to_path_buf)
+}
+
+fn format_duration(d: Option<Duration>) -> String {
+    d.map_or("-".to_string(), |d| format!("{d:.2?}"))
+}
+
+fn format_answer(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(a) => {
+            let mut lines = a.lines();
+            let first = lines.next().unwrap_or("").to_string();
+            match lines.count() {
+                0 => first,
+                n => format!("{first} (+{n} lines)"),
+            }
+        }
+        Err(e) => format!("ERROR: {e}"),
+    }
+}
+
+fn print_table(rows: &[Vec<String>]) {
+    let widths = (0..rows[0].len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    for (i, row) in rows.iter().enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+        if i == 0 {
+            println!(
+                "{}",
+                widths
+                    .iter()
+                    .map(|w| "-".repeat(*w))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+            );
+        }
+    }
+}
+
+fn solution_columns(s: &Solution) -> Vec<String> {
+    vec![
+        s.year.to_string(),
+        s.day.to_string(),
+        s.part.to_string(),
+        s.variant_name().to_string(),
+    ]
+}
+
+fn new_day(years: &[Box<dyn years::Year>], args: &NewArgs) {
+    if !(1..=25).contains(&args.day) {
+        eprintln!("--day must be between 1 and 25");
+        std::process::exit(2);
+    }
+    let Some(year) = years.iter().find(|y| y.year() == args.year) else {
+        eprintln!("No workspace for {}", args.year);
+        std::process::exit(2);
+    };
+
+    let changes = year
+        .scaffold(args.day, args.name.as_deref())
+        .and_then(|changes| scaffold::apply(&changes).map(|_| changes));
+    match changes {
+        Ok(changes) => {
+            for change in changes {
+                match change {
+                    scaffold::Change::Create(path, _) => println!("created {}", path.display()),
+                    scaffold::Change::Edit(path, _) => println!("updated {}", path.display()),
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let root = match cli.root.or_else(find_root) {
+        Some(root) => root,
+        None => {
+            eprintln!("Cannot find the repository root, use --root");
+            std::process::exit(2);
+        }
+    };
+
+    let years = years::all(&root);
+
+    let (filter, run): (Filter, bool) = match cli.command {
+        Commands::List(args) => (args.into(), false),
+        Commands::Run(args) => (args.into(), true),
+        Commands::New(args) => {
+            new_day(&years, &args);
+            return;
+        }
+    };
+    let selected = years
+        .iter()
+        .filter(|y| filter.year.is_none_or(|year| year == y.year()))
+        .map(|y| {
+            let solutions = y
+                .discover()
+                .into_iter()
+                .filter(|s| filter.matches(s))
+                .collect::<Vec<_>>();
+            (y, solutions)
+        })
+        .filter(|(_, solutions)| !solutions.is_empty())
+        .collect::<Vec<_>>();
+
+    if selected.is_empty() {
+        eprintln!("No solutions match");
+        std::process::exit(3);
+    }
+
+    if !run {
+        let mut rows = vec![vec!["Year", "Day", "Part", "Variant"]
+            .into_iter()
+            .map(String::from)
+            .collect()];
+        for (_, solutions) in &selected {
+            rows.extend(solutions.iter().map(solution_columns));
+        }
+        print_table(&rows);
+        return;
+    }
+
+    let mut outcomes: Vec<Outcome> = vec![];
+    for (year, solutions) in &selected {
+        eprintln!(
+            "Running {} solution(s) for {}",
+            solutions.len(),
+            year.year()
+        );
+        outcomes.extend(year.run(solutions));
+    }
+
+    let mut rows = vec![vec!["Year", "Day", "Part", "Variant", "Answer", "Time"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>()];
+    for o in &outcomes {
+        let mut row = solution_columns(&o.solution);
+        row.push(format_answer(&o.answer));
+        row.push(format_duration(o.elapsed));
+        rows.push(row);
+    }
+    print_table(&rows);
+
+    let total = outcomes.iter().filter_map(|o| o.elapsed).sum::<Duration>();
+    let failures = outcomes.iter().filter(|o| o.answer.is_err()).count();
+    println!();
+    println!(
+        "{} solution(s), {} failed, {} total",
+        outcomes.len(),
+        failures,
+        format_duration(Some(total))
+    );
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
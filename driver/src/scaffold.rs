@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+// One step of setting up a new day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    // A new file, never overwrites anything
+    Create(PathBuf, String),
+    // Replace an existing file's contents (lib.rs gaining a `pub mod`)
+    Edit(PathBuf, String),
+}
+
+impl Change {
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Create(path, _) | Change::Edit(path, _) => path,
+        }
+    }
+}
+
+// Check everything first so a half-created day is never left behind because one file already existed
+pub fn apply(changes: &[Change]) -> Result<(), String> {
+    let existing = changes
+        .iter()
+        .filter(|c| matches!(c, Change::Create(..)) && c.path().exists())
+        .map(|c| c.path().display().to_string())
+        .collect::<Vec<_>>();
+    if !existing.is_empty() {
+        return Err(format!("refusing to overwrite {}", existing.join(", ")));
+    }
+
+    // Directories first too, they're the likeliest thing to fail (a dangling input symlink, say)
+    for parent in changes.iter().filter_map(|c| c.path().parent()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+
+    for change in changes {
+        let (path, contents) = match change {
+            Change::Create(path, contents) | Change::Edit(path, contents) => (path, contents),
+        };
+        std::fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+pub fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// Every file under `template`, as it would be under `dest` with `placeholder` replaced
+pub fn copy_dir(
+    template: &Path,
+    dest: &Path,
+    placeholder: &str,
+    value: &str,
+) -> Result<Vec<Change>, String> {
+    let mut changes = vec![];
+    let entries =
+        std::fs::read_dir(template).map_err(|e| format!("{}: {e}", template.display()))?;
+    let mut paths = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+        let target = dest.join(path.file_name().expect("read_dir entries have names"));
+        if path.is_dir() {
+            changes.extend(copy_dir(&path, &target, placeholder, value)?);
+        } else {
+            changes.push(Change::Create(
+                target,
+                read(&path)?.replace(placeholder, value),
+            ));
+        }
+    }
+    Ok(changes)
+}
+
+// Add `pub mod <name>;` to a lib.rs, keeping the existing (alphabetical) order of the `pub mod` lines
+pub fn insert_mod(lib: &str, name: &str) -> Option<String> {
+    let line = format!("pub mod {name};");
+    let lines = lib.lines().collect::<Vec<_>>();
+    if lines.contains(&line.as_str()) {
+        return None;
+    }
+
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod "))
+        .collect::<Vec<_>>();
+    let index = mods
+        .iter()
+        .find(|(_, l)| **l > line.as_str())
+        .map(|(i, _)| *i)
+        .or_else(|| mods.last().map(|(i, _)| i + 1))
+        .unwrap_or(0);
+
+    let mut out = lines.clone();
+    out.insert(index, &line);
+    let mut out = out.join("\n");
+    if lib.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert_mod() {
+        let lib = "pub mod day1;\npub mod day10;\npub mod day3;\n\nmod grid;\n";
+        assert_eq!(
+            insert_mod(lib, "day2").unwrap(),
+            "pub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\n\nmod grid;\n"
+        );
+        assert_eq!(
+            insert_mod(lib, "day4").unwrap(),
+            "pub mod day1;\npub mod day10;\npub mod day3;\npub mod day4;\n\nmod grid;\n"
+        );
+        assert_eq!(insert_mod(lib, "day3"), None);
+    }
+
+    #[test]
+    fn test_apply_refuses_to_overwrite() {
+        let dir = std::env::temp_dir().join(format!("aoc-driver-scaffold-{}", std::process::id()));
+        let existing = dir.join("existing.txt");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&existing, "work in progress").unwrap();
+
+        let changes = vec![
+            Change::Create(dir.join("new.txt"), String::new()),
+            Change::Create(existing.clone(), String::new()),
+        ];
+        assert!(apply(&changes).is_err());
+        assert!(!dir.join("new.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(&existing).unwrap(),
+            "work in progress"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+// One runnable solution, in any year
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    // Empty for the main solution of a part
+    pub variant: String,
+    // How the year's adapter finds it again (binary name, function name, etc)
+    pub target: String,
+    // Marked as taking ages (2025's #[aoc::register(slow)]), left out unless asked for
+    pub slow: bool,
+}
+
+impl Solution {
+    pub fn variant_name(&self) -> &str {
+        if self.variant.is_empty() {
+            "-"
+        } else {
+            &self.variant
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    // Substring match; `-` only matches the main solution of each part
+    pub variant: Option<String>,
+    // Include slow solutions
+    pub slow: bool,
+}
+
+impl Filter {
+    pub fn matches(&self, s: &Solution) -> bool {
+        self.year.is_none_or(|y| y == s.year)
+            && self.day.is_none_or(|d| d == s.day)
+            && self.part.is_none_or(|p| p == s.part)
+            && self.variant.as_deref().is_none_or(|v| match v {
+                "-" => s.variant.is_empty(),
+                v => s.variant.contains(v),
+            })
+            && (self.slow || !s.slow)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub solution: Solution,
+    pub answer: Result<String, String>,
+    pub elapsed: Option<Duration>,
+}
+
+// Run a command to completion (optionally feeding a file to stdin) and time it
+pub fn run_timed(mut cmd: Command, stdin: Option<&Path>) -> (Result<String, String>, Duration) {
+    match stdin {
+        Some(path) => match std::fs::File::open(path) {
+            Ok(f) => {
+                cmd.stdin(f);
+            }
+            Err(e) => return (Err(format!("{}: {e}", path.display())), Duration::ZERO),
+        },
+        None => {
+            cmd.stdin(Stdio::null());
+        }
+    }
+
+    let start = Instant::now();
+    let output = cmd.output();
+    let elapsed = start.elapsed();
+
+    let result = match output {
+        Ok(o) if o.status.success() => Ok(String::from_utf8_lossy(&o.stdout).to_string()),
+        Ok(o) => Err(last_line(&String::from_utf8_lossy(&o.stderr))
+            .unwrap_or_else(|| format!("exited with {}", o.status))),
+        Err(e) => Err(e.to_string()),
+    };
+    (result, elapsed)
+}
+
+// Build a cargo target in release mode, returning the error output if it failed
+pub fn cargo_build(dir: &Path, args: &[&str]) -> Result<(), String> {
+    let output = Command::new("cargo")
+        .current_dir(dir)
+        .args(["build", "--release", "--quiet"])
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(last_line(&String::from_utf8_lossy(&output.stderr))
+            .unwrap_or_else(|| "build failed".to_string()))
+    }
+}
+
+fn last_line(s: &str) -> Option<String> {
+    s.lines()
+        .map(|l| l.trim())
+        .rfind(|l| !l.is_empty())
+        .map(|l| l.to_string())
+}
+
+// Parse a Debug formatted Duration: 150ns, 12.3µs, 1.5ms, 2.000001s
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (value, scale) = if let Some(v) = s.strip_suffix("ns") {
+        (v, 1e-9)
+    } else if let Some(v) = s.strip_suffix("µs").or_else(|| s.strip_suffix("us")) {
+        (v, 1e-6)
+    } else if let Some(v) = s.strip_suffix("ms") {
+        (v, 1e-3)
+    } else {
+        (s.strip_suffix('s')?, 1.0)
+    };
+
+    value
+        .parse::<f64>()
+        .ok()
+        .map(|v| Duration::from_secs_f64(v * scale))
+}
+
+// Numbers from a name, day1 -> 1, 07-foo -> 7
+pub fn leading_number(s: &str) -> Option<u32> {
+    s.chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solution(year: u32, day: u32, part: u32, variant: &str) -> Solution {
+        Solution {
+            year,
+            day,
+            part,
+            variant: variant.to_string(),
+            target: String::new(),
+            slow: false,
+        }
+    }
+
+    #[test]
+    fn test_filter() {
+        let main = solution(2023, 5, 2, "");
+        let brute = solution(2023, 5, 2, "brute-par");
+
+        let f = Filter {
+            year: Some(2023),
+            part: Some(2),
+            ..Filter::default()
+        };
+        assert!(f.matches(&main) && f.matches(&brute));
+
+        let f = Filter {
+            variant: Some("brute".to_string()),
+            ..Filter::default()
+        };
+        assert!(!f.matches(&main) && f.matches(&brute));
+
+        let f = Filter {
+            variant: Some("-".to_string()),
+            ..Filter::default()
+        };
+        assert!(f.matches(&main) && !f.matches(&brute));
+
+        let slow = Solution {
+            slow: true,
+            ..brute.clone()
+        };
+        assert!(!Filter::default().matches(&slow));
+        let f = Filter {
+            slow: true,
+            ..Filter::default()
+        };
+        assert!(f.matches(&slow) && f.matches(&brute));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("150ns"), Some(Duration::from_nanos(150)));
+        assert_eq!(parse_duration("1.5ms"), Some(Duration::from_micros(1500)));
+        assert_eq!(parse_duration("12µs"), Some(Duration::from_micros(12)));
+        assert_eq!(parse_duration("2s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_leading_number() {
+        assert_eq!(leading_number("day12"), Some(12));
+        assert_eq!(leading_number("07-treetopinator"), Some(7));
+        assert_eq!(leading_number("template"), None);
+    }
+}
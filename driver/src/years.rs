@@ -0,0 +1,588 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::scaffold::{self, Change};
+use crate::solution::{cargo_build, leading_number, parse_duration, run_timed, Outcome, Solution};
+
+// Each year's workspace is laid out (and run) differently, this hides that
+pub trait Year {
+    fn year(&self) -> u32;
+    fn discover(&self) -> Vec<Solution>;
+    fn run(&self, solutions: &[Solution]) -> Vec<Outcome>;
+    // What it takes to start a new day from the year's template (nothing is written here)
+    fn scaffold(&self, day: u32, name: Option<&str>) -> Result<Vec<Change>, String>;
+}
+
+pub fn all(root: &Path) -> Vec<Box<dyn Year>> {
+    vec![
+        Box::new(Year2022 {
+            root: root.join("2022"),
+        }),
+        Box::new(Year2023 {
+            root: root.join("2023"),
+        }),
+        Box::new(Year2024 {
+            root: root.join("2024"),
+        }),
+        Box::new(Year2025 {
+            root: root.join("2025"),
+        }),
+    ]
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn failed(solutions: &[Solution], error: &str) -> Vec<Outcome> {
+    solutions
+        .iter()
+        .map(|s| Outcome {
+            solution: s.clone(),
+            answer: Err(error.to_string()),
+            elapsed: None,
+        })
+        .collect()
+}
+
+// 2022: one binary per day (NN-name), run as `NN-name <part> data/NN.txt`
+// Prints the answer and then `took <duration>`
+pub struct Year2022 {
+    pub root: PathBuf,
+}
+
+impl Year for Year2022 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn discover(&self) -> Vec<Solution> {
+        rust_files(&self.root.join("src").join("bin"))
+            .into_iter()
+            .filter(|p| {
+                std::fs::read_to_string(p).is_ok_and(|s| s.contains("aoc_main(part1, part2)"))
+            })
+            .filter_map(|p| {
+                let name = stem(&p);
+                let day = leading_number(&name)?;
+                Some((1..=2).map(move |part| Solution {
+                    year: 2022,
+                    day,
+                    part,
+                    variant: String::new(),
+                    target: name.clone(),
+                    slow: false,
+                }))
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn run(&self, solutions: &[Solution]) -> Vec<Outcome> {
+        if let Err(e) = cargo_build(&self.root, &["--bins"]) {
+            return failed(solutions, &e);
+        }
+
+        solutions
+            .iter()
+            .map(|s| {
+                let mut cmd = Command::new(self.root.join("target/release").join(&s.target));
+                cmd.current_dir(&self.root)
+                    .arg(s.part.to_string())
+                    .arg(format!("data/{:02}.txt", s.day));
+                let (result, wall) = run_timed(cmd, None);
+
+                // Prefer the binary's own timing, it doesn't include process startup
+                let mut elapsed = Some(wall);
+                let answer = result.map(|out| {
+                    let mut lines = out.lines().collect::<Vec<_>>();
+                    if let Some(took) = lines.last().and_then(|l| l.strip_prefix("took ")) {
+                        elapsed = parse_duration(took).or(elapsed);
+                        lines.pop();
+                    }
+                    lines.join("\n")
+                });
+
+                Outcome {
+                    solution: s.clone(),
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect()
+    }
+
+    fn scaffold(&self, day: u32, name: Option<&str>) -> Result<Vec<Change>, String> {
+        let name = name.ok_or("2022 binaries are named NN-name, use --name")?;
+        let prefix = format!("{day:02}-");
+        if let Some(existing) = rust_files(&self.root.join("src/bin"))
+            .into_iter()
+            .find(|p| stem(p).starts_with(&prefix))
+        {
+            return Err(format!("day {day} already exists: {}", existing.display()));
+        }
+
+        let template = scaffold::read(&self.root.join("src/bin/_template.rs.txt"))?;
+        let source = template.replace("aoc_test(\"\"", &format!("aoc_test(\"{day:02}\""));
+        Ok(vec![
+            Change::Create(self.root.join(format!("src/bin/{prefix}{name}.rs")), source),
+            Change::Create(self.root.join(format!("data/{day:02}.txt")), String::new()),
+            Change::Create(
+                self.root.join(format!("data/test/{day:02}.txt")),
+                String::new(),
+            ),
+        ])
+    }
+}
+
+// 2023: one crate per day (solutions/dayNN), one binary per part and variant (part1, part2-brute)
+// Reads the input from stdin
+pub struct Year2023 {
+    pub root: PathBuf,
+}
+
+impl Year for Year2023 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn discover(&self) -> Vec<Solution> {
+        let mut days = std::fs::read_dir(self.root.join("solutions"))
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .filter(|n| n.starts_with("day"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        days.sort();
+
+        days.into_iter()
+            .flat_map(|package| {
+                let bins = rust_files(&self.root.join("solutions").join(&package).join("src/bin"));
+                bins.into_iter().filter_map(move |p| {
+                    let name = stem(&p);
+                    // Renders and graph dumps don't produce answers
+                    if name.contains("render") || name.contains("dot") {
+                        return None;
+                    }
+
+                    let (part, variant) = name.split_once('-').unwrap_or((&name, ""));
+                    Some(Solution {
+                        year: 2023,
+                        day: leading_number(&package)?,
+                        part: leading_number(part)?,
+                        variant: variant.to_string(),
+                        target: format!("{package}/{name}"),
+                        slow: false,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn run(&self, solutions: &[Solution]) -> Vec<Outcome> {
+        let mut outcomes = vec![];
+
+        // Every day has binaries with the same names, so each day has to be built and run before the next
+        for chunk in solutions.chunk_by(|a, b| a.day == b.day) {
+            let package = format!("day{:02}", chunk[0].day);
+            if let Err(e) = cargo_build(&self.root, &["-p", &package, "--bins"]) {
+                outcomes.extend(failed(chunk, &e));
+                continue;
+            }
+
+            for s in chunk {
+                let (_, bin) = s.target.split_once('/').unwrap_or(("", &s.target));
+                let input = self.root.join(format!("data/{:02}.txt", s.day));
+                let (answer, elapsed) = run_timed(
+                    Command::new(self.root.join("target/release").join(bin)),
+                    Some(&input),
+                );
+                outcomes.push(Outcome {
+                    solution: s.clone(),
+                    answer: answer.map(|a| a.trim_end().to_string()),
+                    elapsed: Some(elapsed),
+                });
+            }
+        }
+
+        outcomes
+    }
+
+    // Same as `just init`: copy solutions/_template, replacing __day__, the workspace picks up solutions/* by itself
+    fn scaffold(&self, day: u32, _name: Option<&str>) -> Result<Vec<Change>, String> {
+        let padded = format!("{day:02}");
+        let package = self.root.join("solutions").join(format!("day{padded}"));
+        if package.exists() {
+            return Err(format!("day {day} already exists: {}", package.display()));
+        }
+
+        let mut changes = scaffold::copy_dir(
+            &self.root.join("solutions/_template"),
+            &package,
+            "__day__",
+            &padded,
+        )?;
+        changes.push(Change::Create(
+            self.root.join(format!("data/{padded}.txt")),
+            String::new(),
+        ));
+        changes.push(Change::Create(
+            self.root.join(format!("data/test/{padded}.txt")),
+            String::new(),
+        ));
+        Ok(changes)
+    }
+}
+
+// 2024: aoc-runner, solutions are `#[aoc(dayN, partM, variant)]` functions in the library
+// The generated main always runs everything, so run it once and pick out what was asked for
+pub struct Year2024 {
+    pub root: PathBuf,
+}
+
+// Parse `#[aoc(day11, part1, recursive_memo)]` into (day, part, variant)
+pub fn parse_aoc_attribute(line: &str) -> Option<(u32, u32, String)> {
+    let inner = line.trim().strip_prefix("#[aoc(")?.strip_suffix(")]")?;
+    let mut parts = inner.split(',').map(|s| s.trim());
+    let day = leading_number(parts.next()?)?;
+    let part = leading_number(parts.next()?)?;
+    let variant = parts.next().unwrap_or("").to_string();
+    Some((day, part, variant))
+}
+
+// (day, part, variant), answer, generator + runner time
+pub type RunnerResult = ((u32, u32, String), String, Option<std::time::Duration>);
+
+// Parse the generated main's output:
+// Day 11 - Part 1 - v1: 1234
+//     generator: 1.2ms,
+//     runner: 3.4µs
+pub fn parse_aoc_runner_output(output: &str) -> Vec<RunnerResult> {
+    let mut results = vec![];
+    let mut lines = output.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(rest) = line.strip_prefix("Day ") else {
+            continue;
+        };
+        let Some((header, answer)) = rest.split_once(": ") else {
+            continue;
+        };
+
+        let mut fields = header.split(" - ");
+        let day = fields.next().and_then(|d| d.trim().parse().ok());
+        let part = fields.next().and_then(leading_number);
+        let variant = fields.next().unwrap_or("").trim().to_string();
+        let (Some(day), Some(part)) = (day, part) else {
+            continue;
+        };
+
+        let mut elapsed: Option<std::time::Duration> = None;
+        while let Some(next) = lines.peek() {
+            let next = next.trim();
+            let timing = next
+                .strip_prefix("generator: ")
+                .or_else(|| next.strip_prefix("runner: "));
+            match timing {
+                Some(t) => {
+                    if let Some(d) = parse_duration(t.trim_end_matches(',')) {
+                        elapsed = Some(elapsed.unwrap_or_default() + d);
+                    }
+                    lines.next();
+                }
+                None => break,
+            }
+        }
+
+        results.push(((day, part, variant), answer.to_string(), elapsed));
+    }
+
+    results
+}
+
+impl Year for Year2024 {
+    fn year(&self) -> u32 {
+        2024
+    }
+
+    fn discover(&self) -> Vec<Solution> {
+        let mut solutions = rust_files(&self.root.join("src"))
+            .into_iter()
+            .filter(|p| stem(p).starts_with("day"))
+            .flat_map(|p| {
+                let source = std::fs::read_to_string(&p).unwrap_or_default();
+                source
+                    .lines()
+                    .filter_map(parse_aoc_attribute)
+                    .map(|(day, part, variant)| Solution {
+                        year: 2024,
+                        day,
+                        part,
+                        target: variant.clone(),
+                        variant,
+                        slow: false,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        solutions.sort_by_key(|s| s.day);
+        solutions
+    }
+
+    fn run(&self, solutions: &[Solution]) -> Vec<Outcome> {
+        if let Err(e) = cargo_build(&self.root, &["--bin", "aoc2024"]) {
+            return failed(solutions, &e);
+        }
+
+        let (result, _) = run_timed(Command::new(self.root.join("target/release/aoc2024")), None);
+        let output = match result {
+            Ok(output) => output,
+            Err(e) => return failed(solutions, &e),
+        };
+        let results = parse_aoc_runner_output(&output);
+
+        solutions
+            .iter()
+            .map(|s| {
+                let found = results
+                    .iter()
+                    .find(|((d, p, v), _, _)| *d == s.day && *p == s.part && *v == s.variant);
+                Outcome {
+                    solution: s.clone(),
+                    answer: found
+                        .map(|(_, a, _)| a.clone())
+                        .ok_or_else(|| "no output".to_string()),
+                    elapsed: found.and_then(|(_, _, e)| *e),
+                }
+            })
+            .collect()
+    }
+
+    // The template is written for day5, the new module also has to be added to lib.rs
+    fn scaffold(&self, day: u32, _name: Option<&str>) -> Result<Vec<Change>, String> {
+        let module = format!("day{day}");
+        let template = scaffold::read(&self.root.join("src/template.rs"))?;
+        let lib_path = self.root.join("src/lib.rs");
+        let lib = scaffold::read(&lib_path)?;
+
+        let mut changes = vec![Change::Create(
+            self.root.join(format!("src/{module}.rs")),
+            template.replace("day5", &module),
+        )];
+        if let Some(lib) = scaffold::insert_mod(&lib, &module) {
+            changes.push(Change::Edit(lib_path, lib));
+        }
+        // The tests include_str! the input, so it has to exist to compile
+        changes.push(Change::Create(
+            self.root.join(format!("input/2024/{module}.txt")),
+            String::new(),
+        ));
+        Ok(changes)
+    }
+}
+
+// 2025: aoc::main!, one binary per day with `#[aoc::register]` functions named part1, part1_variant, etc
+pub struct Year2025 {
+    pub root: PathBuf,
+}
+
+// Find the functions marked with #[aoc::register], with the part if the attribute gives one (part = 2) and whether
+// it's marked slow
+pub fn parse_registered(source: &str) -> Vec<(String, Option<u32>, bool)> {
+    let mut registered = vec![];
+    let mut lines = source.lines();
+
+    while let Some(line) = lines.next() {
+        if !line.trim().starts_with("#[aoc::register") || line.contains("register_render") {
+            continue;
+        }
+        // The attribute can run over several lines
+        let mut attribute = line.trim().to_string();
+        for next in lines.by_ref() {
+            if let Some(rest) = next.trim().strip_prefix("fn ") {
+                let name = rest.split('(').next().unwrap_or("").trim().to_string();
+                let args = registered_args(&attribute);
+                let part = args.iter().find_map(|arg| {
+                    let value = arg.strip_prefix("part")?.trim_start().strip_prefix('=')?;
+                    value.trim().parse().ok()
+                });
+                registered.push((name, part, args.contains(&"slow")));
+                break;
+            }
+            attribute.push_str(next.trim());
+        }
+    }
+
+    registered
+}
+
+// `["part = 2", "slow"]` out of `#[aoc::register(part = 2, slow)]`
+fn registered_args(attribute: &str) -> Vec<&str> {
+    attribute
+        .strip_prefix("#[aoc::register(")
+        .and_then(|args| args.strip_suffix(")]"))
+        .map_or(vec![], |args| args.split(',').map(str::trim).collect())
+}
+
+impl Year for Year2025 {
+    fn year(&self) -> u32 {
+        2025
+    }
+
+    fn discover(&self) -> Vec<Solution> {
+        let mut solutions = rust_files(&self.root.join("src").join("bin"))
+            .into_iter()
+            .filter_map(|p| {
+                let bin = stem(&p);
+                let day = leading_number(&bin)?;
+                let source = std::fs::read_to_string(&p).ok()?;
+                Some(
+                    parse_registered(&source)
+                        .into_iter()
+                        .filter_map(|(name, part, slow)| {
+                            let part =
+                                part.or_else(|| leading_number(name.strip_prefix("part")?))?;
+                            // part1_regex is part 1's regex variant, anything else is a variant named after itself
+                            let variant = match name.strip_prefix(&format!("part{part}")) {
+                                Some(rest) => rest.trim_start_matches('_').to_string(),
+                                None => name.clone(),
+                            };
+                            Some(Solution {
+                                year: 2025,
+                                day,
+                                part,
+                                variant,
+                                target: format!("{bin}/{name}"),
+                                slow,
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        solutions.sort_by_key(|s| s.day);
+        solutions
+    }
+
+    fn run(&self, solutions: &[Solution]) -> Vec<Outcome> {
+        if let Err(e) = cargo_build(&self.root, &["--bins"]) {
+            return failed(solutions, &e);
+        }
+
+        solutions
+            .iter()
+            .map(|s| {
+                let (bin, name) = s.target.split_once('/').unwrap_or(("", &s.target));
+                let mut cmd = Command::new(self.root.join("target/release").join(bin));
+                // No input path, the binary finds (or fetches) input/2025/dayN.txt itself
+                cmd.current_dir(&self.root).arg("run").arg(name);
+                let (answer, elapsed) = run_timed(cmd, None);
+
+                Outcome {
+                    solution: s.clone(),
+                    answer: answer.map(|a| a.trim_end().to_string()),
+                    elapsed: Some(elapsed),
+                }
+            })
+            .collect()
+    }
+
+    // No input file: an empty one would be taken as the cached input, the binary fetches it on first run instead
+    fn scaffold(&self, day: u32, _name: Option<&str>) -> Result<Vec<Change>, String> {
+        let bin = format!("day{day}");
+        let template = scaffold::read(&self.root.join("src/bin/_template.txt"))?;
+        Ok(vec![
+            Change::Create(
+                self.root.join(format!("src/bin/{bin}.rs")),
+                template.replace("dayN", &bin),
+            ),
+            Change::Create(
+                self.root.join(format!("input/2025/{bin}_example.txt")),
+                String::new(),
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_aoc_attribute() {
+        assert_eq!(
+            parse_aoc_attribute("#[aoc(day11, part1, recursive_memo)]"),
+            Some((11, 1, "recursive_memo".to_string()))
+        );
+        assert_eq!(
+            parse_aoc_attribute("#[aoc(day3, part2)]"),
+            Some((3, 2, String::new()))
+        );
+        assert_eq!(parse_aoc_attribute("// #[aoc(day11, part2, v1)]"), None);
+    }
+
+    #[test]
+    fn test_parse_aoc_runner_output() {
+        let output = "\
+Advent of code 2024
+Day 1 - Part 1: 11
+\tgenerator: 1ms,
+\trunner: 2ms
+
+Day 11 - Part 2 - recursive_memo: 65601038650482
+\tgenerator: 10µs,
+\trunner: 5µs
+";
+        let results = parse_aoc_runner_output(output);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, (1, 1, String::new()));
+        assert_eq!(results[0].2, Some(std::time::Duration::from_millis(3)));
+        assert_eq!(results[1].0, (11, 2, "recursive_memo".to_string()));
+        assert_eq!(results[1].1, "65601038650482");
+    }
+
+    #[test]
+    fn test_parse_registered() {
+        let source = "\
+#[aoc::register]
+fn part1(input: &str) -> impl Into<String> {}
+
+#[aoc::register_render(scale = 4)]
+fn render(input: &str) {}
+
+#[aoc::register]
+fn part2_bruteforce(input: &str) -> impl Into<String> {}
+
+#[aoc::register(slow, part = 2)]
+fn flood(input: &str) -> impl Into<String> {}
+";
+        assert_eq!(
+            parse_registered(source),
+            vec![
+                ("part1".to_string(), None, false),
+                ("part2_bruteforce".to_string(), None, false),
+                ("flood".to_string(), Some(2), true),
+            ]
+        );
+    }
+}
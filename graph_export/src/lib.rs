@@ -0,0 +1,680 @@
+// Graphs as DOT (graphviz), Mermaid (markdown and GitHub) or GraphML (yEd, Gephi and friends)
+// Anything that can describe itself as a `Graph` gets all three through `Export`
+// Output is sorted (nodes by id, edges by endpoints then attributes) so the same graph always comes out byte for
+// byte the same, however it was built (from a HashMap, say)
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+#[cfg(feature = "petgraph")]
+mod petgraph;
+
+pub type Attributes = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Mermaid,
+    GraphML,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Dot => "dot",
+            Format::Mermaid => "mmd",
+            Format::GraphML => "graphml",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Format> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" | "gv" => Ok(Format::Dot),
+            "mermaid" | "mmd" => Ok(Format::Mermaid),
+            "graphml" => Ok(Format::GraphML),
+            _ => Err(format!(
+                "unknown graph format {s}, expected dot, mermaid or graphml"
+            )),
+        }
+    }
+}
+
+pub trait Export {
+    fn graph(&self) -> Graph;
+
+    fn export(&self, format: Format) -> String {
+        self.graph().export(format)
+    }
+
+    fn to_dot(&self) -> String {
+        self.export(Format::Dot)
+    }
+
+    fn to_mermaid(&self) -> String {
+        self.export(Format::Mermaid)
+    }
+
+    fn to_graphml(&self) -> String {
+        self.export(Format::GraphML)
+    }
+
+    // The format comes from the extension: .dot/.gv, .mmd or .graphml
+    fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("can't tell the graph format of {}", path.display()),
+            )
+        })?;
+        std::fs::write(path, self.export(format))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Node {
+    pub attributes: Attributes,
+    pub cluster: Option<String>,
+}
+
+impl Node {
+    pub fn label(&mut self, label: impl ToString) -> &mut Node {
+        self.attr("label", label)
+    }
+
+    // Anything graphviz understands (color, shape, style, ...), Mermaid only keeps the colours
+    pub fn attr(&mut self, key: impl ToString, value: impl ToString) -> &mut Node {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    // Drawn in a box with the other nodes in the same cluster
+    pub fn cluster(&mut self, cluster: impl ToString) -> &mut Node {
+        self.cluster = Some(cluster.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub attributes: Attributes,
+}
+
+impl Edge {
+    pub fn label(&mut self, label: impl ToString) -> &mut Edge {
+        self.attr("label", label)
+    }
+
+    pub fn attr(&mut self, key: impl ToString, value: impl ToString) -> &mut Edge {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    pub name: String,
+    pub directed: bool,
+    pub attributes: Attributes,
+    pub nodes: BTreeMap<String, Node>,
+    pub edges: Vec<Edge>,
+    // Labels for clusters, any without one are labelled with their name
+    pub clusters: BTreeMap<String, String>,
+}
+
+impl Export for Graph {
+    fn graph(&self) -> Graph {
+        self.clone()
+    }
+
+    fn export(&self, format: Format) -> String {
+        match format {
+            Format::Dot => self.dot(),
+            Format::Mermaid => self.mermaid(),
+            Format::GraphML => self.graphml(),
+        }
+    }
+}
+
+impl Graph {
+    pub fn directed(name: impl ToString) -> Graph {
+        Graph {
+            name: name.to_string(),
+            directed: true,
+            ..Graph::default()
+        }
+    }
+
+    pub fn undirected(name: impl ToString) -> Graph {
+        Graph {
+            name: name.to_string(),
+            directed: false,
+            ..Graph::default()
+        }
+    }
+
+    // Graph wide attributes, e.g. rankdir=LR (which Mermaid also understands)
+    pub fn attr(&mut self, key: impl ToString, value: impl ToString) -> &mut Graph {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    // Added the first time, the same node after that
+    pub fn node(&mut self, id: impl ToString) -> &mut Node {
+        self.nodes.entry(id.to_string()).or_default()
+    }
+
+    // Nodes that don't exist yet are added without any attributes
+    pub fn edge(&mut self, from: impl ToString, to: impl ToString) -> &mut Edge {
+        let (from, to) = (from.to_string(), to.to_string());
+        self.node(&from);
+        self.node(&to);
+        self.edges.push(Edge {
+            from,
+            to,
+            attributes: Attributes::new(),
+        });
+        self.edges.last_mut().unwrap()
+    }
+
+    pub fn cluster_label(&mut self, cluster: impl ToString, label: impl ToString) -> &mut Graph {
+        self.clusters.insert(cluster.to_string(), label.to_string());
+        self
+    }
+
+    fn sorted_edges(&self) -> Vec<&Edge> {
+        let mut edges = self.edges.iter().collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    // Nodes grouped by cluster, those in no cluster first
+    fn by_cluster(&self) -> BTreeMap<Option<&str>, Vec<(&str, &Node)>> {
+        let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (id, node) in &self.nodes {
+            by_cluster
+                .entry(node.cluster.as_deref())
+                .or_default()
+                .push((id.as_str(), node));
+        }
+        by_cluster
+    }
+
+    // Clusters are nodes too in GraphML, so their ids have to stay clear of the real ones
+    // Underscores go on the front until it's free, what follows cluster_ still tells clusters apart
+    fn graphml_cluster_id(&self, cluster: &str) -> String {
+        let mut id = format!("cluster_{cluster}");
+        while self.nodes.contains_key(&id) {
+            id.insert(0, '_');
+        }
+        id
+    }
+
+    fn cluster_title<'a>(&'a self, cluster: &'a str) -> &'a str {
+        self.clusters.get(cluster).map_or(cluster, |l| l.as_str())
+    }
+
+    fn dot(&self) -> String {
+        fn attributes(attributes: &Attributes) -> String {
+            match attributes.is_empty() {
+                true => String::new(),
+                false => format!(
+                    " [{}]",
+                    attributes
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", quote(k), quote(v)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+
+        // Labels with real newlines come out as graphviz's own \n
+        fn quote(s: &str) -> String {
+            let escaped = s
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("\"{escaped}\"")
+        }
+
+        let mut dot = String::new();
+        let (kind, arrow) = match self.directed {
+            true => ("digraph", "->"),
+            false => ("graph", "--"),
+        };
+        let _ = writeln!(dot, "{kind} {} {{", quote(&self.name));
+        for (key, value) in &self.attributes {
+            let _ = writeln!(dot, "  {}={};", quote(key), quote(value));
+        }
+
+        for (cluster, nodes) in self.by_cluster() {
+            let indent = match cluster {
+                Some(cluster) => {
+                    let _ = writeln!(
+                        dot,
+                        "  subgraph {} {{",
+                        quote(&format!("cluster_{cluster}"))
+                    );
+                    let _ = writeln!(dot, "    label={};", quote(self.cluster_title(cluster)));
+                    "    "
+                }
+                None => "  ",
+            };
+            for (id, node) in nodes {
+                let _ = writeln!(
+                    dot,
+                    "{indent}{}{};",
+                    quote(id),
+                    attributes(&node.attributes)
+                );
+            }
+            if cluster.is_some() {
+                dot.push_str("  }\n");
+            }
+        }
+
+        for edge in self.sorted_edges() {
+            let _ = writeln!(
+                dot,
+                "  {} {arrow} {}{};",
+                quote(&edge.from),
+                quote(&edge.to),
+                attributes(&edge.attributes)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn mermaid(&self) -> String {
+        // Mermaid ids have to be plain words (and not keywords), anything else gets a numbered stand in
+        // Clusters share the same ids, so nodes can't have the cluster_ ones
+        let ids = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, id)| {
+                let plain = !id.is_empty()
+                    && !id.starts_with("__")
+                    && !id.starts_with("cluster_")
+                    && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !["end", "graph", "subgraph", "style", "class", "click"]
+                        .contains(&id.to_ascii_lowercase().as_str());
+                let mermaid_id = match plain {
+                    true => id.clone(),
+                    false => format!("__{i}"),
+                };
+                (id.as_str(), mermaid_id)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        fn quote(s: &str) -> String {
+            let escaped = s.replace('"', "#quot;").replace('\n', "<br>");
+            format!("\"{escaped}\"")
+        }
+
+        // Only the colours survive, Mermaid has no equivalent for most of graphviz
+        fn style(attributes: &Attributes) -> Option<String> {
+            let style = [
+                ("color", "stroke"),
+                ("fillcolor", "fill"),
+                ("fontcolor", "color"),
+            ]
+            .iter()
+            .filter_map(|(attribute, css)| Some(format!("{css}:{}", attributes.get(*attribute)?)))
+            .collect::<Vec<_>>();
+            (!style.is_empty()).then(|| style.join(","))
+        }
+
+        let direction = match self
+            .attributes
+            .get("rankdir")
+            .map(|d| d.to_ascii_uppercase())
+        {
+            Some(d) if ["LR", "RL", "TB", "BT"].contains(&d.as_str()) => d,
+            _ => "TB".to_string(),
+        };
+
+        let mut mermaid = format!("flowchart {direction}\n");
+        let mut styles = Vec::new();
+        for (i, (cluster, nodes)) in self.by_cluster().into_iter().enumerate() {
+            let indent = match cluster {
+                Some(cluster) => {
+                    // Numbered like the nodes if the name isn't plain, so a-b and a_b stay apart
+                    let plain = cluster
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_');
+                    let cluster_id = match plain {
+                        true => format!("cluster_{cluster}"),
+                        false => format!("__cluster{i}"),
+                    };
+                    let _ = writeln!(
+                        mermaid,
+                        "  subgraph {cluster_id} [{}]",
+                        quote(self.cluster_title(cluster))
+                    );
+                    "    "
+                }
+                None => "  ",
+            };
+            for (id, node) in nodes {
+                let label = node.attributes.get("label").map_or(id, |l| l.as_str());
+                let _ = writeln!(mermaid, "{indent}{}[{}]", ids[id], quote(label));
+                if let Some(style) = style(&node.attributes) {
+                    styles.push(format!("  style {} {style}", ids[id]));
+                }
+            }
+            if cluster.is_some() {
+                mermaid.push_str("  end\n");
+            }
+        }
+
+        let arrow = if self.directed { "-->" } else { "---" };
+        for (i, edge) in self.sorted_edges().into_iter().enumerate() {
+            let label = match edge.attributes.get("label") {
+                Some(label) => format!("|{}|", quote(label)),
+                None => String::new(),
+            };
+            let _ = writeln!(
+                mermaid,
+                "  {} {arrow}{label} {}",
+                ids[edge.from.as_str()],
+                ids[edge.to.as_str()]
+            );
+            if let Some(style) = style(&edge.attributes) {
+                styles.push(format!("  linkStyle {i} {style}"));
+            }
+        }
+
+        for style in styles {
+            mermaid.push_str(&style);
+            mermaid.push('\n');
+        }
+        mermaid
+    }
+
+    fn graphml(&self) -> String {
+        fn escape(s: &str) -> String {
+            s.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        }
+
+        fn data(xml: &mut String, indent: &str, prefix: &str, attributes: &Attributes) {
+            for (key, value) in attributes {
+                let _ = writeln!(
+                    xml,
+                    "{indent}<data key=\"{prefix}_{}\">{}</data>",
+                    escape(key),
+                    escape(value)
+                );
+            }
+        }
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+
+        // Every attribute used anywhere has to be declared up front, clusters are nodes with a label too
+        let mut keys = std::collections::BTreeSet::new();
+        keys.extend(self.attributes.keys().map(|k| ("graph", "g", k.as_str())));
+        keys.extend(
+            self.nodes
+                .values()
+                .flat_map(|n| n.attributes.keys())
+                .map(|k| ("node", "n", k.as_str())),
+        );
+        keys.extend(
+            self.edges
+                .iter()
+                .flat_map(|e| e.attributes.keys())
+                .map(|k| ("edge", "e", k.as_str())),
+        );
+        if self.nodes.values().any(|n| n.cluster.is_some()) {
+            keys.insert(("node", "n", "label"));
+        }
+        for (domain, prefix, key) in keys {
+            let key = escape(key);
+            let _ = writeln!(
+                xml,
+                "  <key id=\"{prefix}_{key}\" for=\"{domain}\" attr.name=\"{key}\" attr.type=\"string\"/>"
+            );
+        }
+
+        let edgedefault = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        let _ = writeln!(
+            xml,
+            "  <graph id=\"{}\" edgedefault=\"{edgedefault}\">",
+            escape(&self.name)
+        );
+        data(&mut xml, "    ", "g", &self.attributes);
+
+        for (cluster, nodes) in self.by_cluster() {
+            // A cluster is a node with a graph of its own inside
+            let indent = match cluster {
+                Some(cluster) => {
+                    let id = escape(&self.graphml_cluster_id(cluster));
+                    let _ = writeln!(xml, "    <node id=\"{id}\">");
+                    let _ = writeln!(
+                        xml,
+                        "      <data key=\"n_label\">{}</data>",
+                        escape(self.cluster_title(cluster))
+                    );
+                    let _ = writeln!(
+                        xml,
+                        "      <graph id=\"{id}:\" edgedefault=\"{edgedefault}\">"
+                    );
+                    "        "
+                }
+                None => "    ",
+            };
+            for (id, node) in nodes {
+                match node.attributes.is_empty() {
+                    true => {
+                        let _ = writeln!(xml, "{indent}<node id=\"{}\"/>", escape(id));
+                    }
+                    false => {
+                        let _ = writeln!(xml, "{indent}<node id=\"{}\">", escape(id));
+                        data(&mut xml, &format!("{indent}  "), "n", &node.attributes);
+                        let _ = writeln!(xml, "{indent}</node>");
+                    }
+                }
+            }
+            if cluster.is_some() {
+                xml.push_str("      </graph>\n    </node>\n");
+            }
+        }
+
+        for edge in self.sorted_edges() {
+            let (from, to) = (escape(&edge.from), escape(&edge.to));
+            match edge.attributes.is_empty() {
+                true => {
+                    let _ = writeln!(xml, "    <edge source=\"{from}\" target=\"{to}\"/>");
+                }
+                false => {
+                    let _ = writeln!(xml, "    <edge source=\"{from}\" target=\"{to}\">");
+                    data(&mut xml, "      ", "e", &edge.attributes);
+                    xml.push_str("    </edge>\n");
+                }
+            }
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Added out of order, to check it all comes out sorted
+    fn example() -> Graph {
+        let mut graph = Graph::directed("example");
+        graph.attr("rankdir", "LR");
+        graph.edge("b", "c").label("2");
+        graph.edge("a", "b").label("1").attr("color", "red");
+        graph.node("c").label("see\n\"c\"").cluster("out");
+        graph.node("a").attr("shape", "box");
+        graph.cluster_label("out", "Outputs");
+        graph
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(
+            example().to_dot(),
+            r#"digraph "example" {
+  "rankdir"="LR";
+  "a" ["shape"="box"];
+  "b";
+  subgraph "cluster_out" {
+    label="Outputs";
+    "c" ["label"="see\n\"c\""];
+  }
+  "a" -> "b" ["color"="red", "label"="1"];
+  "b" -> "c" ["label"="2"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_mermaid() {
+        assert_eq!(
+            example().to_mermaid(),
+            r#"flowchart LR
+  a["a"]
+  b["b"]
+  subgraph cluster_out ["Outputs"]
+    c["see<br>#quot;c#quot;"]
+  end
+  a -->|"1"| b
+  b -->|"2"| c
+  linkStyle 0 stroke:red
+"#
+        );
+
+        // Ids Mermaid would choke on
+        let mut graph = Graph::undirected("odd");
+        graph.edge("end", "a b");
+        assert_eq!(
+            graph.to_mermaid(),
+            "flowchart TB\n  __0[\"a b\"]\n  __1[\"end\"]\n  __1 --- __0\n"
+        );
+    }
+
+    #[test]
+    fn test_graphml() {
+        assert_eq!(
+            example().to_graphml(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="e_color" for="edge" attr.name="color" attr.type="string"/>
+  <key id="e_label" for="edge" attr.name="label" attr.type="string"/>
+  <key id="g_rankdir" for="graph" attr.name="rankdir" attr.type="string"/>
+  <key id="n_label" for="node" attr.name="label" attr.type="string"/>
+  <key id="n_shape" for="node" attr.name="shape" attr.type="string"/>
+  <graph id="example" edgedefault="directed">
+    <data key="g_rankdir">LR</data>
+    <node id="a">
+      <data key="n_shape">box</data>
+    </node>
+    <node id="b"/>
+    <node id="cluster_out">
+      <data key="n_label">Outputs</data>
+      <graph id="cluster_out:" edgedefault="directed">
+        <node id="c">
+          <data key="n_label">see
+&quot;c&quot;</data>
+        </node>
+      </graph>
+    </node>
+    <edge source="a" target="b">
+      <data key="e_color">red</data>
+      <data key="e_label">1</data>
+    </edge>
+    <edge source="b" target="c">
+      <data key="e_label">2</data>
+    </edge>
+  </graph>
+</graphml>
+"#
+        );
+    }
+
+    #[test]
+    fn test_edge_order() {
+        // The order they're added in makes no difference
+        let edges = [("b", "a"), ("a", "z"), ("a", "y")];
+        let mut forwards = Graph::directed("order");
+        let mut backwards = Graph::directed("order");
+        for (from, to) in edges {
+            forwards.edge(from, to);
+        }
+        for (from, to) in edges.into_iter().rev() {
+            backwards.edge(from, to);
+        }
+
+        assert_eq!(forwards.to_dot(), backwards.to_dot());
+        assert_eq!(
+            forwards
+                .sorted_edges()
+                .iter()
+                .map(|e| (e.from.as_str(), e.to.as_str()))
+                .collect::<Vec<_>>(),
+            [("a", "y"), ("a", "z"), ("b", "a")]
+        );
+    }
+
+    #[test]
+    fn test_cluster_ids() {
+        let mut graph = Graph::directed("clusters");
+        graph.node("x").cluster("a-b");
+        graph.node("y").cluster("a_b");
+        graph.node("cluster_a_b");
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("subgraph __cluster1 [\"a-b\"]"));
+        assert!(mermaid.contains("subgraph cluster_a_b [\"a_b\"]"));
+        assert!(mermaid.contains("  __0[\"cluster_a_b\"]"));
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains("<node id=\"cluster_a-b\">"));
+        assert!(graphml.contains("<node id=\"_cluster_a_b\">"));
+        assert!(graphml.contains("<node id=\"cluster_a_b\"/>"));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("dot".parse(), Ok(Format::Dot));
+        assert_eq!(
+            Format::from_path(Path::new("day24.mmd")),
+            Some(Format::Mermaid)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("day24.GraphML")),
+            Some(Format::GraphML)
+        );
+        assert!("png".parse::<Format>().is_err());
+    }
+}
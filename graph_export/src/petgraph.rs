@@ -0,0 +1,41 @@
+use std::fmt::Display;
+
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+
+use crate::{Export, Graph};
+
+// Nodes are labelled with their weight and numbered by index, edge weights are left out (they're often just ())
+impl<N: Display, E, Ty: EdgeType> Export for petgraph::Graph<N, E, Ty> {
+    fn graph(&self) -> Graph {
+        let mut graph = match self.is_directed() {
+            true => Graph::directed("G"),
+            false => Graph::undirected("G"),
+        };
+        for index in self.node_indices() {
+            graph.node(index.index()).label(&self[index]);
+        }
+        for edge in self.edge_references() {
+            graph.edge(edge.source().index(), edge.target().index());
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_petgraph() {
+        let mut graph = petgraph::graph::UnGraph::<&str, ()>::new_undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b, ());
+
+        assert_eq!(
+            graph.to_dot(),
+            "graph \"G\" {\n  \"0\" [\"label\"=\"a\"];\n  \"1\" [\"label\"=\"b\"];\n  \"0\" -- \"1\";\n}\n"
+        );
+    }
+}
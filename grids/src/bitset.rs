@@ -0,0 +1,152 @@
+use crate::{Bounds, GridLike, Pos};
+
+// One bit per cell, for walls, visited sets and the like
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bitset {
+    bounds: Bounds,
+    words: Vec<u64>,
+}
+
+// get() hands out references, and a bit doesn't have an address of its own
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+impl Bitset {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_bounds(Bounds::from_size((0, 0), width, height))
+    }
+
+    pub fn with_bounds(bounds: Bounds) -> Self {
+        Self {
+            bounds,
+            words: vec![0; (bounds.width() * bounds.height()).div_ceil(64)],
+        }
+    }
+
+    // Set wherever `f` is true; blank lines skipped and every line the same width, like Dense::read
+    pub fn read(input: &str, f: impl Fn(char) -> bool) -> Self {
+        let lines = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+
+        let mut bits = Self::new(width, lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            assert_eq!(
+                width,
+                line.chars().count(),
+                "Line {line} is the wrong width"
+            );
+            for (x, c) in line.chars().enumerate() {
+                if f(c) {
+                    bits.insert((x as isize, y as isize));
+                }
+            }
+        }
+        bits
+    }
+
+    // Out of bounds is never set
+    pub fn contains(&self, p: Pos) -> bool {
+        self.bounds
+            .index_of(p)
+            .is_some_and(|i| self.words[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    // Whether it was newly set; panics out of bounds
+    pub fn insert(&mut self, p: Pos) -> bool {
+        let i = self.index(p);
+        let was = self.words[i / 64] & (1 << (i % 64)) != 0;
+        self.words[i / 64] |= 1 << (i % 64);
+        !was
+    }
+
+    // Whether it was set; out of bounds never was
+    pub fn remove(&mut self, p: Pos) -> bool {
+        let Some(i) = self.bounds.index_of(p) else {
+            return false;
+        };
+        let was = self.words[i / 64] & (1 << (i % 64)) != 0;
+        self.words[i / 64] &= !(1 << (i % 64));
+        was
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // The set positions, row by row
+    pub fn ones(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.bounds.points().filter(|&p| self.contains(p))
+    }
+
+    fn index(&self, p: Pos) -> usize {
+        self.bounds
+            .index_of(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside {:?}", self.bounds))
+    }
+}
+
+impl GridLike for Bitset {
+    type Item = bool;
+
+    fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    fn get(&self, p: Pos) -> Option<&bool> {
+        self.bounds
+            .contains(p)
+            .then(|| if self.contains(p) { &TRUE } else { &FALSE })
+    }
+
+    fn set(&mut self, p: Pos, value: bool) -> bool {
+        if !self.bounds.contains(p) {
+            return false;
+        }
+        match value {
+            true => self.insert(p),
+            false => self.remove(p),
+        };
+        true
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &bool)> {
+        self.bounds.points().map(|p| (p, self.get(p).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        // More than one word's worth
+        let mut bits = Bitset::new(10, 10);
+        assert!(bits.insert((3, 7)));
+        assert!(!bits.insert((3, 7)));
+        assert!(bits.insert((9, 9)));
+        assert!(bits.contains((3, 7)));
+        assert!(!bits.contains((7, 3)));
+        assert_eq!(bits.count(), 2);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), vec![(3, 7), (9, 9)]);
+
+        // Out of bounds is never set, and can't be
+        assert!(!bits.contains((10, 0)));
+        assert!(!bits.remove((-1, 0)));
+        assert!(!bits.set((0, 10), true));
+        assert_eq!(bits.get((0, 10)), None);
+        assert_eq!(bits.get((9, 9)), Some(&true));
+
+        assert!(bits.remove((3, 7)));
+        assert!(!bits.remove((3, 7)));
+        bits.clear();
+        assert_eq!(bits.count(), 0);
+    }
+}
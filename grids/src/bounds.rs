@@ -0,0 +1,112 @@
+use crate::Pos;
+
+// An inclusive rectangle of positions, which can be empty (min past max)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min_x: isize,
+    pub min_y: isize,
+    pub max_x: isize,
+    pub max_y: isize,
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Bounds {
+    pub const EMPTY: Bounds = Bounds {
+        min_x: 0,
+        min_y: 0,
+        max_x: -1,
+        max_y: -1,
+    };
+
+    pub fn new((min_x, min_y): Pos, (max_x, max_y): Pos) -> Self {
+        Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    // `width` by `height` with its top left at `origin`
+    pub fn from_size((x, y): Pos, width: usize, height: usize) -> Self {
+        Self::new((x, y), (x + width as isize - 1, y + height as isize - 1))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_x > self.max_x || self.min_y > self.max_y
+    }
+
+    pub fn min(&self) -> Pos {
+        (self.min_x, self.min_y)
+    }
+
+    pub fn max(&self) -> Pos {
+        (self.max_x, self.max_y)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y + 1).max(0) as usize
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y
+    }
+
+    // Grow to cover `p` (an empty bounds becomes just `p`)
+    pub fn include(&mut self, (x, y): Pos) {
+        if self.is_empty() {
+            *self = Self::new((x, y), (x, y));
+            return;
+        }
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    pub fn union(mut self, other: Bounds) -> Bounds {
+        if !other.is_empty() {
+            self.include(other.min());
+            self.include(other.max());
+        }
+        self
+    }
+
+    // Every position inside, row by row
+    pub fn points(&self) -> impl Iterator<Item = Pos> {
+        let Bounds {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        } = *self;
+        (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+
+    // Where `p` would be in a row-major Vec covering these bounds
+    pub fn index_of(&self, p: Pos) -> Option<usize> {
+        if !self.contains(p) {
+            return None;
+        }
+        Some((p.1 - self.min_y) as usize * self.width() + (p.0 - self.min_x) as usize)
+    }
+}
+
+impl FromIterator<Pos> for Bounds {
+    fn from_iter<I: IntoIterator<Item = Pos>>(iter: I) -> Self {
+        let mut bounds = Bounds::EMPTY;
+        for p in iter {
+            bounds.include(p);
+        }
+        bounds
+    }
+}
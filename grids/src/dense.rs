@@ -0,0 +1,172 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Bounds, GridLike, Pos};
+
+// Every cell stored, row after row, in one Vec
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dense<T> {
+    bounds: Bounds,
+    data: Vec<T>,
+}
+
+impl<T> Dense<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let bounds = Bounds::from_size((0, 0), width, height);
+        Self {
+            bounds,
+            data: bounds.points().map(&mut f).collect(),
+        }
+    }
+
+    // One cell per character, blank lines skipped; every line has to be the same width
+    pub fn read(input: &str, f: impl Fn(char) -> T) -> Self {
+        let mut width = 0;
+        let mut height = 0;
+        let mut data = Vec::new();
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let before = data.len();
+            data.extend(line.chars().map(&f));
+
+            if height == 0 {
+                width = data.len();
+            } else {
+                assert_eq!(width, data.len() - before, "Line {line} is the wrong width");
+            }
+            height += 1;
+        }
+
+        Self {
+            bounds: Bounds::from_size((0, 0), width, height),
+            data,
+        }
+    }
+
+    // The same cells, with the top left one at `origin` instead of (0, 0)
+    pub fn with_origin(self, origin: Pos) -> Self {
+        Self {
+            bounds: Bounds::from_size(origin, self.bounds.width(), self.bounds.height()),
+            data: self.data,
+        }
+    }
+
+    pub fn origin(&self) -> Pos {
+        self.bounds.min()
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        let i = self.bounds.index_of(p)?;
+        Some(&mut self.data[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.bounds.points().zip(self.data.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.bounds.points().zip(self.data.iter_mut())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.bounds.width().max(1))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Dense<U> {
+        Dense {
+            bounds: self.bounds,
+            data: self.iter().map(|(p, v)| f(p, v)).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Dense<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            bounds: Bounds::from_size((0, 0), width, height),
+            data: vec![value; width * height],
+        }
+    }
+}
+
+impl<T: Clone + Default> Dense<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, T::default())
+    }
+}
+
+impl<T> GridLike for Dense<T> {
+    type Item = T;
+
+    fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.bounds.index_of(p).map(|i| &self.data[i])
+    }
+
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(v) => {
+                *v = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.iter()
+    }
+}
+
+impl<T> Index<Pos> for Dense<T> {
+    type Output = T;
+
+    fn index(&self, p: Pos) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside {:?}", self.bounds))
+    }
+}
+
+impl<T> IndexMut<Pos> for Dense<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        let bounds = self.bounds;
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside {bounds:?}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+#..#
+.##.
+#...
+";
+
+    #[test]
+    fn test_row_major() {
+        let dense = Dense::read(INPUT, |c| c);
+        assert_eq!((dense.width(), dense.height()), (4, 3));
+        assert_eq!(dense.get((3, 0)), Some(&'#'));
+        assert_eq!(dense[(0, 2)], '#');
+        assert_eq!(dense.get((4, 0)), None);
+        assert_eq!(
+            dense.cells().take(5).map(|(p, _)| p).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (3, 0), (0, 1)]
+        );
+        assert_eq!(dense.rows().nth(1), Some(&['.', '#', '#', '.'][..]));
+        assert_eq!(dense.to_string(' ', |c| *c), INPUT);
+    }
+}
@@ -0,0 +1,357 @@
+// Grids for every year: one trait, and three ways of storing the cells behind it
+// Dense keeps every cell in a Vec, Sparse only the ones that are set (and grows to fit), Bitset one bit each.
+// Everything is row-major: iteration, to_string and render all go along a row before moving down to the next.
+
+use std::collections::HashSet;
+
+mod bitset;
+mod bounds;
+mod dense;
+pub mod regions;
+pub mod search;
+mod sparse;
+mod view;
+mod wrap;
+
+pub use bitset::Bitset;
+pub use bounds::Bounds;
+pub use dense::Dense;
+pub use regions::{Connectivity, Labels, Region};
+pub use search::{Search, State};
+pub use sparse::Sparse;
+pub use view::View;
+pub use wrap::{Wrap, Wrapped};
+
+// (x, y), with y going down; each year converts its own Point to and from this
+pub type Pos = (isize, isize);
+
+pub type Color = [u8; 3];
+
+// Up, left, right, down: the order they'd be met reading the grid
+pub const ORTHOGONAL: [Pos; 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+pub const ALL_DIRECTIONS: [Pos; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+pub trait GridLike {
+    type Item;
+
+    // Where cells can be; a Sparse grid's grow as cells are added
+    fn bounds(&self) -> Bounds;
+
+    fn get(&self, p: Pos) -> Option<&Self::Item>;
+
+    // False (and nothing changes) if the grid can't hold `p`
+    fn set(&mut self, p: Pos, value: Self::Item) -> bool;
+
+    // Every cell with something in it, row by row
+    fn cells(&self) -> impl Iterator<Item = (Pos, &Self::Item)>;
+
+    fn width(&self) -> usize {
+        self.bounds().width()
+    }
+
+    fn height(&self) -> usize {
+        self.bounds().height()
+    }
+
+    fn in_bounds(&self, p: Pos) -> bool {
+        self.bounds().contains(p)
+    }
+
+    // The (up to) 8 cells around `p` that have something in them
+    fn neighbors(&self, (x, y): Pos) -> impl Iterator<Item = (Pos, &Self::Item)> {
+        ALL_DIRECTIONS.into_iter().filter_map(move |(dx, dy)| {
+            let n = (x + dx, y + dy);
+            self.get(n).map(|v| (n, v))
+        })
+    }
+
+    // The same, but only up, left, right and down
+    fn ortho_neighbors(&self, (x, y): Pos) -> impl Iterator<Item = (Pos, &Self::Item)> {
+        ORTHOGONAL.into_iter().filter_map(move |(dx, dy)| {
+            let n = (x + dx, y + dy);
+            self.get(n).map(|v| (n, v))
+        })
+    }
+
+    // From `start`, `step` at a time, until the edge (skipping empty cells)
+    fn line(&self, start: Pos, step: Pos) -> impl Iterator<Item = (Pos, &Self::Item)> {
+        assert_ne!(step, (0, 0), "a line has to go somewhere");
+        let bounds = self.bounds();
+        std::iter::successors(Some(start), move |&(x, y)| Some((x + step.0, y + step.1)))
+            .take_while(move |&p| bounds.contains(p))
+            .filter_map(move |p| self.get(p).map(|v| (p, v)))
+    }
+
+    fn row(&self, y: isize) -> impl Iterator<Item = (Pos, &Self::Item)> {
+        self.line((self.bounds().min_x, y), (1, 0))
+    }
+
+    fn column(&self, x: isize) -> impl Iterator<Item = (Pos, &Self::Item)> {
+        self.line((x, self.bounds().min_y), (0, 1))
+    }
+
+    // Every line going down and to the right, from the one starting in the bottom left corner to the top right
+    fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Pos, &Self::Item)>> {
+        let b = self.bounds();
+        let left = (b.min_y..=b.max_y).rev().map(move |y| (b.min_x, y));
+        let top = (b.min_x + 1..=b.max_x).map(move |x| (x, b.min_y));
+        left.chain(top).map(move |start| self.line(start, (1, 1)))
+    }
+
+    // Every line going down and to the left, from the one starting in the top left corner to the bottom right
+    fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Pos, &Self::Item)>> {
+        let b = self.bounds();
+        let top = (b.min_x..=b.max_x).map(move |x| (x, b.min_y));
+        let right = (b.min_y + 1..=b.max_y).map(move |y| (b.max_x, y));
+        top.chain(right).map(move |start| self.line(start, (-1, 1)))
+    }
+
+    // Rotated, flipped or windowed without a copy, see View
+    fn view(&self) -> View<'_, Self>
+    where
+        Self: Sized,
+    {
+        View::new(self)
+    }
+
+    // Every cell connected to `start` (up, down, left or right) through cells with the same value as it
+    fn flood_fill(&self, start: Pos) -> Vec<Pos>
+    where
+        Self::Item: PartialEq,
+    {
+        let Some(target) = self.get(start) else {
+            return Vec::new();
+        };
+
+        let mut stack = vec![start];
+        let mut visited = HashSet::from([start]);
+        let mut result = Vec::new();
+
+        while let Some(p) = stack.pop() {
+            result.push(p);
+            for (n, v) in self.ortho_neighbors(p) {
+                if v == target && visited.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+
+        result
+    }
+
+    // Every group of connected cells with the same value, see regions::label
+    fn regions(&self, connectivity: Connectivity) -> Labels
+    where
+        Self::Item: PartialEq,
+    {
+        regions::label(self, connectivity, |a, b| a == b)
+    }
+
+    // The same, with `same` deciding which neighbours go together
+    fn regions_by(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&Self::Item, &Self::Item) -> bool,
+    ) -> Labels {
+        regions::label(self, connectivity, same)
+    }
+
+    // One character per cell over the whole bounds, `empty` where there's nothing
+    fn to_string(&self, empty: char, f: impl Fn(&Self::Item) -> char) -> String {
+        let bounds = self.bounds();
+        let mut s = String::new();
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                s.push(self.get((x, y)).map_or(empty, &f));
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    // The same, for cells that take more than one character
+    fn to_string_with(&self, empty: &str, f: impl Fn(&Self::Item) -> String) -> String {
+        let bounds = self.bounds();
+        let mut s = String::new();
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                match self.get((x, y)) {
+                    Some(v) => s.push_str(&f(v)),
+                    None => s.push_str(empty),
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    // One pixel per cell, (0, 0) at the top left of the bounds, handed to `put` since every year has its own image
+    fn render(
+        &self,
+        empty: Color,
+        f: impl Fn(&Self::Item) -> Color,
+        put: &mut dyn FnMut(u32, u32, Color),
+    ) {
+        let bounds = self.bounds();
+        for (x, y) in bounds.points() {
+            let color = self.get((x, y)).map_or(empty, &f);
+            put((x - bounds.min_x) as u32, (y - bounds.min_y) as u32, color);
+        }
+    }
+
+    // Read through `wrap` instead of stopping at the edge
+    fn wrapped(self, wrap: Wrap) -> Wrapped<Self>
+    where
+        Self: Sized,
+    {
+        Wrapped::new(self, wrap)
+    }
+
+    // Every cell in the bounds, `empty` where there's nothing
+    fn to_dense(&self, empty: Self::Item) -> Dense<Self::Item>
+    where
+        Self::Item: Clone,
+    {
+        let bounds = self.bounds();
+        Dense::from_fn(bounds.width(), bounds.height(), |(x, y)| {
+            let p = (x + bounds.min_x, y + bounds.min_y);
+            self.get(p).unwrap_or(&empty).clone()
+        })
+        .with_origin(bounds.min())
+    }
+
+    // Only the cells with something in them, with the same bounds
+    fn to_sparse(&self) -> Sparse<Self::Item>
+    where
+        Self::Item: Clone,
+    {
+        let mut sparse = Sparse::with_bounds(self.bounds());
+        for (p, v) in self.cells() {
+            sparse.insert(p, v.clone());
+        }
+        sparse
+    }
+
+    // Set wherever `f` is true, with the same bounds
+    fn to_bitset(&self, f: impl Fn(&Self::Item) -> bool) -> Bitset {
+        let mut bits = Bitset::with_bounds(self.bounds());
+        for (p, v) in self.cells() {
+            if f(v) {
+                bits.insert(p);
+            }
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+#..#
+.##.
+#...
+";
+
+    #[test]
+    fn test_backends_agree() {
+        let dense = Dense::read(INPUT, |c| c == '#');
+        let sparse = Sparse::read(INPUT, |c| (c == '#').then_some(true));
+        let bits = Bitset::read(INPUT, |c| c == '#');
+
+        let walls = |g: &dyn Fn(Pos) -> bool| {
+            Bounds::new((0, 0), (3, 2))
+                .points()
+                .filter(|&p| g(p))
+                .collect::<Vec<_>>()
+        };
+        let expected = walls(&|p| dense[p]);
+        assert_eq!(walls(&|p| sparse.get(p).is_some()), expected);
+        assert_eq!(walls(&|p| bits.contains(p)), expected);
+        assert_eq!(sparse.positions().collect::<Vec<_>>(), expected);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), expected);
+        assert_eq!(bits.count(), 5);
+
+        let text = dense.to_string('?', |&b| if b { '#' } else { '.' });
+        assert_eq!(sparse.to_string('.', |_| '#'), text);
+        assert_eq!(bits.to_string('?', |&b| if b { '#' } else { '.' }), text);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let mut sparse = Sparse::new();
+        sparse.insert((-2, 5), 'a');
+        sparse.insert((1, 6), 'b');
+        assert_eq!(sparse.bounds(), Bounds::new((-2, 5), (1, 6)));
+
+        let dense = sparse.to_dense('.');
+        assert_eq!(dense.origin(), (-2, 5));
+        assert_eq!(dense.to_string(' ', |c| *c), "a...\n...b\n");
+        assert_eq!(dense.to_sparse().len(), 8);
+
+        let bits = dense.to_bitset(|c| *c != '.');
+        assert_eq!(bits.ones().collect::<Vec<_>>(), vec![(-2, 5), (1, 6)]);
+        assert!(bits.to_dense(false)[(1, 6)]);
+        assert_eq!(bits.to_sparse().len(), 8);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let dense = Dense::read(INPUT, |c| c);
+        let mut region = dense.flood_fill((1, 0));
+        region.sort();
+        assert_eq!(region, vec![(1, 0), (2, 0)]);
+        assert_eq!(dense.flood_fill((1, 1)).len(), 2);
+        assert!(dense.flood_fill((9, 9)).is_empty());
+    }
+
+    #[test]
+    fn test_lines() {
+        let dense = Dense::read("ab\ncd\nef\n", |c| c);
+        let collect = |line: &mut dyn Iterator<Item = (Pos, &char)>| {
+            line.map(|(_, c)| *c).collect::<String>()
+        };
+
+        assert_eq!(collect(&mut dense.row(1)), "cd");
+        assert_eq!(collect(&mut dense.column(1)), "bdf");
+        assert_eq!(
+            dense
+                .diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["e", "cf", "ad", "b"]
+        );
+        assert_eq!(
+            dense
+                .anti_diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["a", "bc", "de", "f"]
+        );
+        assert_eq!(collect(&mut dense.view().rotate90().row(0)), "eca");
+    }
+
+    #[test]
+    fn test_render() {
+        let bits = Bitset::read(INPUT, |c| c == '#');
+        let mut pixels = [[0; 3]; 12];
+        bits.render(
+            [9; 3],
+            |&b| if b { [255; 3] } else { [0; 3] },
+            &mut |x, y, color| pixels[y as usize * 4 + x as usize] = color,
+        );
+        assert_eq!(pixels[3], [255; 3]);
+        assert_eq!(pixels[4], [0; 3]);
+    }
+}
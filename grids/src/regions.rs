@@ -0,0 +1,400 @@
+use crate::{Bitset, Bounds, GridLike, Pos, Sparse, ALL_DIRECTIONS, ORTHOGONAL};
+
+// Which cells count as touching
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    // Up, left, right and down
+    #[default]
+    Four,
+    // Diagonals too
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Pos] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &ALL_DIRECTIONS,
+        }
+    }
+}
+
+// A connected group of cells
+// The shape measurements treat each cell as a square, so they're the same whichever connectivity found the region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: Vec<Pos>,
+    bits: Bitset,
+}
+
+impl FromIterator<Pos> for Region {
+    fn from_iter<I: IntoIterator<Item = Pos>>(iter: I) -> Self {
+        let mut cells = iter.into_iter().collect::<Vec<_>>();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+
+        let mut bits = Bitset::with_bounds(cells.iter().copied().collect());
+        for &p in &cells {
+            bits.insert(p);
+        }
+        Self { cells, bits }
+    }
+}
+
+impl Region {
+    // Row by row
+    pub fn cells(&self) -> &[Pos] {
+        &self.cells
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.bits.contains(p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // The smallest rectangle it fits in
+    pub fn bounds(&self) -> Bounds {
+        self.bits.bounds()
+    }
+
+    // How many cell edges there are between this region and anything else (holes included)
+    pub fn perimeter(&self) -> usize {
+        self.edges().count()
+    }
+
+    // The same edges, but a straight run of them only counts once
+    pub fn sides(&self) -> usize {
+        self.edges()
+            .filter(|&((x, y), (dx, dy))| {
+                // The cell before this one along the side (left for tops and bottoms, above for lefts and rights)
+                let before = (x - dy.abs(), y - dx.abs());
+                let carries_on = (before.0 + dx, before.1 + dy);
+                !self.contains(before) || self.contains(carries_on)
+            })
+            .count()
+    }
+
+    // Every corner where the outline turns, in or out; always the same as sides
+    pub fn corners(&self) -> usize {
+        let mut corners = 0;
+        for &(x, y) in &self.cells {
+            for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                let across = self.contains((x + dx, y));
+                let down = self.contains((x, y + dy));
+                let diagonal = self.contains((x + dx, y + dy));
+
+                // Sticking out, or tucked in
+                if (!across && !down) || (across && down && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+
+    // The gaps it surrounds, each as a region of its own
+    // Gaps join up four ways, so one that only touches the outside corner to corner is still a hole.
+    pub fn holes(&self) -> Vec<Region> {
+        let bounds = self.bounds();
+        let around = Bounds::new(
+            (bounds.min_x - 1, bounds.min_y - 1),
+            (bounds.max_x + 1, bounds.max_y + 1),
+        );
+
+        let mut gaps = Bitset::with_bounds(around);
+        for p in around.points().filter(|&p| !self.contains(p)) {
+            gaps.insert(p);
+        }
+
+        // Everything the outside can reach isn't a hole
+        gaps.remove(around.min());
+        fill(around.min(), |to| gaps.remove(to));
+
+        let mut holes = vec![];
+        for p in gaps.ones().collect::<Vec<_>>() {
+            if gaps.remove(p) {
+                holes.push(fill(p, |to| gaps.remove(to)).into_iter().collect());
+            }
+        }
+        holes
+    }
+
+    // Whether `p` is in one of the holes
+    pub fn encloses(&self, p: Pos) -> bool {
+        let bounds = self.bounds();
+        if self.contains(p) || !bounds.contains(p) {
+            return false;
+        }
+
+        let mut seen = Bitset::with_bounds(bounds);
+        let mut escaped = false;
+        seen.insert(p);
+        fill(p, |to| {
+            escaped |= !bounds.contains(to);
+            !escaped && !self.contains(to) && seen.insert(to)
+        });
+        !escaped
+    }
+
+    // Each cell edge on the outline, as the cell and which way it faces
+    fn edges(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        self.cells.iter().flat_map(move |&(x, y)| {
+            ORTHOGONAL
+                .into_iter()
+                .filter(move |&(dx, dy)| !self.contains((x + dx, y + dy)))
+                .map(move |d| ((x, y), d))
+        })
+    }
+}
+
+// Every cell of a grid sorted into regions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    ids: Sparse<usize>,
+    regions: Vec<Region>,
+}
+
+impl Labels {
+    // In the order their first cells come, row by row
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn into_regions(self) -> Vec<Region> {
+        self.regions
+    }
+
+    // Which region `p` is in, as an index into regions()
+    pub fn id(&self, p: Pos) -> Option<usize> {
+        self.ids.get(p).copied()
+    }
+
+    pub fn region_at(&self, p: Pos) -> Option<&Region> {
+        self.id(p).map(|id| &self.regions[id])
+    }
+
+    // Every cell's region id, as a grid (to render, say)
+    pub fn ids(&self) -> &Sparse<usize> {
+        &self.ids
+    }
+}
+
+// Neighbouring cells go in the same region whenever `same` says so
+// Neighbours are whatever the grid says they are, so a wrapped grid joins up across its edges instead of running off them
+pub fn label<G: GridLike + ?Sized>(
+    grid: &G,
+    connectivity: Connectivity,
+    same: impl Fn(&G::Item, &G::Item) -> bool,
+) -> Labels {
+    let mut ids = Sparse::new();
+    let mut regions = vec![];
+
+    for (start, _) in grid.cells() {
+        if ids.contains(start) {
+            continue;
+        }
+
+        let id = regions.len();
+        ids.insert(start, id);
+        let mut cells = vec![start];
+        let mut stack = vec![start];
+        while let Some(from) = stack.pop() {
+            let Some(here) = grid.get(from) else {
+                continue;
+            };
+            for (to, there) in neighbours(grid, connectivity, from) {
+                if same(here, there) && !ids.contains(to) {
+                    ids.insert(to, id);
+                    cells.push(to);
+                    stack.push(to);
+                }
+            }
+        }
+        regions.push(cells.into_iter().collect());
+    }
+
+    Labels { ids, regions }
+}
+
+fn neighbours<G: GridLike + ?Sized>(
+    grid: &G,
+    connectivity: Connectivity,
+    p: Pos,
+) -> Vec<(Pos, &G::Item)> {
+    match connectivity {
+        Connectivity::Four => grid.ortho_neighbors(p).collect(),
+        Connectivity::Eight => grid.neighbors(p).collect(),
+    }
+}
+
+// Whether `p` is inside a closed loop, one step up, down, left or right at a time (the last step back to the first)
+// Points on the loop aren't inside it. Two bits of loop side by side don't wall anything off, unlike with holes.
+pub fn enclosed_by(path: &[Pos], p: Pos) -> bool {
+    !path.contains(&p) && crossings(path, p.1).filter(|&x| x > p.0).count() % 2 == 1
+}
+
+// Every position enclosed_by the loop, row by row
+pub fn enclosed(path: &[Pos]) -> Vec<Pos> {
+    let bounds = path.iter().copied().collect::<Bounds>();
+    let mut on_path = Bitset::with_bounds(bounds);
+    for &p in path {
+        on_path.insert(p);
+    }
+
+    let mut inside = vec![];
+    for y in bounds.min_y..=bounds.max_y {
+        let mut xs = crossings(path, y).collect::<Vec<_>>();
+        xs.sort();
+        for pair in xs.chunks_exact(2) {
+            inside.extend(
+                (pair[0]..pair[1])
+                    .map(|x| (x, y))
+                    .filter(|&p| !on_path.contains(p)),
+            );
+        }
+    }
+    inside
+}
+
+// Where the loop goes from row `y` to the one below (or back up), which is where a line along `y` crosses it
+fn crossings(path: &[Pos], y: isize) -> impl Iterator<Item = isize> + '_ {
+    path.iter()
+        .zip(path.iter().cycle().skip(1))
+        .filter_map(move |(&(x1, y1), &(x2, y2))| {
+            assert!(
+                (x1 - x2).abs() + (y1 - y2).abs() == 1,
+                "{:?} to {:?} isn't one step",
+                (x1, y1),
+                (x2, y2)
+            );
+            (x1 == x2 && y1.min(y2) == y).then_some(x1)
+        })
+}
+
+// Everything reachable from `start` up, down, left or right, wherever `take(to)` agrees to (it marks what it takes)
+fn fill(start: Pos, mut take: impl FnMut(Pos) -> bool) -> Vec<Pos> {
+    let mut found = vec![start];
+    let mut stack = vec![start];
+    while let Some((x, y)) = stack.pop() {
+        for (dx, dy) in ORTHOGONAL {
+            let next = (x + dx, y + dy);
+            if take(next) {
+                found.push(next);
+                stack.push(next);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Dense, Wrap};
+
+    #[test]
+    fn test_regions() {
+        // From 2024 day 12
+        let plots = Dense::read("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n", |c| c);
+        let labels = plots.regions(Connectivity::Four);
+        assert_eq!(labels.regions().len(), 3);
+
+        let a = labels.region_at((0, 0)).unwrap();
+        assert_eq!(a.area(), 28);
+        assert_eq!(a.perimeter(), 40);
+        assert_eq!(a.sides(), 12);
+        assert_eq!(a.corners(), 12);
+        assert_eq!(a.bounds(), plots.bounds());
+        assert_eq!(a.holes().len(), 2);
+        assert!(a.encloses((4, 1)));
+        assert!(!a.encloses((0, 0)));
+        assert_eq!(labels.id((1, 4)), Some(2));
+        assert_eq!(labels.region_at((4, 2)).unwrap().sides(), 4);
+
+        // The two B's only touch corner to corner
+        let eight = plots.regions(Connectivity::Eight);
+        assert_eq!(eight.regions().len(), 2);
+        let b = eight.region_at((1, 3)).unwrap();
+        assert_eq!(b.area(), 8);
+        assert_eq!((b.sides(), b.corners()), (8, 8));
+        assert!(b.holes().is_empty());
+
+        // Anything but a space goes together (and the spaces on the left and right don't touch)
+        let bits = Dense::read("ab \n c \n", |c| c);
+        let labels = bits.regions_by(Connectivity::Four, |a, b| (*a == ' ') == (*b == ' '));
+        assert_eq!(labels.regions().len(), 3);
+        assert_eq!(labels.regions()[0].cells(), &[(0, 0), (1, 0), (1, 1)]);
+        assert_eq!(labels.ids().len(), 6);
+    }
+
+    #[test]
+    fn test_wrapped_regions() {
+        let dense = Dense::read("#..#\n.##.\n#...\n", |c| c);
+
+        // The corners meet across the edges, and so do all the dots
+        let torus = dense.clone().wrapped(Wrap::Toroidal);
+        let labels = torus.regions(Connectivity::Four);
+        assert_eq!(labels.regions().len(), 3);
+        let mut corners = labels.region_at((0, 0)).unwrap().cells().to_vec();
+        corners.sort();
+        assert_eq!(corners, vec![(0, 0), (0, 2), (3, 0)]);
+        assert_eq!(labels.region_at((1, 0)).unwrap().area(), 7);
+        assert_eq!(labels.id((3, 2)), labels.id((0, 1)));
+        assert_eq!(torus.regions(Connectivity::Eight).regions().len(), 2);
+
+        // Only the interior is labelled, none of the border around it
+        let interior = dense.wrapped(Wrap::Interior(1));
+        let labels = interior.regions(Connectivity::Four);
+        assert_eq!(labels.regions().len(), 1);
+        assert_eq!(labels.ids().len(), 2);
+    }
+
+    #[test]
+    fn test_enclosed() {
+        // Doubled back on itself, so (2, 2) has loop on three sides but isn't inside it
+        //  #####
+        //  #####
+        //  ##.##
+        //  ##.##
+        let path = vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (4, 0),
+            (4, 1),
+            (4, 2),
+            (4, 3),
+            (3, 3),
+            (3, 2),
+            (3, 1),
+            (2, 1),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (0, 3),
+            (0, 2),
+            (0, 1),
+        ];
+        assert!(!enclosed_by(&path, (2, 2)));
+        assert!(!enclosed_by(&path, (1, 1)));
+        assert_eq!(enclosed(&path), Vec::<Pos>::new());
+
+        let square = vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        assert!(enclosed_by(&square, (1, 1)));
+        assert!(!enclosed_by(&square, (3, 1)));
+        assert_eq!(enclosed(&square), vec![(1, 1)]);
+    }
+}
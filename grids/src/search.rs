@@ -0,0 +1,356 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::{Bitset, GridLike, Pos, Sparse, ORTHOGONAL};
+
+// Something to search through: a position, plus whatever else decides where it can go next (facing, how long it's
+// been going straight, ...). `Grid` is whatever successors needs to look at, usually the puzzle itself.
+pub trait State: Clone + Eq + Hash {
+    type Grid: ?Sized;
+
+    // Where this state is, for distance maps and traces
+    fn pos(&self) -> Pos;
+
+    // Every state one move from this one, and what the move costs
+    fn successors(&self, grid: &Self::Grid) -> impl IntoIterator<Item = (Self, u64)>;
+
+    // A guess at what's left to pay to reach a goal, for astar
+    // It must never guess high, or the paths found might not be the shortest
+    fn heuristic(&self, _grid: &Self::Grid) -> u64 {
+        0
+    }
+}
+
+// Plain walking: one step up, left, right or down, wherever the Bitset (of walls) isn't set
+impl State for Pos {
+    type Grid = Bitset;
+
+    fn pos(&self) -> Pos {
+        *self
+    }
+
+    fn successors(&self, walls: &Bitset) -> impl IntoIterator<Item = (Pos, u64)> {
+        let (x, y) = *self;
+        ORTHOGONAL
+            .into_iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|&n| walls.in_bounds(n) && !walls.contains(n))
+            .map(|n| (n, 1))
+    }
+}
+
+// What a search found
+// Costs are only kept for the states it finished with (the ones in the trace), so they're all the best there is.
+// Searches stop once every goal at the best cost has been reached; explore never stops, so it has them all.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    costs: HashMap<S, u64>,
+    // Every state that reaches this one at its best cost, so all the shortest paths can be rebuilt
+    parents: HashMap<S, Vec<S>>,
+    trace: Vec<S>,
+    goals: Vec<S>,
+}
+
+impl<S: State> Search<S> {
+    // What the goals cost, None if none were reached
+    pub fn cost(&self) -> Option<u64> {
+        self.goals.first().map(|goal| self.costs[goal])
+    }
+
+    pub fn cost_to(&self, state: &S) -> Option<u64> {
+        self.costs.get(state).copied()
+    }
+
+    // The goals reached, all at the same (best) cost
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    // Every state the search finished with, in the order it did, for renders to play back
+    pub fn trace(&self) -> &[S] {
+        &self.trace
+    }
+
+    // One shortest path, start to goal
+    pub fn path(&self) -> Option<Vec<S>> {
+        let mut state = self.goals.first()?;
+        let mut path = vec![state.clone()];
+        while let Some(parents) = self.parents.get(state) {
+            state = &parents[0];
+            path.push(state.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Every shortest path, start to goal
+    // There can be a lot of these; on_shortest_paths is cheaper if which states they go through is enough
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = vec![];
+        for goal in &self.goals {
+            self.paths_to(goal, &mut vec![goal.clone()], &mut paths);
+        }
+        paths
+    }
+
+    // `reversed` is the path from `state` to the goal, backwards
+    fn paths_to(&self, state: &S, reversed: &mut Vec<S>, paths: &mut Vec<Vec<S>>) {
+        let Some(parents) = self.parents.get(state) else {
+            paths.push(reversed.iter().rev().cloned().collect());
+            return;
+        };
+        for parent in parents {
+            reversed.push(parent.clone());
+            self.paths_to(parent, reversed, paths);
+            reversed.pop();
+        }
+    }
+
+    // Every state on at least one shortest path
+    pub fn on_shortest_paths(&self) -> HashSet<S> {
+        let mut seen = self.goals.iter().cloned().collect::<HashSet<_>>();
+        let mut stack = self.goals.clone();
+        while let Some(state) = stack.pop() {
+            for parent in self.parents.get(&state).into_iter().flatten() {
+                if seen.insert(parent.clone()) {
+                    stack.push(parent.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    // The cheapest way to each position, whatever else the state had going on there
+    pub fn distances(&self) -> Sparse<u64> {
+        let mut best = HashMap::new();
+        for (state, &cost) in &self.costs {
+            best.entry(state.pos())
+                .and_modify(|c: &mut u64| *c = (*c).min(cost))
+                .or_insert(cost);
+        }
+        best.into_iter().collect()
+    }
+}
+
+// Cheapest first, stopping at the goals
+pub fn dijkstra<S: State>(
+    grid: &S::Grid,
+    starts: impl IntoIterator<Item = S>,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S> {
+    run(grid, starts, is_goal, |_| 0, |cost| cost)
+}
+
+// Like dijkstra, but trying states that look closer to a goal (by State::heuristic) first
+pub fn astar<S: State>(
+    grid: &S::Grid,
+    starts: impl IntoIterator<Item = S>,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S> {
+    run(
+        grid,
+        starts,
+        is_goal,
+        |s: &S| s.heuristic(grid),
+        |cost| cost,
+    )
+}
+
+// Fewest moves rather than cheapest: every move counts as 1, whatever successors says
+pub fn bfs<S: State>(
+    grid: &S::Grid,
+    starts: impl IntoIterator<Item = S>,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S> {
+    run(grid, starts, is_goal, |_| 0, |_| 1)
+}
+
+// Everywhere reachable, for distance maps
+pub fn explore<S: State>(grid: &S::Grid, starts: impl IntoIterator<Item = S>) -> Search<S> {
+    dijkstra(grid, starts, |_| false)
+}
+
+fn run<S: State>(
+    grid: &S::Grid,
+    starts: impl IntoIterator<Item = S>,
+    is_goal: impl Fn(&S) -> bool,
+    heuristic: impl Fn(&S) -> u64,
+    step_cost: impl Fn(u64) -> u64,
+) -> Search<S> {
+    let mut costs = HashMap::new();
+    let mut parents: HashMap<S, Vec<S>> = HashMap::new();
+    let mut done = HashSet::new();
+    let mut trace = vec![];
+    let mut goals = vec![];
+    let mut best = None;
+
+    // States aren't Ord, so the heap holds (estimate, cost, index into queued)
+    let mut queued = vec![];
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if costs.insert(start.clone(), 0).is_none() {
+            heap.push(Reverse((heuristic(&start), 0, queued.len())));
+            queued.push(start);
+        }
+    }
+
+    while let Some(Reverse((estimate, cost, i))) = heap.pop() {
+        // Anything left can only reach a goal the long way round
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+
+        let state = queued[i].clone();
+        if cost > costs[&state] || !done.insert(state.clone()) {
+            continue;
+        }
+        trace.push(state.clone());
+
+        // Nothing past a goal can be a shorter way to one
+        if is_goal(&state) {
+            best = Some(cost);
+            goals.push(state);
+            continue;
+        }
+
+        for (next, step) in state.successors(grid) {
+            let next_cost = cost + step_cost(step);
+            match costs.get(&next) {
+                Some(&c) if c < next_cost => {}
+                // Another way in at the same cost (unless it's already done, which would make a loop)
+                Some(&c) if c == next_cost => {
+                    if !done.contains(&next) {
+                        parents.entry(next).or_default().push(state.clone());
+                    }
+                }
+                _ => {
+                    costs.insert(next.clone(), next_cost);
+                    parents.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Reverse((
+                        next_cost + heuristic(&next),
+                        next_cost,
+                        queued.len(),
+                    )));
+                    queued.push(next);
+                }
+            }
+        }
+    }
+
+    costs.retain(|state, _| done.contains(state));
+    Search {
+        costs,
+        parents,
+        trace,
+        goals,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Walking a maze, but turning costs 10
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Walker(Pos, Pos);
+
+    impl State for Walker {
+        type Grid = Bitset;
+
+        fn pos(&self) -> Pos {
+            self.0
+        }
+
+        fn successors(&self, walls: &Bitset) -> impl IntoIterator<Item = (Walker, u64)> {
+            let Walker((x, y), (dx, dy)) = *self;
+            let ahead = (x + dx, y + dy);
+            let walk = (walls.in_bounds(ahead) && !walls.contains(ahead))
+                .then_some((Walker(ahead, (dx, dy)), 1));
+            [(-dy, dx), (dy, -dx)]
+                .into_iter()
+                .map(move |turn| (Walker((x, y), turn), 10))
+                .chain(walk)
+        }
+
+        fn heuristic(&self, walls: &Bitset) -> u64 {
+            let (x, y) = self.0;
+            (walls.bounds().max_x - x + walls.bounds().max_y - y) as u64
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let walls = Bitset::read("...\n.#.\n...\n", |c| c == '#');
+        let corner = |p: &Pos| *p == (2, 2);
+
+        let found = bfs(&walls, [(0, 0)], corner);
+        assert_eq!(found.cost(), Some(4));
+        assert_eq!(found.paths().len(), 2);
+        assert_eq!(found.path().map(|path| path.len()), Some(5));
+        assert_eq!(found.on_shortest_paths().len(), 8);
+        assert_eq!(found.trace().first(), Some(&(0, 0)));
+        assert_eq!(found.trace().last(), Some(&(2, 2)));
+
+        let distances = explore(&walls, [(0, 0)]).distances();
+        assert_eq!(distances.len(), 8);
+        assert_eq!(distances.get((2, 2)), Some(&4));
+        assert_eq!(distances.get((1, 1)), None);
+
+        // Going down first means turning twice, so there's only one best way, and astar agrees with dijkstra
+        let start = Walker((0, 0), (1, 0));
+        let goal = |w: &Walker| w.0 == (2, 2);
+        let slow = dijkstra(&walls, [start.clone()], goal);
+        let fast = astar(&walls, [start], goal);
+        assert_eq!(slow.cost(), Some(14));
+        assert_eq!(fast.cost(), Some(14));
+        assert_eq!(slow.paths(), fast.paths());
+        assert_eq!(fast.paths().len(), 1);
+        assert!(fast.trace().len() <= slow.trace().len());
+
+        // Walled off
+        let walls = Bitset::read(".#.\n##.\n", |c| c == '#');
+        let found = bfs(&walls, [(0, 0)], corner);
+        assert_eq!(found.cost(), None);
+        assert_eq!(found.path(), None);
+        assert!(found.paths().is_empty());
+    }
+
+    #[test]
+    fn test_equal_paths() {
+        // Four ways round the wall to the far corner, all 5 long: three along the top and one down the side
+        let walls = Bitset::read("....\n.#..\n....\n", |c| c == '#');
+        let corner = |p: &Pos| *p == (3, 2);
+
+        let found = bfs(&walls, [(0, 0)], corner);
+        assert_eq!(found.cost(), Some(5));
+        let paths = found.paths();
+        assert_eq!(paths.len(), 4);
+        assert!(paths.iter().all(|path| path.len() == 6));
+        assert!(paths
+            .iter()
+            .all(|path| path[0] == (0, 0) && path[5] == (3, 2)));
+        assert_eq!(paths.iter().collect::<HashSet<_>>().len(), 4);
+        assert!(paths.contains(&vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)]));
+        assert!(paths.contains(&found.path().unwrap()));
+
+        // Every cell but the wall is on one of them
+        let on = found.on_shortest_paths();
+        assert_eq!(on.len(), 11);
+        assert_eq!(on, paths.iter().flatten().copied().collect());
+
+        // Weighted searches find the same ones
+        for other in [
+            dijkstra(&walls, [(0, 0)], corner),
+            astar(&walls, [(0, 0)], corner),
+        ] {
+            assert_eq!(other.cost(), Some(5));
+            let mut other = other.paths();
+            let mut paths = paths.clone();
+            other.sort();
+            paths.sort();
+            assert_eq!(other, paths);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::{Bounds, GridLike, Pos, ORTHOGONAL};
+
+// Only the cells that have something in them, for mostly empty or unbounded grids
+// Kept in a HashMap for quick lookups; iterating sorts the positions so it's row-major like the other backends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparse<T> {
+    bounds: Bounds,
+    cells: HashMap<Pos, T, BuildHasherDefault<PosHasher>>,
+}
+
+// Positions are just two numbers, so SipHash (the HashMap default) is a lot of work for nothing
+// This is the same sum fxhash does, one word at a time
+#[derive(Default)]
+struct PosHasher(u64);
+
+impl Hasher for PosHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517cc1b727220a95);
+    }
+
+    fn write_i64(&mut self, n: i64) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_isize(&mut self, n: isize) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<T> Default for Sparse<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Sparse<T> {
+    pub fn new() -> Self {
+        Self::with_bounds(Bounds::EMPTY)
+    }
+
+    // Bounds start out covering `bounds` (rather than nothing) and grow from there
+    pub fn with_bounds(bounds: Bounds) -> Self {
+        Self {
+            bounds,
+            cells: HashMap::default(),
+        }
+    }
+
+    // A cell for each character `f` gives a value for
+    pub fn read(input: &str, f: impl Fn(char) -> Option<T>) -> Self {
+        let mut grid = Self::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(v) = f(c) {
+                    grid.insert((x as isize, y as isize), v);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn insert(&mut self, p: Pos, value: T) -> Option<T> {
+        self.bounds.include(p);
+        self.cells.insert(p, value)
+    }
+
+    // Bounds don't shrink back
+    pub fn remove(&mut self, p: Pos) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Row by row, like everything else; this sorts, so lookups are cheaper when order doesn't matter
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let mut cells = self.cells.iter().map(|(&p, v)| (p, v)).collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|&((x, y), _)| (y, x));
+        cells.into_iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let mut cells = self
+            .cells
+            .iter_mut()
+            .map(|(&p, v)| (p, v))
+            .collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|&((x, y), _)| (y, x));
+        cells.into_iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.iter().map(|(p, _)| p)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<T: Clone> Sparse<T> {
+    // Fill the empty cells reachable from `start` (without crossing a filled one) with `value`
+    // Stays inside the bounds, returns how many were filled
+    pub fn fill_empty(&mut self, start: Pos, value: T) -> usize {
+        let bounds = self.bounds;
+        let mut stack = vec![start];
+        let mut filled = 0;
+
+        while let Some(p) = stack.pop() {
+            if !bounds.contains(p) || self.contains(p) {
+                continue;
+            }
+            self.insert(p, value.clone());
+            filled += 1;
+            stack.extend(ORTHOGONAL.map(|(dx, dy)| (p.0 + dx, p.1 + dy)));
+        }
+
+        filled
+    }
+}
+
+impl<T> GridLike for Sparse<T> {
+    type Item = T;
+
+    fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    fn set(&mut self, p: Pos, value: T) -> bool {
+        self.insert(p, value);
+        true
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for Sparse<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (p, v) in iter {
+            grid.insert(p, v);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+#..#
+.##.
+#...
+";
+
+    #[test]
+    fn test_sparse() {
+        let mut sparse = Sparse::new();
+        assert!(sparse.is_empty());
+        assert_eq!(sparse.insert((2, -1), 'a'), None);
+        assert_eq!(sparse.insert((0, 3), 'b'), None);
+        assert_eq!(sparse.insert((2, -1), 'c'), Some('a'));
+        assert_eq!(sparse.bounds(), Bounds::new((0, -1), (2, 3)));
+
+        // Row by row, whatever order they went in
+        assert_eq!(
+            sparse.iter().collect::<Vec<_>>(),
+            vec![((2, -1), &'c'), ((0, 3), &'b')]
+        );
+
+        // Bounds don't shrink back
+        assert_eq!(sparse.remove((0, 3)), Some('b'));
+        assert!(!sparse.contains((0, 3)));
+        assert_eq!(sparse.len(), 1);
+        assert_eq!(sparse.bounds(), Bounds::new((0, -1), (2, 3)));
+    }
+
+    #[test]
+    fn test_fill_empty() {
+        // Fills the gaps between the cells that are there
+        let mut sparse = Sparse::read(INPUT, |c| (c == '#').then_some('#'));
+        assert_eq!(sparse.fill_empty((3, 2), 'o'), 4);
+        assert_eq!(sparse.to_string('.', |c| *c), "#..#\n.##o\n#ooo\n");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{Bounds, Dense, GridLike, Pos};
+
+type Matrix = [[isize; 2]; 2];
+
+const IDENTITY: Matrix = [[1, 0], [0, 1]];
+
+fn apply(m: Matrix, (x, y): Pos) -> Pos {
+    (m[0][0] * x + m[0][1] * y, m[1][0] * x + m[1][1] * y)
+}
+
+fn multiply(a: Matrix, b: Matrix) -> Matrix {
+    let column = |i: usize| apply(a, (b[0][i], b[1][i]));
+    let (c0, c1) = (column(0), column(1));
+    [[c0.0, c1.0], [c0.1, c1.1]]
+}
+
+// A rotated, flipped or cut down look at a grid, without copying it
+// Positions in a view always start at (0, 0); each one maps to `origin + matrix * position` in the grid underneath,
+// so views of views are just as cheap. Views can't be written through (set always fails), to_dense or to_sparse
+// (or the owned versions on Dense) make a copy that can.
+#[derive(Debug)]
+pub struct View<'a, G> {
+    grid: &'a G,
+    origin: Pos,
+    matrix: Matrix,
+    width: usize,
+    height: usize,
+}
+
+// derive would want G: Clone, but it's only ever borrowed
+impl<G> Clone for View<'_, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for View<'_, G> {}
+
+impl<'a, G: GridLike> View<'a, G> {
+    pub fn new(grid: &'a G) -> Self {
+        let bounds = grid.bounds();
+        Self {
+            grid,
+            origin: bounds.min(),
+            matrix: IDENTITY,
+            width: bounds.width(),
+            height: bounds.height(),
+        }
+    }
+
+    // Where `p` in this view is in the grid underneath
+    pub fn source(&self, p: Pos) -> Option<Pos> {
+        if !self.bounds().contains(p) {
+            return None;
+        }
+        let (dx, dy) = apply(self.matrix, p);
+        Some((self.origin.0 + dx, self.origin.1 + dy))
+    }
+
+    // This view's (0, 0) moved to `offset`, turned by `matrix`, and `width` by `height`
+    fn then(self, offset: Pos, matrix: Matrix, width: usize, height: usize) -> Self {
+        let (dx, dy) = apply(self.matrix, offset);
+        Self {
+            origin: (self.origin.0 + dx, self.origin.1 + dy),
+            matrix: multiply(self.matrix, matrix),
+            width,
+            height,
+            ..self
+        }
+    }
+
+    fn far(&self) -> Pos {
+        (self.width as isize - 1, self.height as isize - 1)
+    }
+
+    // Clockwise
+    pub fn rotate90(self) -> Self {
+        self.then(
+            (0, self.far().1),
+            [[0, 1], [-1, 0]],
+            self.height,
+            self.width,
+        )
+    }
+
+    pub fn rotate180(self) -> Self {
+        self.then(self.far(), [[-1, 0], [0, -1]], self.width, self.height)
+    }
+
+    pub fn rotate270(self) -> Self {
+        self.then(
+            (self.far().0, 0),
+            [[0, -1], [1, 0]],
+            self.height,
+            self.width,
+        )
+    }
+
+    // Rows become columns
+    pub fn transpose(self) -> Self {
+        self.then((0, 0), [[0, 1], [1, 0]], self.height, self.width)
+    }
+
+    // Left to right
+    pub fn flip_horizontal(self) -> Self {
+        self.then(
+            (self.far().0, 0),
+            [[-1, 0], [0, 1]],
+            self.width,
+            self.height,
+        )
+    }
+
+    // Top to bottom
+    pub fn flip_vertical(self) -> Self {
+        self.then(
+            (0, self.far().1),
+            [[1, 0], [0, -1]],
+            self.width,
+            self.height,
+        )
+    }
+
+    // Just `window` (in this view's positions, cut down to fit)
+    pub fn window(self, window: Bounds) -> Self {
+        let bounds = self.bounds();
+        let clipped = Bounds::new(
+            (window.min_x.max(0), window.min_y.max(0)),
+            (
+                window.max_x.min(bounds.max_x),
+                window.max_y.min(bounds.max_y),
+            ),
+        );
+        match clipped.is_empty() {
+            true => self.then((0, 0), IDENTITY, 0, 0),
+            false => self.then(clipped.min(), IDENTITY, clipped.width(), clipped.height()),
+        }
+    }
+
+    // Every `width` by `height` window, row by row
+    pub fn windows(self, width: usize, height: usize) -> impl Iterator<Item = View<'a, G>> {
+        let across = (self.width + 1).saturating_sub(width) as isize;
+        let down = (self.height + 1).saturating_sub(height) as isize;
+        Bounds::new((0, 0), (across - 1, down - 1))
+            .points()
+            .map(move |p| self.window(Bounds::from_size(p, width, height)))
+    }
+
+    pub fn inner(&self) -> &'a G {
+        self.grid
+    }
+}
+
+impl<G: GridLike> GridLike for View<'_, G> {
+    type Item = G::Item;
+
+    fn bounds(&self) -> Bounds {
+        Bounds::from_size((0, 0), self.width, self.height)
+    }
+
+    fn get(&self, p: Pos) -> Option<&G::Item> {
+        self.grid.get(self.source(p)?)
+    }
+
+    fn set(&mut self, _: Pos, _: G::Item) -> bool {
+        false
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &G::Item)> {
+        self.bounds()
+            .points()
+            .filter_map(|p| self.get(p).map(|v| (p, v)))
+    }
+}
+
+// The owned versions, for when the result is going to be changed or kept around
+impl<T: Clone> Dense<T> {
+    fn copy_of(view: View<Self>) -> Self {
+        Dense::from_fn(view.width, view.height, |p| view[p].clone())
+    }
+
+    pub fn rotate90(&self) -> Self {
+        Self::copy_of(self.view().rotate90())
+    }
+
+    pub fn rotate180(&self) -> Self {
+        Self::copy_of(self.view().rotate180())
+    }
+
+    pub fn rotate270(&self) -> Self {
+        Self::copy_of(self.view().rotate270())
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::copy_of(self.view().transpose())
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        Self::copy_of(self.view().flip_horizontal())
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        Self::copy_of(self.view().flip_vertical())
+    }
+
+    // `window` is in the grid's own positions, the copy starts at (0, 0)
+    pub fn sub_grid(&self, window: Bounds) -> Self {
+        let (x, y) = self.origin();
+        let local = Bounds::new(
+            (window.min_x - x, window.min_y - y),
+            (window.max_x - x, window.max_y - y),
+        );
+        Self::copy_of(self.view().window(local))
+    }
+}
+
+impl<G: GridLike> std::ops::Index<Pos> for View<'_, G> {
+    type Output = G::Item;
+
+    fn index(&self, p: Pos) -> &G::Item {
+        self.get(p)
+            .unwrap_or_else(|| panic!("nothing at {p:?} in a {}x{} view", self.width, self.height))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_views() {
+        // ab
+        // cd
+        // ef
+        let dense = Dense::read("ab\ncd\nef\n", |c| c);
+        let show = |v: View<Dense<char>>| v.to_string(' ', |c| *c);
+
+        assert_eq!(show(dense.view()), "ab\ncd\nef\n");
+        assert_eq!(show(dense.view().rotate90()), "eca\nfdb\n");
+        assert_eq!(show(dense.view().rotate180()), "fe\ndc\nba\n");
+        assert_eq!(show(dense.view().rotate270()), "bdf\nace\n");
+        assert_eq!(show(dense.view().transpose()), "ace\nbdf\n");
+        assert_eq!(show(dense.view().flip_horizontal()), "ba\ndc\nfe\n");
+        assert_eq!(show(dense.view().flip_vertical()), "ef\ncd\nab\n");
+        assert_eq!(
+            show(dense.view().rotate90().rotate90()),
+            show(dense.view().rotate180())
+        );
+        assert_eq!(
+            show(dense.view().transpose().flip_horizontal()),
+            show(dense.view().rotate90())
+        );
+        assert_eq!(
+            show(dense.view().window(Bounds::new((1, 1), (5, 5)))),
+            "d\nf\n"
+        );
+        assert_eq!(
+            show(dense.view().rotate90().window(Bounds::new((1, 0), (2, 1)))),
+            "ca\ndb\n"
+        );
+        assert_eq!(dense.view().windows(2, 2).count(), 2);
+        assert_eq!(dense.view().windows(3, 1).count(), 0);
+
+        // Owned copies match
+        assert_eq!(dense.rotate90().to_string(' ', |c| *c), "eca\nfdb\n");
+        assert_eq!(
+            dense
+                .sub_grid(Bounds::new((0, 1), (1, 1)))
+                .to_string(' ', |c| *c),
+            "cd\n"
+        );
+        let moved = dense.clone().with_origin((10, 10));
+        assert_eq!(
+            moved
+                .sub_grid(Bounds::new((10, 11), (11, 11)))
+                .to_string(' ', |c| *c),
+            "cd\n"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Bounds, GridLike, Pos, ALL_DIRECTIONS, ORTHOGONAL};
+
+// What's past the edge of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Wrap {
+    // Nothing
+    #[default]
+    Bounded,
+    // The opposite edge, so positions always come back inside
+    Toroidal,
+    // Another copy of the grid, forever; positions keep their own coordinates and tile() says which copy
+    Tiled,
+    // Like Toroidal, but around the inside of a border this thick (walls, say), which can't be reached
+    Interior(usize),
+}
+
+impl Wrap {
+    // The rectangle positions wrap around in
+    fn area(self, bounds: Bounds) -> Bounds {
+        match self {
+            Wrap::Interior(n) => {
+                let n = n as isize;
+                Bounds::new(
+                    (bounds.min_x + n, bounds.min_y + n),
+                    (bounds.max_x - n, bounds.max_y - n),
+                )
+            }
+            _ => bounds,
+        }
+    }
+
+    // Where `p` is stored in a grid covering `bounds`, None if it's off the edge (or nothing is left inside the border)
+    pub fn resolve(self, bounds: Bounds, (x, y): Pos) -> Option<Pos> {
+        let area = self.area(bounds);
+        if area.is_empty() {
+            return None;
+        }
+        match self {
+            Wrap::Bounded => area.contains((x, y)).then_some((x, y)),
+            _ => Some((
+                area.min_x + (x - area.min_x).rem_euclid(area.width() as isize),
+                area.min_y + (y - area.min_y).rem_euclid(area.height() as isize),
+            )),
+        }
+    }
+
+    // Which copy of the grid `p` is in, (0, 0) being the grid itself (and the only one for Bounded)
+    pub fn tile(self, bounds: Bounds, (x, y): Pos) -> Pos {
+        let area = self.area(bounds);
+        match self {
+            Wrap::Bounded => (0, 0),
+            _ if area.is_empty() => (0, 0),
+            _ => (
+                (x - area.min_x).div_euclid(area.width() as isize),
+                (y - area.min_y).div_euclid(area.height() as isize),
+            ),
+        }
+    }
+}
+
+// A grid read (and written) through a Wrap
+// Toroidal and Interior neighbours come back already wrapped, so flood_fill covers each cell once. Tiled grids go
+// on forever, so bounds, cells, neighbours and flood_fill stop at `limit` (the one tile unless within() says otherwise).
+// An Interior grid's border is out of bounds and left out of cells; get wraps it to the far side like anything else
+// past the edge, so what's stored there can only be reached through inner().
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wrapped<G> {
+    grid: G,
+    wrap: Wrap,
+    limit: Bounds,
+}
+
+impl<G: GridLike> Wrapped<G> {
+    pub fn new(grid: G, wrap: Wrap) -> Self {
+        let limit = grid.bounds();
+        Self { grid, wrap, limit }
+    }
+
+    // How far a Tiled grid reaches
+    pub fn within(self, limit: Bounds) -> Self {
+        Self { limit, ..self }
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+
+    // Where `p` is stored in the grid underneath
+    pub fn resolve(&self, p: Pos) -> Option<Pos> {
+        if self.wrap == Wrap::Tiled && !self.limit.contains(p) {
+            return None;
+        }
+        self.wrap.resolve(self.grid.bounds(), p)
+    }
+
+    pub fn tile(&self, p: Pos) -> Pos {
+        self.wrap.tile(self.grid.bounds(), p)
+    }
+
+    // The neighbour as flood_fill should see it: wrapped, except for Tiled grids
+    fn step(&self, (x, y): Pos, (dx, dy): Pos) -> Option<Pos> {
+        let n = (x + dx, y + dy);
+        match self.wrap {
+            Wrap::Tiled => self.resolve(n).map(|_| n),
+            _ => self.resolve(n),
+        }
+    }
+}
+
+impl<G: GridLike> GridLike for Wrapped<G> {
+    type Item = G::Item;
+
+    fn bounds(&self) -> Bounds {
+        match self.wrap {
+            Wrap::Tiled => self.limit,
+            _ => self.wrap.area(self.grid.bounds()),
+        }
+    }
+
+    fn get(&self, p: Pos) -> Option<&G::Item> {
+        self.grid.get(self.resolve(p)?)
+    }
+
+    fn set(&mut self, p: Pos, value: G::Item) -> bool {
+        match self.resolve(p) {
+            Some(p) => self.grid.set(p, value),
+            None => false,
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Pos, &G::Item)> {
+        let tiled = match self.wrap {
+            Wrap::Tiled => Some(
+                self.limit
+                    .points()
+                    .filter_map(|p| self.get(p).map(|v| (p, v))),
+            ),
+            _ => None,
+        };
+        let area = self.wrap.area(self.grid.bounds());
+        let plain = match self.wrap {
+            Wrap::Tiled => None,
+            _ => Some(self.grid.cells().filter(move |&(p, _)| area.contains(p))),
+        };
+        tiled
+            .into_iter()
+            .flatten()
+            .chain(plain.into_iter().flatten())
+    }
+
+    fn in_bounds(&self, p: Pos) -> bool {
+        self.resolve(p).is_some()
+    }
+
+    fn neighbors(&self, p: Pos) -> impl Iterator<Item = (Pos, &G::Item)> {
+        ALL_DIRECTIONS.into_iter().filter_map(move |d| {
+            let n = self.step(p, d)?;
+            self.get(n).map(|v| (n, v))
+        })
+    }
+
+    fn ortho_neighbors(&self, p: Pos) -> impl Iterator<Item = (Pos, &G::Item)> {
+        ORTHOGONAL.into_iter().filter_map(move |d| {
+            let n = self.step(p, d)?;
+            self.get(n).map(|v| (n, v))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Dense;
+
+    const INPUT: &str = "\
+#..#
+.##.
+#...
+";
+
+    #[test]
+    fn test_wrap() {
+        let bounds = Bounds::new((0, 0), (3, 2));
+        assert_eq!(Wrap::Bounded.resolve(bounds, (4, 0)), None);
+        assert_eq!(Wrap::Toroidal.resolve(bounds, (4, -1)), Some((0, 2)));
+        assert_eq!(Wrap::Tiled.resolve(bounds, (-5, 7)), Some((3, 1)));
+        assert_eq!(Wrap::Tiled.tile(bounds, (-5, 7)), (-2, 2));
+        // Inside a border one thick the interior is 2x1, (1, 1) to (2, 1)
+        assert_eq!(Wrap::Interior(1).resolve(bounds, (3, 1)), Some((1, 1)));
+        assert_eq!(Wrap::Interior(1).resolve(bounds, (0, 0)), Some((2, 1)));
+        assert_eq!(Wrap::Interior(2).resolve(bounds, (1, 1)), None);
+
+        let dense = Dense::read(INPUT, |c| c);
+        let torus = dense.clone().wrapped(Wrap::Toroidal);
+        assert_eq!(torus.get((-1, -1)), Some(&'.'));
+        assert_eq!(torus.get((4, 3)), Some(&'#'));
+        assert_eq!(torus.ortho_neighbors((0, 0)).count(), 4);
+        // (0, 0) reaches (3, 0) and (0, 2) across the edges, but (1, 1) and (2, 1) are cut off
+        let mut region = torus.flood_fill((0, 0));
+        region.sort();
+        assert_eq!(region, vec![(0, 0), (0, 2), (3, 0)]);
+
+        // The border isn't part of it: what get says is there, cells and bounds agree
+        let interior = dense.clone().wrapped(Wrap::Interior(1));
+        assert_eq!(interior.bounds(), Bounds::new((1, 1), (2, 1)));
+        assert_eq!(
+            interior.cells().collect::<Vec<_>>(),
+            vec![((1, 1), &'#'), ((2, 1), &'#')]
+        );
+        assert!(interior.cells().all(|(p, v)| interior.get(p) == Some(v)));
+        assert_eq!(interior.get((0, 0)), Some(&'#'));
+
+        let tiled = dense
+            .wrapped(Wrap::Tiled)
+            .within(Bounds::new((-4, 0), (7, 2)));
+        assert_eq!(tiled.get((-4, 0)), Some(&'#'));
+        assert_eq!(tiled.get((8, 0)), None);
+        assert_eq!(tiled.tile((-4, 0)), (-1, 0));
+        assert_eq!(tiled.cells().count(), 36);
+        // (3, 1) leads into the next tile over at (4, 1)
+        assert_eq!(tiled.inner().flood_fill((2, 2)).len(), 4);
+        assert_eq!(tiled.flood_fill((2, 2)).len(), 5);
+    }
+}
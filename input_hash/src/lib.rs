@@ -0,0 +1,22 @@
+// FNV-1a, so the hash is stable across builds and Rust versions (unlike std's hashers)
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in input.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        // FNV-1a's published test vectors, names already on disk depend on these
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(input_hash("foobar"), "85944171f73967e8");
+    }
+}
@@ -0,0 +1,352 @@
+// Text and shapes drawn on top of a rendered grid
+// Positions are in grid coordinates (one cell per pixel of the unscaled image), but everything is drawn after
+// the image has been scaled up, so text and lines stay one crisp pixel wide instead of turning into blocks
+
+pub mod font;
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [255, 255, 255];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    // Top left corner at the top left of the cell, `size` times the font's own 5x7
+    Text {
+        x: i32,
+        y: i32,
+        text: String,
+        size: u32,
+        color: Color,
+    },
+    // From the centre of one cell to the centre of another
+    Line {
+        from: (i32, i32),
+        to: (i32, i32),
+        color: Color,
+    },
+    // Around (or over) whole cells
+    Rect {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        color: Color,
+        filled: bool,
+    },
+    // Centred on a cell, radius in cells
+    Circle {
+        center: (i32, i32),
+        radius: f32,
+        color: Color,
+        filled: bool,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overlay {
+    pub shapes: Vec<Shape>,
+}
+
+impl Overlay {
+    pub fn new() -> Overlay {
+        Overlay::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn text(self, x: i32, y: i32, text: impl ToString, color: Color) -> Overlay {
+        self.sized_text(x, y, 1, text, color)
+    }
+
+    pub fn sized_text(
+        mut self,
+        x: i32,
+        y: i32,
+        size: u32,
+        text: impl ToString,
+        color: Color,
+    ) -> Overlay {
+        self.shapes.push(Shape::Text {
+            x,
+            y,
+            text: text.to_string(),
+            size: size.max(1),
+            color,
+        });
+        self
+    }
+
+    pub fn line(mut self, from: (i32, i32), to: (i32, i32), color: Color) -> Overlay {
+        self.shapes.push(Shape::Line { from, to, color });
+        self
+    }
+
+    // A path through several cells, e.g. the route found by a search
+    pub fn path(self, points: impl IntoIterator<Item = (i32, i32)>, color: Color) -> Overlay {
+        let points = points.into_iter().collect::<Vec<_>>();
+        points
+            .windows(2)
+            .fold(self, |overlay, w| overlay.line(w[0], w[1], color))
+    }
+
+    pub fn rect(mut self, x: i32, y: i32, width: u32, height: u32, color: Color) -> Overlay {
+        self.shapes.push(Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+            filled: false,
+        });
+        self
+    }
+
+    pub fn fill_rect(mut self, x: i32, y: i32, width: u32, height: u32, color: Color) -> Overlay {
+        self.shapes.push(Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+            filled: true,
+        });
+        self
+    }
+
+    pub fn circle(mut self, center: (i32, i32), radius: f32, color: Color) -> Overlay {
+        self.shapes.push(Shape::Circle {
+            center,
+            radius,
+            color,
+            filled: false,
+        });
+        self
+    }
+
+    pub fn fill_circle(mut self, center: (i32, i32), radius: f32, color: Color) -> Overlay {
+        self.shapes.push(Shape::Circle {
+            center,
+            radius,
+            color,
+            filled: true,
+        });
+        self
+    }
+
+    // Draw onto a `width` x `height` image that's already been scaled up by `scale`, one pixel at a time through `put`
+    // Anything off the edge of the image is clipped
+    pub fn draw(&self, width: u32, height: u32, scale: u32, put: &mut dyn FnMut(u32, u32, Color)) {
+        let mut canvas = Canvas {
+            width: width as i64,
+            height: height as i64,
+            scale: scale.max(1) as i64,
+            put,
+        };
+        for shape in &self.shapes {
+            canvas.shape(shape);
+        }
+    }
+}
+
+struct Canvas<'a> {
+    width: i64,
+    height: i64,
+    scale: i64,
+    put: &'a mut dyn FnMut(u32, u32, Color),
+}
+
+impl Canvas<'_> {
+    fn put(&mut self, x: i64, y: i64, color: Color) {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            (self.put)(x as u32, y as u32, color);
+        }
+    }
+
+    // The pixel at the middle of a cell
+    fn center(&self, (x, y): (i32, i32)) -> (i64, i64) {
+        (
+            x as i64 * self.scale + self.scale / 2,
+            y as i64 * self.scale + self.scale / 2,
+        )
+    }
+
+    fn shape(&mut self, shape: &Shape) {
+        match shape {
+            Shape::Text {
+                x,
+                y,
+                text,
+                size,
+                color,
+            } => {
+                let (x, y) = (*x as i64 * self.scale, *y as i64 * self.scale);
+                // A one pixel shadow keeps text readable whatever it's drawn over
+                let luma = color[0] as u32 * 299 + color[1] as u32 * 587 + color[2] as u32 * 114;
+                let shadow = if luma > 128_000 { BLACK } else { WHITE };
+                self.text(x + 1, y + 1, text, *size as i64, shadow);
+                self.text(x, y, text, *size as i64, *color);
+            }
+            Shape::Line { from, to, color } => {
+                let from = self.center(*from);
+                let to = self.center(*to);
+                self.line(from, to, *color);
+            }
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+                filled,
+            } => {
+                let (left, top) = (*x as i64 * self.scale, *y as i64 * self.scale);
+                let right = left + *width as i64 * self.scale - 1;
+                let bottom = top + *height as i64 * self.scale - 1;
+                for py in top..=bottom {
+                    for px in left..=right {
+                        let edge = px == left || px == right || py == top || py == bottom;
+                        if *filled || edge {
+                            self.put(px, py, *color);
+                        }
+                    }
+                }
+            }
+            Shape::Circle {
+                center,
+                radius,
+                color,
+                filled,
+            } => {
+                let (cx, cy) = self.center(*center);
+                let r = *radius as f64 * self.scale as f64;
+                let reach = r.ceil() as i64 + 1;
+                for py in cy - reach..=cy + reach {
+                    for px in cx - reach..=cx + reach {
+                        let d = (((px - cx).pow(2) + (py - cy).pow(2)) as f64).sqrt();
+                        if (*filled && d <= r) || (d - r).abs() < 0.5 {
+                            self.put(px, py, *color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Bresenham
+    fn line(&mut self, (mut x, mut y): (i64, i64), (x1, y1): (i64, i64), color: Color) {
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.put(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn text(&mut self, x: i64, y: i64, text: &str, size: i64, color: Color) {
+        let advance = (font::WIDTH + font::SPACING) as i64 * size;
+        let line_height = (font::HEIGHT + font::SPACING) as i64 * size;
+
+        for (row, line) in text.lines().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                let left = x + column as i64 * advance;
+                let top = y + row as i64 * line_height;
+                for (gy, bits) in font::glyph(c).iter().enumerate() {
+                    for gx in 0..font::WIDTH {
+                        if bits & (1 << (font::WIDTH - 1 - gx)) == 0 {
+                            continue;
+                        }
+                        for sy in 0..size {
+                            for sx in 0..size {
+                                self.put(
+                                    left + gx as i64 * size + sx,
+                                    top + gy as i64 * size + sy,
+                                    color,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Draw into a plain buffer of pixels, None where nothing was drawn
+    fn draw(overlay: &Overlay, width: u32, height: u32, scale: u32) -> Vec<Vec<Option<Color>>> {
+        let mut pixels = vec![vec![None; width as usize]; height as usize];
+        overlay.draw(width, height, scale, &mut |x, y, c| {
+            pixels[y as usize][x as usize] = Some(c)
+        });
+        pixels
+    }
+
+    #[test]
+    fn test_rect() {
+        let red = [255, 0, 0];
+        let pixels = draw(&Overlay::new().rect(1, 1, 2, 1, red), 12, 8, 2);
+
+        // Cells (1, 1) and (2, 1) at scale 2 cover pixels x 2..=5, y 2..=3
+        assert_eq!(pixels[2][2], Some(red));
+        assert_eq!(pixels[3][5], Some(red));
+        assert_eq!(pixels[2][6], None);
+        assert_eq!(pixels[1][2], None);
+    }
+
+    #[test]
+    fn test_line() {
+        let pixels = draw(&Overlay::new().line((0, 0), (3, 0), WHITE), 8, 2, 2);
+        // Cell centres are (1, 1) and (7, 1)
+        let row = pixels[1].iter().map(|p| p.is_some()).collect::<Vec<_>>();
+        assert_eq!(row, vec![false, true, true, true, true, true, true, true]);
+    }
+
+    #[test]
+    fn test_circle() {
+        let pixels = draw(&Overlay::new().fill_circle((2, 2), 1.0, WHITE), 5, 5, 1);
+        assert!(pixels[2][2].is_some());
+        assert!(pixels[1][2].is_some());
+        assert!(pixels[0][0].is_none());
+    }
+
+    #[test]
+    fn test_text() {
+        let pixels = draw(&Overlay::new().text(0, 0, "|", WHITE), 8, 9, 1);
+
+        // The bar is in the middle column of the glyph, with a black shadow one pixel down and right
+        assert_eq!(pixels[0][2], Some(WHITE));
+        assert_eq!(pixels[6][2], Some(WHITE));
+        assert_eq!(pixels[7][3], Some(BLACK));
+        assert_eq!(pixels[0][0], None);
+    }
+
+    #[test]
+    fn test_clipped() {
+        // Nothing panics or wraps around when shapes hang off the edge
+        let overlay = Overlay::new()
+            .text(-2, -2, "clipped", WHITE)
+            .line((-5, -5), (20, 20), WHITE)
+            .circle((0, 0), 10.0, WHITE);
+        draw(&overlay, 10, 10, 3);
+    }
+}
@@ -0,0 +1,200 @@
+// A single standalone HTML page to step through a render frame by frame: a canvas, a slider, play/pause and each
+// frame's label underneath
+// Frames are PNGs, and only the part that changed since the frame before, with a full keyframe every so often so
+// scrubbing backwards is quick
+
+use std::io;
+use std::path::Path;
+
+pub const KEYFRAME_EVERY: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct Scrubber {
+    width: u32,
+    height: u32,
+    fps: u32,
+    scale: u32,
+    entries: Vec<serde_json::Value>,
+    previous: Option<Vec<u8>>,
+}
+
+impl Scrubber {
+    // Every frame has to be `width` by `height`
+    pub fn new(width: u32, height: u32) -> Scrubber {
+        Scrubber {
+            width,
+            height,
+            fps: 10,
+            scale: 1,
+            entries: vec![],
+            previous: None,
+        }
+    }
+
+    // How fast play goes
+    pub fn fps(self, fps: u32) -> Scrubber {
+        Scrubber {
+            fps: fps.max(1),
+            ..self
+        }
+    }
+
+    // How much bigger the page shows frames than they are (without smoothing), so small grids can go in as they are
+    pub fn scale(self, scale: u32) -> Scrubber {
+        Scrubber {
+            scale: scale.max(1),
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // `rgb` is the frame row by row, three bytes a pixel (what image's RgbImage::as_raw gives)
+    pub fn push(&mut self, rgb: &[u8], label: Option<&str>) -> io::Result<()> {
+        assert_eq!(
+            rgb.len(),
+            self.width as usize * self.height as usize * 3,
+            "frame {} isn't {}x{}",
+            self.entries.len(),
+            self.width,
+            self.height
+        );
+
+        let keyframe = self.entries.len().is_multiple_of(KEYFRAME_EVERY);
+        let patch = match &self.previous {
+            Some(previous) if !keyframe => changed(self.width, previous, rgb),
+            _ => Some((0, 0, self.width, self.height)),
+        };
+        let (x, y, src) = match patch {
+            Some((x, y, width, height)) => {
+                let crop = crop(self.width, rgb, (x, y, width, height));
+                (x, y, Some(png_data_uri(width, height, &crop)?))
+            }
+            None => (0, 0, None),
+        };
+
+        self.entries.push(serde_json::json!({
+            "key": keyframe,
+            "x": x,
+            "y": y,
+            "src": src,
+            "label": label,
+        }));
+        self.previous = Some(rgb.to_vec());
+        Ok(())
+    }
+
+    pub fn to_html(&self, title: &str) -> String {
+        // Nothing in the JSON can close the script tag early
+        let entries = serde_json::to_string(&self.entries)
+            .expect("failed to serialize frames")
+            .replace("</", "<\\/");
+        include_str!("scrubber.html")
+            .replace("{{title}}", &title.replace('<', "&lt;"))
+            .replace("{{width}}", &self.width.to_string())
+            .replace("{{height}}", &self.height.to_string())
+            .replace("{{scale}}", &self.scale.to_string())
+            .replace("{{fps}}", &self.fps.to_string())
+            .replace("{{keyframe_every}}", &KEYFRAME_EVERY.to_string())
+            .replace("{{frames}}", &entries)
+    }
+
+    // Titled after the file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let title = path
+            .file_stem()
+            .map_or(String::new(), |s| s.to_string_lossy().to_string());
+        std::fs::write(path, self.to_html(&title))
+    }
+}
+
+// The smallest rectangle (x, y, width, height) containing every pixel that differs, None if nothing does
+fn changed(width: u32, previous: &[u8], frame: &[u8]) -> Option<(u32, u32, u32, u32)> {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0, 0);
+    for (i, (a, b)) in previous.chunks(3).zip(frame.chunks(3)).enumerate() {
+        if a != b {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+    (min_x != u32::MAX).then(|| (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+}
+
+fn crop(width: u32, rgb: &[u8], (x, y, w, h): (u32, u32, u32, u32)) -> Vec<u8> {
+    let row = |y: u32| {
+        let start = (y * width + x) as usize * 3;
+        &rgb[start..start + w as usize * 3]
+    };
+    (y..y + h).flat_map(row).copied().collect()
+}
+
+fn png_data_uri(width: u32, height: u32, rgb: &[u8]) -> io::Result<String> {
+    use base64::Engine;
+
+    let mut bytes = vec![];
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(rgb).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)?;
+
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 4x3, red in column `i` on dark blue
+    fn frame(i: u32) -> Vec<u8> {
+        (0..12)
+            .flat_map(|p| match p % 4 == i {
+                true => [255, 0, 0],
+                false => [0, 0, 40],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_changed() {
+        // Red moves from column 0 to 1
+        assert_eq!(changed(4, &frame(0), &frame(1)), Some((0, 0, 2, 3)));
+        assert_eq!(changed(4, &frame(0), &frame(0)), None);
+        assert_eq!(
+            crop(4, &frame(1), (1, 1, 2, 1)),
+            [255, 0, 0, 0, 0, 40].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let mut scrubber = Scrubber::new(4, 3).fps(5).scale(2);
+        for (i, label) in [(0, Some("start")), (1, None), (1, Some("</script>"))] {
+            scrubber.push(&frame(i), label).unwrap();
+        }
+        assert_eq!(scrubber.len(), 3);
+
+        let page = scrubber.to_html("test");
+        assert!(page.contains(r#"<canvas id="canvas" width="4" height="3">"#));
+        // The last frame is the same as the one before, so has no image at all
+        assert_eq!(page.matches(r#""src":"data:image/png;base64,"#).count(), 2);
+        assert_eq!(page.matches(r#""src":null"#).count(), 1);
+        assert_eq!(page.matches(r#""key":true"#).count(), 1);
+        assert!(page.contains(r#""label":"<\/script>""#));
+        assert!(!page.contains("{{"));
+    }
+}